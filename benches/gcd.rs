@@ -12,19 +12,19 @@ use bitwise::word::gcd;
 
 
 pub fn run_u8_small<F: Fn(u8, u8) -> u8>(b: &mut Bencher, f: F) {
-    b.iter(|| gcd::test_util::run_u8(0, u8::max_value() / 3,
+    b.iter(|| gcd::test_util::run_u8(0, u8::MAX / 3,
                                      |x, y| bencher::black_box(f(bencher::black_box(x),
                                                                  bencher::black_box(y)))));
 }
 
 pub fn run_u8_mid<F: Fn(u8, u8) -> u8>(b: &mut Bencher, f: F) {
-    b.iter(|| gcd::test_util::run_u8(u8::max_value() / 3, u8::max_value() / 3 * 2,
+    b.iter(|| gcd::test_util::run_u8(u8::MAX / 3, u8::MAX / 3 * 2,
                                      |x, y| bencher::black_box(f(bencher::black_box(x),
                                                                  bencher::black_box(y)))));
 }
 
 pub fn run_u8_large<F: Fn(u8, u8) -> u8>(b: &mut Bencher, f: F) {
-    b.iter(|| gcd::test_util::run_u8(u8::max_value() / 3 * 2, u8::max_value(),
+    b.iter(|| gcd::test_util::run_u8(u8::MAX / 3 * 2, u8::MAX,
                                      |x, y| bencher::black_box(f(bencher::black_box(x),
                                                                  bencher::black_box(y)))));
 }
@@ -121,19 +121,19 @@ benchmark_group!(u08_large_g,
 
 
 pub fn run_u16_small<F: Fn(u16, u16) -> u16>(b: &mut Bencher, f: F) {
-    b.iter(|| gcd::test_util::run_u16(0, u16::max_value() / 3,
+    b.iter(|| gcd::test_util::run_u16(0, u16::MAX / 3,
                                      |x, y| bencher::black_box(f(bencher::black_box(x),
                                                                  bencher::black_box(y)))));
 }
 
 pub fn run_u16_mid<F: Fn(u16, u16) -> u16>(b: &mut Bencher, f: F) {
-    b.iter(|| gcd::test_util::run_u16(u16::max_value() / 3, u16::max_value() / 3 * 2,
+    b.iter(|| gcd::test_util::run_u16(u16::MAX / 3, u16::MAX / 3 * 2,
                                      |x, y| bencher::black_box(f(bencher::black_box(x),
                                                                  bencher::black_box(y)))));
 }
 
 pub fn run_u16_large<F: Fn(u16, u16) -> u16>(b: &mut Bencher, f: F) {
-    b.iter(|| gcd::test_util::run_u16(u16::max_value() / 6 * 5 , u16::max_value(),
+    b.iter(|| gcd::test_util::run_u16(u16::MAX / 6 * 5 , u16::MAX,
                                      |x, y| bencher::black_box(f(bencher::black_box(x),
                                                                  bencher::black_box(y)))));
 }
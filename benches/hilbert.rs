@@ -0,0 +1,344 @@
+#[macro_use]
+extern crate bencher;
+
+extern crate bitwise;
+
+use bencher::Bencher;
+
+use std::fmt::Debug;
+use bitwise::word::Word;
+use bitwise::word::hilbert;
+
+use bitwise::word::morton::testing_utils::*;
+
+struct LUTEncode2D;
+impl RunnerFn for LUTEncode2D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::lut::encode_2d::<T, T>(bencher::black_box(v),
+                                                           bencher::black_box(v)));
+    }
+}
+
+fn encode_2d_u08_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&LUTEncode2D {}));
+}
+fn encode_2d_u16_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&LUTEncode2D {}));
+}
+fn encode_2d_u32_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&LUTEncode2D {}));
+}
+fn encode_2d_u64_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&LUTEncode2D {}));
+}
+
+struct SKILLINGEncode2D;
+impl RunnerFn for SKILLINGEncode2D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::skilling::encode_2d::<T, T>(bencher::black_box(v),
+                                                                bencher::black_box(v)));
+    }
+}
+
+fn encode_2d_u08_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&SKILLINGEncode2D {}));
+}
+fn encode_2d_u16_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&SKILLINGEncode2D {}));
+}
+fn encode_2d_u32_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&SKILLINGEncode2D {}));
+}
+fn encode_2d_u64_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&SKILLINGEncode2D {}));
+}
+
+struct Encode2D;
+impl RunnerFn for Encode2D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::encode_2d::<T>(bencher::black_box(v),
+                                                   bencher::black_box(v)));
+    }
+}
+
+fn encode_2d_u08(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&Encode2D {}));
+}
+fn encode_2d_u16(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&Encode2D {}));
+}
+fn encode_2d_u32(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&Encode2D {}));
+}
+fn encode_2d_u64(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&Encode2D {}));
+}
+
+struct LUTDecode2D;
+impl RunnerFn for LUTDecode2D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::lut::decode_2d::<T, T>(bencher::black_box(v)));
+    }
+}
+
+fn decode_2d_u08_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&LUTDecode2D {}));
+}
+fn decode_2d_u16_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&LUTDecode2D {}));
+}
+fn decode_2d_u32_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&LUTDecode2D {}));
+}
+fn decode_2d_u64_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&LUTDecode2D {}));
+}
+
+struct SKILLINGDecode2D;
+impl RunnerFn for SKILLINGDecode2D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::skilling::decode_2d::<T, T>(bencher::black_box(v)));
+    }
+}
+
+fn decode_2d_u08_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&SKILLINGDecode2D {}));
+}
+fn decode_2d_u16_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&SKILLINGDecode2D {}));
+}
+fn decode_2d_u32_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&SKILLINGDecode2D {}));
+}
+fn decode_2d_u64_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&SKILLINGDecode2D {}));
+}
+
+struct Decode2D;
+impl RunnerFn for Decode2D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::decode_2d::<T>(bencher::black_box(v)));
+    }
+}
+
+fn decode_2d_u08(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&Decode2D {}));
+}
+fn decode_2d_u16(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&Decode2D {}));
+}
+fn decode_2d_u32(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&Decode2D {}));
+}
+fn decode_2d_u64(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&Decode2D {}));
+}
+
+struct LUTEncode3D;
+impl RunnerFn for LUTEncode3D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::lut::encode_3d::<T, T>(bencher::black_box(v),
+                                                           bencher::black_box(v),
+                                                           bencher::black_box(v)));
+    }
+}
+
+fn encode_3d_u08_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&LUTEncode3D {}));
+}
+fn encode_3d_u16_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&LUTEncode3D {}));
+}
+fn encode_3d_u32_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&LUTEncode3D {}));
+}
+fn encode_3d_u64_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&LUTEncode3D {}));
+}
+
+struct SKILLINGEncode3D;
+impl RunnerFn for SKILLINGEncode3D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::skilling::encode_3d::<T, T>(bencher::black_box(v),
+                                                                bencher::black_box(v),
+                                                                bencher::black_box(v)));
+    }
+}
+
+fn encode_3d_u08_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&SKILLINGEncode3D {}));
+}
+fn encode_3d_u16_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&SKILLINGEncode3D {}));
+}
+fn encode_3d_u32_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&SKILLINGEncode3D {}));
+}
+fn encode_3d_u64_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&SKILLINGEncode3D {}));
+}
+
+struct Encode3D;
+impl RunnerFn for Encode3D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::encode_3d::<T>(bencher::black_box(v),
+                                                   bencher::black_box(v),
+                                                   bencher::black_box(v)));
+    }
+}
+
+fn encode_3d_u08(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&Encode3D {}));
+}
+fn encode_3d_u16(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&Encode3D {}));
+}
+fn encode_3d_u32(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&Encode3D {}));
+}
+fn encode_3d_u64(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&Encode3D {}));
+}
+
+struct LUTDecode3D;
+impl RunnerFn for LUTDecode3D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::lut::decode_3d::<T, T>(bencher::black_box(v)));
+    }
+}
+
+fn decode_3d_u08_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&LUTDecode3D {}));
+}
+fn decode_3d_u16_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&LUTDecode3D {}));
+}
+fn decode_3d_u32_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&LUTDecode3D {}));
+}
+fn decode_3d_u64_lut(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&LUTDecode3D {}));
+}
+
+struct SKILLINGDecode3D;
+impl RunnerFn for SKILLINGDecode3D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::skilling::decode_3d::<T, T>(bencher::black_box(v)));
+    }
+}
+
+fn decode_3d_u08_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&SKILLINGDecode3D {}));
+}
+fn decode_3d_u16_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&SKILLINGDecode3D {}));
+}
+fn decode_3d_u32_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&SKILLINGDecode3D {}));
+}
+fn decode_3d_u64_skilling(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&SKILLINGDecode3D {}));
+}
+
+struct Decode3D;
+impl RunnerFn for Decode3D {
+    fn run<T: Word + Debug>(&self, v: T) {
+        bencher::black_box(hilbert::decode_3d::<T>(bencher::black_box(v)));
+    }
+}
+
+fn decode_3d_u08(b: &mut Bencher) {
+    b.iter(|| Runner::run_u8(&Decode3D {}));
+}
+fn decode_3d_u16(b: &mut Bencher) {
+    b.iter(|| Runner::run_u16(&Decode3D {}));
+}
+fn decode_3d_u32(b: &mut Bencher) {
+    b.iter(|| Runner::run_u32(&Decode3D {}));
+}
+fn decode_3d_u64(b: &mut Bencher) {
+    b.iter(|| Runner::run_u64(&Decode3D {}));
+}
+
+benchmark_group!(encode_2d_u08_g,
+                 encode_2d_u08_lut,
+                 encode_2d_u08_skilling,
+                 encode_2d_u08);
+benchmark_group!(decode_2d_u08_g,
+                 decode_2d_u08_lut,
+                 decode_2d_u08_skilling,
+                 decode_2d_u08);
+benchmark_group!(encode_3d_u08_g,
+                 encode_3d_u08_lut,
+                 encode_3d_u08_skilling,
+                 encode_3d_u08);
+benchmark_group!(decode_3d_u08_g,
+                 decode_3d_u08_lut,
+                 decode_3d_u08_skilling,
+                 decode_3d_u08);
+benchmark_group!(encode_2d_u16_g,
+                 encode_2d_u16_lut,
+                 encode_2d_u16_skilling,
+                 encode_2d_u16);
+benchmark_group!(decode_2d_u16_g,
+                 decode_2d_u16_lut,
+                 decode_2d_u16_skilling,
+                 decode_2d_u16);
+benchmark_group!(encode_3d_u16_g,
+                 encode_3d_u16_lut,
+                 encode_3d_u16_skilling,
+                 encode_3d_u16);
+benchmark_group!(decode_3d_u16_g,
+                 decode_3d_u16_lut,
+                 decode_3d_u16_skilling,
+                 decode_3d_u16);
+benchmark_group!(encode_2d_u32_g,
+                 encode_2d_u32_lut,
+                 encode_2d_u32_skilling,
+                 encode_2d_u32);
+benchmark_group!(decode_2d_u32_g,
+                 decode_2d_u32_lut,
+                 decode_2d_u32_skilling,
+                 decode_2d_u32);
+benchmark_group!(encode_3d_u32_g,
+                 encode_3d_u32_lut,
+                 encode_3d_u32_skilling,
+                 encode_3d_u32);
+benchmark_group!(decode_3d_u32_g,
+                 decode_3d_u32_lut,
+                 decode_3d_u32_skilling,
+                 decode_3d_u32);
+benchmark_group!(encode_2d_u64_g,
+                 encode_2d_u64_lut,
+                 encode_2d_u64_skilling,
+                 encode_2d_u64);
+benchmark_group!(decode_2d_u64_g,
+                 decode_2d_u64_lut,
+                 decode_2d_u64_skilling,
+                 decode_2d_u64);
+benchmark_group!(encode_3d_u64_g,
+                 encode_3d_u64_lut,
+                 encode_3d_u64_skilling,
+                 encode_3d_u64);
+benchmark_group!(decode_3d_u64_g,
+                 decode_3d_u64_lut,
+                 decode_3d_u64_skilling,
+                 decode_3d_u64);
+
+
+benchmark_main!(encode_2d_u08_g,
+                decode_2d_u08_g,
+                encode_3d_u08_g,
+                decode_3d_u08_g,
+                encode_2d_u16_g,
+                decode_2d_u16_g,
+                encode_3d_u16_g,
+                decode_3d_u16_g,
+                encode_2d_u32_g,
+                decode_2d_u32_g,
+                encode_3d_u32_g,
+                decode_3d_u32_g,
+                encode_2d_u64_g,
+                decode_2d_u64_g,
+                encode_3d_u64_g,
+                decode_3d_u64_g);
@@ -0,0 +1,81 @@
+#[macro_use]
+extern crate bencher;
+
+extern crate bitwise;
+
+use bencher::Bencher;
+
+use bitwise::bits::matrix;
+
+fn rows<const N: usize>(seed: u64) -> [u64; N] {
+    let mut state = seed;
+    let mut m = [0u64; N];
+    for row in m.iter_mut() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        *row = state;
+    }
+    m
+}
+
+fn mul_64x64(b: &mut Bencher) {
+    let (x, y) = (rows::<64>(0x9E37_79B9_7F4A_7C15), rows::<64>(0x2545_F491_4F6C_DD1D));
+    let mut c = [0u64; 64];
+    b.iter(|| {
+        matrix::mul(bencher::black_box(&x), bencher::black_box(&y), &mut c);
+        bencher::black_box(&c);
+    });
+}
+
+fn mul_four_russians_64x64(b: &mut Bencher) {
+    let (x, y) = (rows::<64>(0x9E37_79B9_7F4A_7C15), rows::<64>(0x2545_F491_4F6C_DD1D));
+    let mut c = [0u64; 64];
+    b.iter(|| {
+        matrix::mul_four_russians(bencher::black_box(&x), bencher::black_box(&y), &mut c);
+        bencher::black_box(&c);
+    });
+}
+
+fn mul_64x64_fast_path(b: &mut Bencher) {
+    let (x, y) = (rows::<64>(0x9E37_79B9_7F4A_7C15), rows::<64>(0x2545_F491_4F6C_DD1D));
+    b.iter(|| {
+        bencher::black_box(matrix::mul_64x64(bencher::black_box(&x), bencher::black_box(&y)));
+    });
+}
+
+fn mul_1024x64(b: &mut Bencher) {
+    let (x, y) = (rows::<1024>(0x9E37_79B9_7F4A_7C15), rows::<64>(0x2545_F491_4F6C_DD1D));
+    let mut c = [0u64; 1024];
+    b.iter(|| {
+        matrix::mul(bencher::black_box(&x), bencher::black_box(&y), &mut c);
+        bencher::black_box(&c);
+    });
+}
+
+fn mul_four_russians_1024x64(b: &mut Bencher) {
+    let (x, y) = (rows::<1024>(0x9E37_79B9_7F4A_7C15), rows::<64>(0x2545_F491_4F6C_DD1D));
+    let mut c = [0u64; 1024];
+    b.iter(|| {
+        matrix::mul_four_russians(bencher::black_box(&x), bencher::black_box(&y), &mut c);
+        bencher::black_box(&c);
+    });
+}
+
+fn invert_64x64(b: &mut Bencher) {
+    let x = rows::<64>(0x9E37_79B9_7F4A_7C15);
+    b.iter(|| {
+        let mut m = bencher::black_box(x);
+        bencher::black_box(matrix::invert(&mut m));
+        bencher::black_box(&m);
+    });
+}
+
+fn rank_64x64(b: &mut Bencher) {
+    let x = rows::<64>(0x9E37_79B9_7F4A_7C15);
+    b.iter(|| bencher::black_box(matrix::rank(bencher::black_box(&x))));
+}
+
+benchmark_group!(matrix_g, mul_64x64, mul_four_russians_64x64, mul_64x64_fast_path,
+                 mul_1024x64, mul_four_russians_1024x64, invert_64x64, rank_64x64);
+benchmark_main!(matrix_g);
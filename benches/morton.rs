@@ -441,6 +441,187 @@ benchmark_group!(decode_3d_u64_g,
                  decode_3d_u64);
 
 
+// Batch encoding/decoding of `BATCH` indices: the loops over the scalar
+// functions are compared against the slice (struct-of-arrays) and points
+// (array-of-structs) functions.
+
+const BATCH: usize = 4096;
+
+fn batch_input<T: Word>() -> Vec<T> {
+    (0..BATCH as u64).map(|i| T::from_u64(i.wrapping_mul(0x9E37_79B9_7F4A_7C15))).collect()
+}
+
+macro_rules! batch_benches {
+    ($T:ty, $encode_2d_loop:ident, $encode_2d_slice:ident, $encode_2d_points:ident,
+     $decode_2d_loop:ident, $decode_2d_slice:ident, $decode_2d_points:ident,
+     $encode_3d_loop:ident, $encode_3d_slice:ident, $encode_3d_points:ident,
+     $decode_3d_loop:ident, $decode_3d_slice:ident, $decode_3d_points:ident) => {
+        fn $encode_2d_loop(b: &mut Bencher) {
+            let (x, y) = (batch_input::<$T>(), batch_input::<$T>());
+            let mut v = vec![0 as $T; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                for i in 0..BATCH {
+                    v[i] = morton::encode_2d(x[i], y[i]);
+                }
+                bencher::black_box(&mut v);
+            });
+        }
+        fn $encode_2d_slice(b: &mut Bencher) {
+            let (x, y) = (batch_input::<$T>(), batch_input::<$T>());
+            let mut v = vec![0 as $T; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::encode_2d_slice(&x, &y, &mut v);
+                bencher::black_box(&mut v);
+            });
+        }
+        fn $encode_2d_points(b: &mut Bencher) {
+            let points: Vec<[$T; 2]> = batch_input::<$T>().into_iter().map(|x| [x, !x]).collect();
+            let mut v = vec![0 as $T; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::encode_2d_points(&points, &mut v);
+                bencher::black_box(&mut v);
+            });
+        }
+        fn $decode_2d_loop(b: &mut Bencher) {
+            let v = batch_input::<$T>();
+            let (mut x, mut y) = (vec![0 as $T; BATCH], vec![0 as $T; BATCH]);
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                for i in 0..BATCH {
+                    let (a, b) = morton::decode_2d(v[i]);
+                    x[i] = a;
+                    y[i] = b;
+                }
+                bencher::black_box((&mut x, &mut y));
+            });
+        }
+        fn $decode_2d_slice(b: &mut Bencher) {
+            let v = batch_input::<$T>();
+            let (mut x, mut y) = (vec![0 as $T; BATCH], vec![0 as $T; BATCH]);
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::decode_2d_slice(&v, &mut x, &mut y);
+                bencher::black_box((&mut x, &mut y));
+            });
+        }
+        fn $decode_2d_points(b: &mut Bencher) {
+            let v = batch_input::<$T>();
+            let mut points = vec![[0 as $T; 2]; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::decode_2d_points(&v, &mut points);
+                bencher::black_box(&mut points);
+            });
+        }
+        fn $encode_3d_loop(b: &mut Bencher) {
+            let (x, y, z) = (batch_input::<$T>(), batch_input::<$T>(), batch_input::<$T>());
+            let mut v = vec![0 as $T; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                for i in 0..BATCH {
+                    v[i] = morton::encode_3d(x[i], y[i], z[i]);
+                }
+                bencher::black_box(&mut v);
+            });
+        }
+        fn $encode_3d_slice(b: &mut Bencher) {
+            let (x, y, z) = (batch_input::<$T>(), batch_input::<$T>(), batch_input::<$T>());
+            let mut v = vec![0 as $T; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::encode_3d_slice(&x, &y, &z, &mut v);
+                bencher::black_box(&mut v);
+            });
+        }
+        fn $encode_3d_points(b: &mut Bencher) {
+            let points: Vec<[$T; 3]> =
+                batch_input::<$T>().into_iter().map(|x| [x, !x, x >> 3]).collect();
+            let mut v = vec![0 as $T; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::encode_3d_points(&points, &mut v);
+                bencher::black_box(&mut v);
+            });
+        }
+        fn $decode_3d_loop(b: &mut Bencher) {
+            let v = batch_input::<$T>();
+            let (mut x, mut y, mut z) =
+                (vec![0 as $T; BATCH], vec![0 as $T; BATCH], vec![0 as $T; BATCH]);
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                for i in 0..BATCH {
+                    let (a, b, c) = morton::decode_3d(v[i]);
+                    x[i] = a;
+                    y[i] = b;
+                    z[i] = c;
+                }
+                bencher::black_box((&mut x, &mut y, &mut z));
+            });
+        }
+        fn $decode_3d_slice(b: &mut Bencher) {
+            let v = batch_input::<$T>();
+            let (mut x, mut y, mut z) =
+                (vec![0 as $T; BATCH], vec![0 as $T; BATCH], vec![0 as $T; BATCH]);
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::decode_3d_slice(&v, &mut x, &mut y, &mut z);
+                bencher::black_box((&mut x, &mut y, &mut z));
+            });
+        }
+        fn $decode_3d_points(b: &mut Bencher) {
+            let v = batch_input::<$T>();
+            let mut points = vec![[0 as $T; 3]; BATCH];
+            b.bytes = (BATCH * std::mem::size_of::<$T>()) as u64;
+            b.iter(|| {
+                morton::decode_3d_points(&v, &mut points);
+                bencher::black_box(&mut points);
+            });
+        }
+    }
+}
+
+batch_benches!(u32,
+               encode_2d_u32_batch_loop, encode_2d_u32_batch_slice, encode_2d_u32_batch_points,
+               decode_2d_u32_batch_loop, decode_2d_u32_batch_slice, decode_2d_u32_batch_points,
+               encode_3d_u32_batch_loop, encode_3d_u32_batch_slice, encode_3d_u32_batch_points,
+               decode_3d_u32_batch_loop, decode_3d_u32_batch_slice, decode_3d_u32_batch_points);
+batch_benches!(u64,
+               encode_2d_u64_batch_loop, encode_2d_u64_batch_slice, encode_2d_u64_batch_points,
+               decode_2d_u64_batch_loop, decode_2d_u64_batch_slice, decode_2d_u64_batch_points,
+               encode_3d_u64_batch_loop, encode_3d_u64_batch_slice, encode_3d_u64_batch_points,
+               decode_3d_u64_batch_loop, decode_3d_u64_batch_slice, decode_3d_u64_batch_points);
+
+benchmark_group!(batch_u32_g,
+                 encode_2d_u32_batch_loop,
+                 encode_2d_u32_batch_slice,
+                 encode_2d_u32_batch_points,
+                 decode_2d_u32_batch_loop,
+                 decode_2d_u32_batch_slice,
+                 decode_2d_u32_batch_points,
+                 encode_3d_u32_batch_loop,
+                 encode_3d_u32_batch_slice,
+                 encode_3d_u32_batch_points,
+                 decode_3d_u32_batch_loop,
+                 decode_3d_u32_batch_slice,
+                 decode_3d_u32_batch_points);
+benchmark_group!(batch_u64_g,
+                 encode_2d_u64_batch_loop,
+                 encode_2d_u64_batch_slice,
+                 encode_2d_u64_batch_points,
+                 decode_2d_u64_batch_loop,
+                 decode_2d_u64_batch_slice,
+                 decode_2d_u64_batch_points,
+                 encode_3d_u64_batch_loop,
+                 encode_3d_u64_batch_slice,
+                 encode_3d_u64_batch_points,
+                 decode_3d_u64_batch_loop,
+                 decode_3d_u64_batch_slice,
+                 decode_3d_u64_batch_points);
+
+
 benchmark_main!(encode_2d_u08_g,
                 decode_2d_u08_g,
                 encode_3d_u08_g,
@@ -456,4 +637,6 @@ benchmark_main!(encode_2d_u08_g,
                 encode_2d_u64_g,
                 decode_2d_u64_g,
                 encode_3d_u64_g,
-                decode_3d_u64_g);
+                decode_3d_u64_g,
+                batch_u32_g,
+                batch_u64_g);
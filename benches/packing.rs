@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate bencher;
+
+extern crate bitwise;
+
+use bencher::Bencher;
+
+use bitwise::bits::packing;
+
+const BLOCKS: usize = 64;
+
+fn values(width: u32) -> Vec<u64> {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    (0..BLOCKS * 256).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        if width == 64 { state } else { state & ((1 << width) - 1) }
+    }).collect()
+}
+
+macro_rules! packing_benches {
+    ($($pack:ident, $unpack:ident: $len:expr, $width:expr;)*) => {
+        $(
+            fn $pack(b: &mut Bencher) {
+                let values = values($width);
+                let mut packed = vec![0u64; BLOCKS * $len];
+                b.iter(|| {
+                    for (v, p) in values.chunks($len).zip(packed.chunks_mut($len)) {
+                        packing::pack(bencher::black_box(v), $width, p);
+                    }
+                    bencher::black_box(&packed);
+                });
+                b.bytes = (BLOCKS * $len * 8) as u64;
+            }
+
+            fn $unpack(b: &mut Bencher) {
+                let values = values($width);
+                let mut packed = vec![0u64; BLOCKS * $len];
+                for (v, p) in values.chunks($len).zip(packed.chunks_mut($len)) {
+                    packing::pack(v, $width, p);
+                }
+                let mut unpacked = vec![0u64; BLOCKS * $len];
+                b.iter(|| {
+                    for (p, v) in packed.chunks($len).zip(unpacked.chunks_mut($len)) {
+                        packing::unpack(bencher::black_box(p), $width, v);
+                    }
+                    bencher::black_box(&unpacked);
+                });
+                b.bytes = (BLOCKS * $len * 8) as u64;
+            }
+        )*
+        benchmark_group!(packing_g, $($pack, $unpack),*);
+    }
+}
+
+packing_benches! {
+    pack_128_w03, unpack_128_w03: 128, 3;
+    pack_128_w22, unpack_128_w22: 128, 22;
+    pack_256_w03, unpack_256_w03: 256, 3;
+    pack_256_w22, unpack_256_w22: 256, 22;
+    pack_256_w47, unpack_256_w47: 256, 47;
+    pack_256_w64, unpack_256_w64: 256, 64;
+}
+
+benchmark_main!(packing_g);
@@ -0,0 +1,388 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::ops::RangeBounds;
+
+use word::{self, UnsignedWord};
+use super::{bounds, word_masks, find_first, find_last, Ones};
+
+/// Fixed-capacity set of bits stored in an array of `N` words of type `W`.
+///
+/// The capacity is `N * W::bit_size()` bits. Accessing a bit outside of the
+/// capacity panics.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::BitArray;
+///
+/// let mut a = BitArray::<u64, 2>::new();
+/// a.set_bit(3);
+/// a.set_bits(60..70);
+/// assert!(a.test_bit(3) && a.test_bit(64) && !a.test_bit(70));
+/// assert_eq!(a.count_ones(), 11);
+/// assert_eq!(a.find_first_set(), Some(3));
+/// assert_eq!(a.find_first_clear(), Some(0));
+///
+/// let b = !a & BitArray::from_words([0xF, 0]);
+/// assert_eq!(b.iter_ones().collect::<Vec<_>>(), [0, 1, 2]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitArray<W: UnsignedWord, const N: usize> {
+    words: [W; N],
+}
+
+impl<W: UnsignedWord, const N: usize> Default for BitArray<W, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: UnsignedWord, const N: usize> BitArray<W, N> {
+    /// Bit array with all bits cleared.
+    #[inline]
+    pub fn new() -> Self {
+        BitArray { words: [W::zero(); N] }
+    }
+
+    /// Bit array with all bits set.
+    #[inline]
+    pub fn full() -> Self {
+        BitArray { words: [!W::zero(); N] }
+    }
+
+    /// Bit array with the bits of `words`.
+    #[inline]
+    pub fn from_words(words: [W; N]) -> Self {
+        BitArray { words }
+    }
+
+    /// Returns the words of the bit array.
+    #[inline]
+    pub fn into_words(self) -> [W; N] {
+        self.words
+    }
+
+    /// The words of the bit array.
+    #[inline]
+    pub fn as_words(&self) -> &[W] {
+        &self.words
+    }
+
+    /// The words of the bit array.
+    #[inline]
+    pub fn as_mut_words(&mut self) -> &mut [W] {
+        &mut self.words
+    }
+
+    /// Number of bits of the bit array.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N * W::bit_size().to_usize()
+    }
+
+    /// Word index and bit offset within the word of bit `bit`.
+    #[inline]
+    fn locate(&self, bit: usize) -> (usize, u32) {
+        assert!(bit < self.capacity(), "bit {} is out of bounds ({} bits)", bit,
+                self.capacity());
+        let bits = W::bit_size().to_usize();
+        (bit / bits, (bit % bits) as u32)
+    }
+
+    /// Is bit `bit` set?
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= capacity()`.
+    #[inline]
+    pub fn test_bit(&self, bit: usize) -> bool {
+        let (i, b) = self.locate(bit);
+        word::test_bit(self.words[i], b)
+    }
+
+    /// Sets bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= capacity()`.
+    #[inline]
+    pub fn set_bit(&mut self, bit: usize) {
+        let (i, b) = self.locate(bit);
+        self.words[i] = word::set_bit(self.words[i], b);
+    }
+
+    /// Clears bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= capacity()`.
+    #[inline]
+    pub fn clear_bit(&mut self, bit: usize) {
+        let (i, b) = self.locate(bit);
+        self.words[i] = word::clear_bit(self.words[i], b);
+    }
+
+    /// Flips bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= capacity()`.
+    #[inline]
+    pub fn flip_bit(&mut self, bit: usize) {
+        let (i, b) = self.locate(bit);
+        self.words[i] = word::flip_bit(self.words[i], b);
+    }
+
+    /// Sets bit `bit` if `value` is true, and clears it otherwise.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= capacity()`.
+    #[inline]
+    pub fn assign_bit(&mut self, bit: usize, value: bool) {
+        if value {
+            self.set_bit(bit)
+        } else {
+            self.clear_bit(bit)
+        }
+    }
+
+    /// Are all bits in `range` set? True for empty ranges.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn test_all<R: RangeBounds<usize>>(&self, range: R) -> bool {
+        let (start, end) = bounds(range, self.capacity());
+        word_masks(start, end).all(|(i, mask)| self.words[i] & mask == mask)
+    }
+
+    /// Is any bit in `range` set? False for empty ranges.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn test_any<R: RangeBounds<usize>>(&self, range: R) -> bool {
+        let (start, end) = bounds(range, self.capacity());
+        word_masks(start, end).any(|(i, mask)| self.words[i] & mask != W::zero())
+    }
+
+    /// Sets all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn set_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(range, self.capacity());
+        for (i, mask) in word_masks::<W>(start, end) {
+            self.words[i] = self.words[i] | mask;
+        }
+    }
+
+    /// Clears all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn clear_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(range, self.capacity());
+        for (i, mask) in word_masks::<W>(start, end) {
+            self.words[i] = self.words[i] & !mask;
+        }
+    }
+
+    /// Flips all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn flip_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(range, self.capacity());
+        for (i, mask) in word_masks::<W>(start, end) {
+            self.words[i] = self.words[i] ^ mask;
+        }
+    }
+
+    /// Number of set bits.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|&w| word::count_ones(w).to_usize()).sum()
+    }
+
+    /// Number of cleared bits.
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.capacity() - self.count_ones()
+    }
+
+    /// Are all bits cleared?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == W::zero())
+    }
+
+    /// Position of the first set bit, if any.
+    #[inline]
+    pub fn find_first_set(&self) -> Option<usize> {
+        find_first(&self.words, 0, self.capacity(), |w| w)
+    }
+
+    /// Position of the first cleared bit, if any.
+    #[inline]
+    pub fn find_first_clear(&self) -> Option<usize> {
+        find_first(&self.words, 0, self.capacity(), |w| !w)
+    }
+
+    /// Position of the last set bit, if any.
+    #[inline]
+    pub fn find_last_set(&self) -> Option<usize> {
+        find_last(&self.words, 0, self.capacity())
+    }
+
+    /// Iterator over the positions of the set bits in increasing order.
+    #[inline]
+    pub fn iter_ones(&self) -> Ones<'_, W> {
+        Ones::new(&self.words)
+    }
+}
+
+impl<'a, W: UnsignedWord, const N: usize> IntoIterator for &'a BitArray<W, N> {
+    type Item = usize;
+    type IntoIter = Ones<'a, W>;
+    #[inline]
+    fn into_iter(self) -> Ones<'a, W> {
+        self.iter_ones()
+    }
+}
+
+impl<W: UnsignedWord, const N: usize> Not for BitArray<W, N> {
+    type Output = Self;
+    #[inline]
+    fn not(mut self) -> Self {
+        for w in self.words.iter_mut() {
+            *w = !*w;
+        }
+        self
+    }
+}
+
+/// Implements the binary operator `$Op` and its assignment version between
+/// bit arrays word-wise.
+macro_rules! bit_array_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident) => {
+        impl<W: UnsignedWord, const N: usize> $OpAssign for BitArray<W, N> {
+            #[inline]
+            fn $op_assign(&mut self, other: Self) {
+                for (w, &o) in self.words.iter_mut().zip(other.words.iter()) {
+                    *w = $Op::$op(*w, o);
+                }
+            }
+        }
+
+        impl<W: UnsignedWord, const N: usize> $Op for BitArray<W, N> {
+            type Output = Self;
+            #[inline]
+            fn $op(mut self, other: Self) -> Self {
+                $OpAssign::$op_assign(&mut self, other);
+                self
+            }
+        }
+    }
+}
+
+bit_array_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+bit_array_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+bit_array_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    /// Bit array of 80 bits with its model.
+    fn make(words: [u16; 5]) -> (BitArray<u16, 5>, [bool; 80]) {
+        let mut model = [false; 80];
+        for (i, b) in model.iter_mut().enumerate() {
+            *b = words[i / 16] & (1 << (i % 16)) != 0;
+        }
+        (BitArray::from_words(words), model)
+    }
+
+    fn matches(a: &BitArray<u16, 5>, model: &[bool; 80]) -> bool {
+        (0..80).all(|i| a.test_bit(i) == model[i]) &&
+        a.count_ones() == model.iter().filter(|&&b| b).count() &&
+        a.find_first_set() == model.iter().position(|&b| b) &&
+        a.find_first_clear() == model.iter().position(|&b| !b) &&
+        a.find_last_set() == model.iter().rposition(|&b| b) &&
+        a.iter_ones().eq((0..80).filter(|&i| model[i])) &&
+        a.is_empty() == model.iter().all(|&b| !b)
+    }
+
+    #[test]
+    fn prop_bit_array() {
+        fn inner(w: (u16, u16, u16, u16, u16), start: usize, len: usize, bit: usize) -> bool {
+            let words = [w.0, !w.1, w.2, w.3 << 8, !w.4];
+            let (start, bit) = (start % 80, bit % 80);
+            let end = start + len % (80 - start + 1);
+            let (mut a, mut model) = make(words);
+            if !matches(&a, &model) ||
+               a.test_all(start..end) != model[start..end].iter().all(|&b| b) ||
+               a.test_any(start..end) != model[start..end].iter().any(|&b| b) {
+                return false;
+            }
+
+            a.flip_bit(bit);
+            model[bit] = !model[bit];
+            a.flip_bits(start..end);
+            for b in &mut model[start..end] {
+                *b = !*b;
+            }
+            if !matches(&a, &model) {
+                return false;
+            }
+            a.set_bits(start..end);
+            a.clear_bits(bit..=bit);
+            for b in &mut model[start..end] {
+                *b = true;
+            }
+            model[bit] = false;
+            if !matches(&a, &model) {
+                return false;
+            }
+
+            let (b, b_model) = make([w.4, w.3, !w.2, w.1, w.0]);
+            let (and, or, xor, not) = (a & b, a | b, a ^ b, !a);
+            (0..80).all(|i| {
+                and.test_bit(i) == (model[i] && b_model[i]) &&
+                or.test_bit(i) == (model[i] || b_model[i]) &&
+                xor.test_bit(i) == (model[i] != b_model[i]) &&
+                not.test_bit(i) != model[i]
+            })
+        }
+        QuickCheck::new()
+            .quickcheck(inner as fn((u16, u16, u16, u16, u16), usize, usize, usize) -> bool);
+    }
+
+    #[test]
+    fn full_words() {
+        let mut a = BitArray::<u64, 3>::new();
+        a.set_bits(..);
+        assert_eq!(a, BitArray::full());
+        assert_eq!(a.count_ones(), 192);
+        assert_eq!(a.find_first_clear(), None);
+        a.clear_bits(64..128);
+        assert_eq!(a.into_words(), [!0, 0, !0]);
+        assert!(a.test_all(..64) && !a.test_any(64..128) && a.test_all(128..));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        BitArray::<u8, 2>::new().set_bit(16);
+    }
+}
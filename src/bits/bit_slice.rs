@@ -0,0 +1,595 @@
+use std::fmt;
+use std::ops::RangeBounds;
+
+use word::{self, UnsignedWord};
+use super::{bounds, word_masks, count_ones, find_first, find_last, Ones};
+
+/// Borrowed view of a sequence of bits stored in a slice of words.
+///
+/// The view starts at an arbitrary bit of the words and can have any length.
+/// The bits of the view are numbered from zero, independently of the bit in
+/// which the view starts. Accessing a bit outside of the view panics.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::BitSlice;
+///
+/// let words = [0b1111_0000u8, 0b0000_0011];
+/// let s = BitSlice::new(&words).slice(4..10);
+/// assert_eq!(s.len(), 6);
+/// assert!(s.test_all(..));
+/// assert_eq!(s.count_ones(), 6);
+///
+/// let t = BitSlice::new(&words).slice(6..);
+/// assert_eq!(t.iter_ones().collect::<Vec<_>>(), [0, 1, 2, 3]);
+/// assert_eq!(t.find_first_clear(), Some(4));
+/// ```
+#[derive(Copy, Clone)]
+pub struct BitSlice<'a, W: UnsignedWord> {
+    words: &'a [W],
+    /// Position of the first bit of the view in `words`.
+    offset: usize,
+    len: usize,
+}
+
+/// Mutable borrowed view of a sequence of bits stored in a slice of words.
+///
+/// This is the mutable version of [`BitSlice`](struct.BitSlice.html). Only
+/// the bits of the view are modified.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::BitSliceMut;
+///
+/// let mut words = [0u8; 3];
+/// let mut s = BitSliceMut::new(&mut words).slice(4..20);
+/// s.set_bits(2..14);
+/// s.flip_bit(0);
+/// assert_eq!(s.as_bit_slice().count_ones(), 13);
+/// assert_eq!(words, [0b1101_0000, 0xFF, 0b0000_0011]);
+/// ```
+pub struct BitSliceMut<'a, W: UnsignedWord> {
+    words: &'a mut [W],
+    offset: usize,
+    len: usize,
+}
+
+/// Word index and bit offset within the word of bit `bit` of a view starting
+/// at bit `offset` of its words.
+#[inline]
+fn locate<W: UnsignedWord>(offset: usize, len: usize, bit: usize) -> (usize, u32) {
+    assert!(bit < len, "bit {} is out of bounds ({} bits)", bit, len);
+    let bits = W::bit_size().to_usize();
+    let bit = offset + bit;
+    (bit / bits, (bit % bits) as u32)
+}
+
+impl<'a, W: UnsignedWord> BitSlice<'a, W> {
+    /// View of all bits of `words`.
+    #[inline]
+    pub fn new(words: &'a [W]) -> Self {
+        BitSlice { words, offset: 0, len: words.len() * W::bit_size().to_usize() }
+    }
+
+    /// View of the bits `range` of this view.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn slice<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let (start, end) = bounds(range, self.len);
+        BitSlice { words: self.words, offset: self.offset + start, len: end - start }
+    }
+
+    /// The words that store the bits of the view.
+    #[inline]
+    pub fn words(&self) -> &'a [W] {
+        self.words
+    }
+
+    /// Position of the first bit of the view in `words()`.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of bits of the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the view empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of words required to store the bits of the view, that is, the
+    /// number of words returned by `load_word`.
+    #[inline]
+    pub fn word_len(&self) -> usize {
+        let bits = W::bit_size().to_usize();
+        (self.len + bits - 1) / bits
+    }
+
+    /// The bits `i * W::bit_size()..(i + 1) * W::bit_size()` of the view as a
+    /// word. The bits past the end of the view are cleared.
+    ///
+    /// # Panics
+    ///
+    /// If `i >= word_len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bitwise::bits::BitSlice;
+    ///
+    /// let words = [0xABu8, 0xCD, 0xEF];
+    /// let s = BitSlice::new(&words).slice(4..22);
+    /// assert_eq!(s.word_len(), 3);
+    /// assert_eq!(s.load_word(0), 0xDA);
+    /// assert_eq!(s.load_word(1), 0xFC);
+    /// assert_eq!(s.load_word(2), 0x2);
+    /// ```
+    #[inline]
+    pub fn load_word(&self, i: usize) -> W {
+        let bits = W::bit_size().to_usize();
+        assert!(i < self.word_len(), "word {} is out of bounds ({} words)", i,
+                self.word_len());
+        let start = self.offset + i * bits;
+        let (index, shift) = (start / bits, start % bits);
+        let mut w = self.words[index] >> W::from_u32(shift as u32);
+        if shift != 0 && index + 1 < self.words.len() {
+            w = w | (self.words[index + 1] << W::from_u32((bits - shift) as u32));
+        }
+        let remaining = self.len - i * bits;
+        if remaining < bits {
+            w = word::clear_bits_geq(w, remaining as u32);
+        }
+        w
+    }
+
+    /// Is bit `bit` set?
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn test_bit(&self, bit: usize) -> bool {
+        let (i, b) = locate::<W>(self.offset, self.len, bit);
+        word::test_bit(self.words[i], b)
+    }
+
+    /// Bit `bit`, or `None` if `bit >= len()`.
+    #[inline]
+    pub fn get(&self, bit: usize) -> Option<bool> {
+        if bit < self.len {
+            Some(self.test_bit(bit))
+        } else {
+            None
+        }
+    }
+
+    /// Are all bits in `range` set? True for empty ranges.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn test_all<R: RangeBounds<usize>>(&self, range: R) -> bool {
+        let (start, end) = bounds(range, self.len);
+        word_masks(self.offset + start, self.offset + end)
+            .all(|(i, mask)| self.words[i] & mask == mask)
+    }
+
+    /// Is any bit in `range` set? False for empty ranges.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn test_any<R: RangeBounds<usize>>(&self, range: R) -> bool {
+        let (start, end) = bounds(range, self.len);
+        word_masks(self.offset + start, self.offset + end)
+            .any(|(i, mask)| self.words[i] & mask != W::zero())
+    }
+
+    /// Number of set bits.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        count_ones(self.words, self.offset, self.offset + self.len)
+    }
+
+    /// Number of cleared bits.
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Position of the first set bit, if any.
+    #[inline]
+    pub fn find_first_set(&self) -> Option<usize> {
+        find_first(self.words, self.offset, self.offset + self.len, |w| w)
+            .map(|i| i - self.offset)
+    }
+
+    /// Position of the first cleared bit, if any.
+    #[inline]
+    pub fn find_first_clear(&self) -> Option<usize> {
+        find_first(self.words, self.offset, self.offset + self.len, |w| !w)
+            .map(|i| i - self.offset)
+    }
+
+    /// Position of the last set bit, if any.
+    #[inline]
+    pub fn find_last_set(&self) -> Option<usize> {
+        find_last(self.words, self.offset, self.offset + self.len).map(|i| i - self.offset)
+    }
+
+    /// Iterator over the bits.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, W> {
+        Iter { slice: *self, front: 0, back: self.len }
+    }
+
+    /// Iterator over the positions of the set bits in increasing order.
+    #[inline]
+    pub fn iter_ones(&self) -> Ones<'a, W> {
+        Ones::from_range(self.words, self.offset, self.offset + self.len)
+    }
+}
+
+impl<'a, W: UnsignedWord> PartialEq for BitSlice<'a, W> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, W: UnsignedWord> Eq for BitSlice<'a, W> {}
+
+impl<'a, W: UnsignedWord> fmt::Debug for BitSlice<'a, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[")?;
+        for b in self.iter() {
+            f.write_str(if b { "1" } else { "0" })?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<'a, W: UnsignedWord> IntoIterator for BitSlice<'a, W> {
+    type Item = bool;
+    type IntoIter = Iter<'a, W>;
+    #[inline]
+    fn into_iter(self) -> Iter<'a, W> {
+        self.iter()
+    }
+}
+
+/// Iterator over the bits of a [`BitSlice`](struct.BitSlice.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, W: UnsignedWord> {
+    slice: BitSlice<'a, W>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, W: UnsignedWord> Iterator for Iter<'a, W> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.slice.test_bit(self.front - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl<'a, W: UnsignedWord> DoubleEndedIterator for Iter<'a, W> {
+    #[inline]
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.slice.test_bit(self.back))
+    }
+}
+
+impl<'a, W: UnsignedWord> ExactSizeIterator for Iter<'a, W> {}
+
+impl<'a, W: UnsignedWord> BitSliceMut<'a, W> {
+    /// Mutable view of all bits of `words`.
+    #[inline]
+    pub fn new(words: &'a mut [W]) -> Self {
+        let len = words.len() * W::bit_size().to_usize();
+        BitSliceMut { words, offset: 0, len }
+    }
+
+    /// Mutable view of the bits `range` of this view.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn slice<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let (start, end) = bounds(range, self.len);
+        BitSliceMut { words: self.words, offset: self.offset + start, len: end - start }
+    }
+
+    /// Reborrows the view with a shorter lifetime.
+    #[inline]
+    pub fn reborrow(&mut self) -> BitSliceMut<'_, W> {
+        BitSliceMut { words: self.words, offset: self.offset, len: self.len }
+    }
+
+    /// Immutable view of the bits.
+    #[inline]
+    pub fn as_bit_slice(&self) -> BitSlice<'_, W> {
+        BitSlice { words: self.words, offset: self.offset, len: self.len }
+    }
+
+    /// Number of bits of the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the view empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Is bit `bit` set?
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn test_bit(&self, bit: usize) -> bool {
+        self.as_bit_slice().test_bit(bit)
+    }
+
+    /// Sets bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn set_bit(&mut self, bit: usize) {
+        let (i, b) = locate::<W>(self.offset, self.len, bit);
+        self.words[i] = word::set_bit(self.words[i], b);
+    }
+
+    /// Clears bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn clear_bit(&mut self, bit: usize) {
+        let (i, b) = locate::<W>(self.offset, self.len, bit);
+        self.words[i] = word::clear_bit(self.words[i], b);
+    }
+
+    /// Flips bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn flip_bit(&mut self, bit: usize) {
+        let (i, b) = locate::<W>(self.offset, self.len, bit);
+        self.words[i] = word::flip_bit(self.words[i], b);
+    }
+
+    /// Sets bit `bit` if `value` is true, and clears it otherwise.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn assign_bit(&mut self, bit: usize, value: bool) {
+        if value {
+            self.set_bit(bit)
+        } else {
+            self.clear_bit(bit)
+        }
+    }
+
+    /// Sets all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn set_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(range, self.len);
+        for (i, mask) in word_masks::<W>(self.offset + start, self.offset + end) {
+            self.words[i] = self.words[i] | mask;
+        }
+    }
+
+    /// Clears all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn clear_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(range, self.len);
+        for (i, mask) in word_masks::<W>(self.offset + start, self.offset + end) {
+            self.words[i] = self.words[i] & !mask;
+        }
+    }
+
+    /// Flips all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn flip_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(range, self.len);
+        for (i, mask) in word_masks::<W>(self.offset + start, self.offset + end) {
+            self.words[i] = self.words[i] ^ mask;
+        }
+    }
+
+    /// Copies the bits of `other` into this view.
+    ///
+    /// The bits are copied a word at a time: each word of `other` is merged
+    /// into the (at most two) words of this view that it overlaps.
+    ///
+    /// # Panics
+    ///
+    /// If the views do not have the same length.
+    #[inline]
+    pub fn copy_from(&mut self, other: BitSlice<W>) {
+        assert_eq!(self.len, other.len, "the views do not have the same length");
+        let bits = W::bit_size().to_usize();
+        for k in 0..other.word_len() {
+            let w = other.load_word(k);
+            let start = self.offset + k * bits;
+            let end = self.offset + self.len.min((k + 1) * bits);
+            let shift = start % bits;
+            for (i, mask) in word_masks::<W>(start, end) {
+                let w = if i == start / bits {
+                    w << W::from_u32(shift as u32)
+                } else {
+                    w >> W::from_u32((bits - shift) as u32)
+                };
+                self.words[i] = (self.words[i] & !mask) | (w & mask);
+            }
+        }
+    }
+}
+
+impl<'a, W: UnsignedWord> fmt::Debug for BitSliceMut<'a, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_bit_slice().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn prop_bit_slice() {
+        fn inner(w: (u8, u8, u8, u8), start: usize, len: usize, a: usize, b: usize) -> bool {
+            let mut words = [w.0, !w.1, w.2, w.3];
+            let mut model = [false; 32];
+            for (i, m) in model.iter_mut().enumerate() {
+                *m = words[i / 8] & (1 << (i % 8)) != 0;
+            }
+            let start = start % 33;
+            let end = start + len % (33 - start);
+            let (a, b) = (a % (end - start + 1), b % (end - start + 1));
+            let (a, b) = (a.min(b), a.max(b));
+            let model = &mut model[start..end];
+
+            let s = BitSlice::new(&words).slice(start..end);
+            let matches = |s: BitSlice<u8>, model: &[bool]| {
+                s.len() == model.len() && s.iter().eq(model.iter().cloned()) &&
+                s.iter().rev().eq(model.iter().rev().cloned()) &&
+                s.count_ones() == model.iter().filter(|&&b| b).count() &&
+                s.find_first_set() == model.iter().position(|&b| b) &&
+                s.find_first_clear() == model.iter().position(|&b| !b) &&
+                s.find_last_set() == model.iter().rposition(|&b| b) &&
+                s.iter_ones().eq((0..model.len()).filter(|&i| model[i])) &&
+                s.test_all(a..b) == model[a..b].iter().all(|&b| b) &&
+                s.test_any(a..b) == model[a..b].iter().any(|&b| b) &&
+                s.slice(a..b).iter().eq(model[a..b].iter().cloned())
+            };
+            if !matches(s, model) {
+                return false;
+            }
+
+            let mut s = BitSliceMut::new(&mut words).slice(start..end);
+            s.flip_bits(a..b);
+            for m in &mut model[a..b] {
+                *m = !*m;
+            }
+            if !matches(s.as_bit_slice(), model) {
+                return false;
+            }
+            s.set_bits(..a);
+            s.clear_bits(b..);
+            for (i, m) in model.iter_mut().enumerate() {
+                if i < a {
+                    *m = true;
+                } else if i >= b {
+                    *m = false;
+                }
+            }
+            matches(s.as_bit_slice(), model)
+        }
+        QuickCheck::new()
+            .quickcheck(inner as fn((u8, u8, u8, u8), usize, usize, usize, usize) -> bool);
+    }
+
+    #[test]
+    fn prop_load_word() {
+        fn inner(w: (u16, u16, u16), start: usize, len: usize) -> bool {
+            let words = [w.0, w.1, w.2];
+            let start = start % 49;
+            let s = BitSlice::new(&words).slice(start..start + len % (49 - start));
+            (0..s.word_len()).all(|i| {
+                (0..16).all(|b| {
+                    let bit = i * 16 + b;
+                    (s.load_word(i) >> b & 1 != 0) == (bit < s.len() && s.test_bit(bit))
+                })
+            })
+        }
+        QuickCheck::new().quickcheck(inner as fn((u16, u16, u16), usize, usize) -> bool);
+    }
+
+    #[test]
+    fn prop_copy_from() {
+        fn inner(src: (u16, u16, u16), dst: (u16, u16, u16), from: usize, to: usize,
+                 len: usize) -> bool {
+            let source = [src.0, src.1, src.2];
+            let mut words = [dst.0, dst.1, dst.2];
+            let (from, to) = (from % 49, to % 49);
+            let len = len % (49 - from.max(to));
+            let mut expected = words;
+            for i in 0..48 {
+                let bit = if i >= to && i < to + len {
+                    source[(i - to + from) / 16] >> ((i - to + from) % 16) & 1
+                } else {
+                    words[i / 16] >> (i % 16) & 1
+                };
+                expected[i / 16] = expected[i / 16] & !(1 << (i % 16)) | bit << (i % 16);
+            }
+            BitSliceMut::new(&mut words).slice(to..to + len)
+                .copy_from(BitSlice::new(&source).slice(from..from + len));
+            words == expected
+        }
+        QuickCheck::new().quickcheck(inner as fn((u16, u16, u16), (u16, u16, u16), usize, usize,
+                                                 usize) -> bool);
+    }
+
+    #[test]
+    fn outside_bits_are_not_modified() {
+        let mut words = [0u16; 3];
+        BitSliceMut::new(&mut words).slice(5..40).set_bits(..);
+        assert_eq!(words, [0xFFE0, 0xFFFF, 0x00FF]);
+        BitSliceMut::new(&mut words).slice(6..39).flip_bits(..);
+        assert_eq!(words, [0x0020, 0x0000, 0x0080]);
+    }
+}
@@ -0,0 +1,474 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+
+use alloc::vec::Vec;
+
+use word::{self, UnsignedWord};
+use super::{BitSlice, BitSliceMut, Iter, Ones};
+
+/// Growable sequence of bits stored in a vector of words of type `W`.
+///
+/// Requires the `alloc` cargo feature.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::BitVec;
+///
+/// let mut v = BitVec::<u64>::new();
+/// v.push(true);
+/// v.resize(100, false);
+/// v.set_bits(70..80);
+/// v.insert(1, true);
+/// assert_eq!(v.len(), 101);
+/// assert_eq!(v.iter_ones().collect::<Vec<_>>(),
+///            [0, 1, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80]);
+/// assert_eq!(v.remove(0), true);
+/// assert_eq!(v.pop(), Some(false));
+/// assert_eq!(v.count_ones(), 11);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec<W: UnsignedWord> {
+    /// The bits of the words at positions `>= len` are always cleared.
+    words: Vec<W>,
+    len: usize,
+}
+
+/// Number of words required to store `bits` bits.
+#[inline]
+fn words_for<W: UnsignedWord>(bits: usize) -> usize {
+    let word_bits = W::bit_size().to_usize();
+    (bits + word_bits - 1) / word_bits
+}
+
+impl<W: UnsignedWord> BitVec<W> {
+    /// Empty bit vector.
+    #[inline]
+    pub fn new() -> Self {
+        BitVec { words: Vec::new(), len: 0 }
+    }
+
+    /// Empty bit vector with space for at least `bits` bits.
+    #[inline]
+    pub fn with_capacity(bits: usize) -> Self {
+        BitVec { words: Vec::with_capacity(words_for::<W>(bits)), len: 0 }
+    }
+
+    /// Bit vector of `len` bits equal to `value`.
+    #[inline]
+    pub fn repeat(value: bool, len: usize) -> Self {
+        let mut v = Self::new();
+        v.resize(len, value);
+        v
+    }
+
+    /// Bit vector with the bits of `words`.
+    #[inline]
+    pub fn from_words(words: Vec<W>) -> Self {
+        let len = words.len() * W::bit_size().to_usize();
+        BitVec { words, len }
+    }
+
+    /// Bit vector with the bits of `slice`.
+    pub fn from_bit_slice(slice: BitSlice<W>) -> Self {
+        let mut v = Self::repeat(false, slice.len());
+        v.as_mut_bit_slice().copy_from(slice);
+        v
+    }
+
+    /// Returns the words of the bit vector; the bits after the last bit of
+    /// the vector are cleared.
+    #[inline]
+    pub fn into_words(self) -> Vec<W> {
+        self.words
+    }
+
+    /// The words of the bit vector; the bits after the last bit of the vector
+    /// are cleared.
+    #[inline]
+    pub fn as_words(&self) -> &[W] {
+        &self.words
+    }
+
+    /// View of the bits of the vector.
+    #[inline]
+    pub fn as_bit_slice(&self) -> BitSlice<'_, W> {
+        BitSlice::new(&self.words).slice(..self.len)
+    }
+
+    /// Mutable view of the bits of the vector.
+    #[inline]
+    pub fn as_mut_bit_slice(&mut self) -> BitSliceMut<'_, W> {
+        let len = self.len;
+        BitSliceMut::new(&mut self.words).slice(..len)
+    }
+
+    /// Number of bits.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the vector empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of bits the vector can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.words.capacity() * W::bit_size().to_usize()
+    }
+
+    /// Reserves capacity for at least `additional` more bits.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        let words = words_for::<W>(self.len + additional);
+        self.words.reserve(words - self.words.len());
+    }
+
+    /// Is bit `bit` set?
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn test_bit(&self, bit: usize) -> bool {
+        self.as_bit_slice().test_bit(bit)
+    }
+
+    /// Bit `bit`, or `None` if `bit >= len()`.
+    #[inline]
+    pub fn get(&self, bit: usize) -> Option<bool> {
+        self.as_bit_slice().get(bit)
+    }
+
+    /// Sets bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn set_bit(&mut self, bit: usize) {
+        self.as_mut_bit_slice().set_bit(bit)
+    }
+
+    /// Clears bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn clear_bit(&mut self, bit: usize) {
+        self.as_mut_bit_slice().clear_bit(bit)
+    }
+
+    /// Flips bit `bit`.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn flip_bit(&mut self, bit: usize) {
+        self.as_mut_bit_slice().flip_bit(bit)
+    }
+
+    /// Sets bit `bit` if `value` is true, and clears it otherwise.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    #[inline]
+    pub fn assign_bit(&mut self, bit: usize, value: bool) {
+        self.as_mut_bit_slice().assign_bit(bit, value)
+    }
+
+    /// Sets all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn set_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.as_mut_bit_slice().set_bits(range)
+    }
+
+    /// Clears all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn clear_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.as_mut_bit_slice().clear_bits(range)
+    }
+
+    /// Flips all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn flip_bits<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.as_mut_bit_slice().flip_bits(range)
+    }
+
+    /// Number of set bits.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|&w| word::count_ones(w).to_usize()).sum()
+    }
+
+    /// Number of cleared bits.
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Position of the first set bit, if any.
+    #[inline]
+    pub fn find_first_set(&self) -> Option<usize> {
+        self.as_bit_slice().find_first_set()
+    }
+
+    /// Position of the first cleared bit, if any.
+    #[inline]
+    pub fn find_first_clear(&self) -> Option<usize> {
+        self.as_bit_slice().find_first_clear()
+    }
+
+    /// Position of the last set bit, if any.
+    #[inline]
+    pub fn find_last_set(&self) -> Option<usize> {
+        self.as_bit_slice().find_last_set()
+    }
+
+    /// Iterator over the bits.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, W> {
+        self.as_bit_slice().iter()
+    }
+
+    /// Iterator over the positions of the set bits in increasing order.
+    #[inline]
+    pub fn iter_ones(&self) -> Ones<'_, W> {
+        Ones::new(&self.words)
+    }
+
+    /// Appends a bit.
+    #[inline]
+    pub fn push(&mut self, value: bool) {
+        if self.len == self.words.len() * W::bit_size().to_usize() {
+            self.words.push(W::zero());
+        }
+        self.len += 1;
+        self.assign_bit(self.len - 1, value);
+    }
+
+    /// Removes the last bit and returns it, or `None` if the vector is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<bool> {
+        if self.len == 0 {
+            return None;
+        }
+        let value = self.test_bit(self.len - 1);
+        self.truncate(self.len - 1);
+        Some(value)
+    }
+
+    /// Shortens the vector to `len` bits. Has no effect if `len >= len()`.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        self.words.truncate(words_for::<W>(len));
+        let bit = len % W::bit_size().to_usize();
+        if let (Some(last), true) = (self.words.last_mut(), bit != 0) {
+            *last = word::clear_bits_geq(*last, bit as u32);
+        }
+        self.len = len;
+    }
+
+    /// Removes all bits.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+
+    /// Resizes the vector to `len` bits, filling the new bits with `value`.
+    pub fn resize(&mut self, len: usize, value: bool) {
+        if len <= self.len {
+            self.truncate(len);
+            return;
+        }
+        let old_len = self.len;
+        self.words.resize(words_for::<W>(len), W::zero());
+        self.len = len;
+        if value {
+            self.set_bits(old_len..);
+        }
+    }
+
+    /// Inserts `value` at position `bit`, shifting all bits after it up.
+    ///
+    /// # Panics
+    ///
+    /// If `bit > len()`.
+    pub fn insert(&mut self, bit: usize, value: bool) {
+        assert!(bit <= self.len, "bit {} is out of bounds ({} bits)", bit, self.len);
+        self.push(false);
+        let bits = W::bit_size().to_usize();
+        let (index, offset) = (bit / bits, (bit % bits) as u32);
+        let top = W::bit_size() - W::one();
+        for i in (index + 1..self.words.len()).rev() {
+            self.words[i] = (self.words[i] << W::one()) | (self.words[i - 1] >> top);
+        }
+        // Shift the bits of the word `>= offset` up by one:
+        let w = self.words[index];
+        let low = if offset == 0 { W::zero() } else { word::clear_bits_geq(w, offset) };
+        let high = w ^ low;
+        self.words[index] = low | (high << W::one());
+        self.assign_bit(bit, value);
+    }
+
+    /// Removes the bit at position `bit` and returns it, shifting all bits
+    /// after it down.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= len()`.
+    pub fn remove(&mut self, bit: usize) -> bool {
+        let value = self.test_bit(bit);
+        let bits = W::bit_size().to_usize();
+        let (index, offset) = (bit / bits, (bit % bits) as u32);
+        let top = W::bit_size() - W::one();
+        let n = self.words.len();
+        // Shift the bits of the word `> offset` down by one:
+        let w = self.words[index];
+        let low = if offset == 0 { W::zero() } else { word::clear_bits_geq(w, offset) };
+        let high = word::clear_bits_leq(w, offset);
+        self.words[index] = low | (high >> W::one());
+        for i in index..n {
+            if i > index {
+                self.words[i] = self.words[i] >> W::one();
+            }
+            if i + 1 < n {
+                self.words[i] = self.words[i] | (self.words[i + 1] << top);
+            }
+        }
+        // The last bit is now cleared:
+        self.truncate(self.len - 1);
+        value
+    }
+
+    /// Appends the bits of `slice`.
+    pub fn extend_from_bit_slice(&mut self, slice: BitSlice<W>) {
+        let len = self.len;
+        self.resize(len + slice.len(), false);
+        self.as_mut_bit_slice().slice(len..).copy_from(slice);
+    }
+}
+
+impl<W: UnsignedWord> fmt::Debug for BitVec<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_bit_slice().fmt(f)
+    }
+}
+
+impl<W: UnsignedWord> Extend<bool> for BitVec<W> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for b in iter {
+            self.push(b);
+        }
+    }
+}
+
+impl<W: UnsignedWord> FromIterator<bool> for BitVec<W> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut v = Self::new();
+        v.extend(iter);
+        v
+    }
+}
+
+impl<'a, W: UnsignedWord> IntoIterator for &'a BitVec<W> {
+    type Item = bool;
+    type IntoIter = Iter<'a, W>;
+    #[inline]
+    fn into_iter(self) -> Iter<'a, W> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    fn matches(v: &BitVec<u8>, model: &[bool]) -> bool {
+        v.len() == model.len() && v.iter().eq(model.iter().cloned()) &&
+        v.count_ones() == model.iter().filter(|&&b| b).count() &&
+        v.iter_ones().eq((0..model.len()).filter(|&i| model[i])) &&
+        v.as_words().len() == words_for::<u8>(v.len()) &&
+        v.as_words().last().map_or(true, |&w| v.len() % 8 == 0 || w >> (v.len() % 8) == 0)
+    }
+
+    #[test]
+    fn prop_bit_vec() {
+        fn inner(ops: Vec<(u8, u16, bool)>) -> bool {
+            let mut v = BitVec::<u8>::new();
+            let mut model = Vec::new();
+            for (op, n, b) in ops {
+                let n = n as usize;
+                match op % 6 {
+                    0 => {
+                        v.push(b);
+                        model.push(b);
+                    }
+                    1 => {
+                        if v.pop() != model.pop() {
+                            return false;
+                        }
+                    }
+                    2 => {
+                        let i = n % (model.len() + 1);
+                        v.insert(i, b);
+                        model.insert(i, b);
+                    }
+                    3 if !model.is_empty() => {
+                        let i = n % model.len();
+                        if v.remove(i) != model.remove(i) {
+                            return false;
+                        }
+                    }
+                    4 => {
+                        let len = n % 100;
+                        v.resize(len, b);
+                        model.resize(len, b);
+                    }
+                    _ => {
+                        let i = n % (model.len() + 1);
+                        v.flip_bits(i..);
+                        for m in &mut model[i..] {
+                            *m = !*m;
+                        }
+                    }
+                }
+                if !matches(&v, &model) {
+                    return false;
+                }
+            }
+            v.iter().collect::<BitVec<u8>>() == v &&
+            BitVec::from_bit_slice(v.as_bit_slice()) == v
+        }
+        QuickCheck::new().quickcheck(inner as fn(Vec<(u8, u16, bool)>) -> bool);
+    }
+}
@@ -0,0 +1,557 @@
+//! Universal and parametric codes for unsigned integers.
+//!
+//! Each code has a `write_*` function that appends the code of a value to a
+//! [`BitWriter`](../struct.BitWriter.html), a `read_*` function that decodes
+//! a value from a [`BitReader`](../struct.BitReader.html), and a `*_len`
+//! function that returns the length of the code of a value in bits:
+//!
+//! - unary: `x >= 0` is `x` zeros followed by a one,
+//! - Elias gamma: `x > 0` with `n = floor(log2(x))` is the unary code of `n`
+//!   followed by the `n` lower bits of `x`,
+//! - Elias delta: `x > 0` is the gamma code of `n + 1` followed by the `n`
+//!   lower bits of `x`,
+//! - Elias omega: `x > 0` is the recursive encoding of the number of bits of
+//!   `x`, each group starting with a one, terminated by a zero,
+//! - Golomb with modulus `m > 0`: `x >= 0` is the unary code of `x / m`
+//!   followed by the truncated binary code of `x % m`,
+//! - Rice with parameter `k`: the Golomb code with modulus `2^k`, that is,
+//!   the unary code of `x >> k` followed by the `k` lower bits of `x`.
+//!
+//! Codes are written in the order of the stream: in a
+//! [`MsbFirst`](../enum.BitOrder.html) stream they are the textbook codes.
+//! Decoding the unary parts uses
+//! [`count_leading_zeros`](../../word/fn.count_leading_zeros.html) or
+//! [`count_trailing_zeros`](../../word/fn.count_trailing_zeros.html) on up to
+//! 64 bits at a time.
+//!
+//! Writes check that the whole code fits before writing any bit, and reads
+//! do not advance the reader on error.
+//!
+//! # Example
+//!
+//! ```
+//! use bitwise::bits::{BitOrder, BitReader, BitWriter};
+//! use bitwise::bits::codes::*;
+//!
+//! let mut bytes = [0; 4];
+//! {
+//!     let mut w = BitWriter::new(&mut bytes, BitOrder::MsbFirst);
+//!     write_gamma(&mut w, 5u32).unwrap(); // 00 1 01
+//!     write_delta(&mut w, 5u32).unwrap(); // 0 1 1 01
+//!     write_rice(&mut w, 13u8, 2).unwrap(); // 0001 01
+//!     assert_eq!(w.position(), gamma_len(5u32) + delta_len(5u32) + rice_len(13u8, 2));
+//! }
+//! assert_eq!(bytes[..2], [0b0010_1011, 0b0100_0101]);
+//!
+//! let mut r = BitReader::new(&bytes, BitOrder::MsbFirst);
+//! assert_eq!(read_gamma::<u32>(&mut r), Ok(5));
+//! assert_eq!(read_delta::<u32>(&mut r), Ok(5));
+//! assert_eq!(read_rice::<u8>(&mut r, 2), Ok(13));
+//! ```
+
+use std::fmt;
+
+use word::{self, UnsignedWord};
+use super::{BitOrder, BitReader, BitWriter, Truncated};
+
+/// Error decoding a value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The stream ends before the end of the code.
+    Truncated(Truncated),
+    /// The decoded value does not fit in the word type.
+    Overflow,
+}
+
+impl From<Truncated> for Error {
+    #[inline]
+    fn from(e: Truncated) -> Self {
+        Error::Truncated(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Truncated(ref e) => e.fmt(f),
+            Error::Overflow => f.write_str("the decoded value does not fit in the word type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::libstd::error::Error for Error {}
+
+/// Converts a number of bits into a `usize`, saturating.
+#[inline]
+fn saturate(bits: u128) -> usize {
+    bits.min(usize::MAX as u128) as usize
+}
+
+/// `floor(log2(x))` for `x > 0`.
+#[inline]
+fn floor_log2<T: UnsignedWord>(x: T) -> u32 {
+    assert!(x > T::zero(), "the code is only defined for values greater than zero");
+    (T::bit_size() - T::one() - word::count_leading_zeros(x)).to_u32()
+}
+
+/// Checks that `bits` bits can be written to `w`.
+#[inline]
+fn reserve(w: &BitWriter, bits: usize) -> Result<(), Truncated> {
+    let available = w.remaining();
+    if bits > available {
+        Err(Truncated { position: w.position(), bits, available })
+    } else {
+        Ok(())
+    }
+}
+
+/// Decodes a value with `f`, advancing `r` only on success.
+#[inline]
+fn atomically<'a, T, F>(r: &mut BitReader<'a>, f: F) -> Result<T, Error>
+    where F: FnOnce(&mut BitReader<'a>) -> Result<T, Error>
+{
+    let mut s = r.clone();
+    let x = f(&mut s)?;
+    *r = s;
+    Ok(x)
+}
+
+/// Reads `bits` bits into a `T`, reporting values wider than `T` as
+/// overflows.
+#[inline]
+fn read_bits<T: UnsignedWord>(r: &mut BitReader, bits: u32) -> Result<T, Error> {
+    if bits > T::bit_size().to_u32() {
+        return Err(Error::Overflow);
+    }
+    Ok(r.read(bits)?)
+}
+
+/// Writes `n` zeros.
+#[inline]
+fn write_zeros(w: &mut BitWriter, mut n: u128) -> Result<(), Truncated> {
+    while n > 0 {
+        let k = n.min(64) as u32;
+        w.write(0u64, k)?;
+        n -= k as u128;
+    }
+    Ok(())
+}
+
+/// Length in bits of the unary code of `x`.
+#[inline]
+pub fn unary_len<T: UnsignedWord>(x: T) -> usize {
+    saturate(x.to_u128()).saturating_add(1)
+}
+
+/// Writes the unary code of `x`: `x` zeros followed by a one.
+#[inline]
+pub fn write_unary<T: UnsignedWord>(w: &mut BitWriter, x: T) -> Result<(), Truncated> {
+    reserve(w, unary_len(x))?;
+    write_zeros(w, x.to_u128())?;
+    w.write_bit(true)
+}
+
+/// Reads a unary code.
+#[inline]
+pub fn read_unary<T: UnsignedWord>(r: &mut BitReader) -> Result<T, Error> {
+    atomically(r, |r| {
+        let max = (!T::zero()).to_u128();
+        let mut x = 0u128;
+        loop {
+            let n = r.remaining().min(64) as u32;
+            let bits: u64 = r.peek(n.max(1))?;
+            // Number of zeros before the first one in stream order:
+            let zeros = if bits == 0 {
+                n
+            } else {
+                match r.order() {
+                    BitOrder::LsbFirst => word::count_trailing_zeros(bits) as u32,
+                    BitOrder::MsbFirst => word::count_leading_zeros(bits) as u32 - (64 - n),
+                }
+            };
+            x += zeros as u128;
+            if x > max {
+                return Err(Error::Overflow);
+            }
+            if zeros < n {
+                r.skip(zeros as usize + 1)?;
+                return Ok(T::from_u128(x));
+            }
+            r.skip(n as usize)?;
+        }
+    })
+}
+
+/// Length in bits of the Elias gamma code of `x > 0`.
+///
+/// # Panics
+///
+/// If `x == 0`.
+#[inline]
+pub fn gamma_len<T: UnsignedWord>(x: T) -> usize {
+    2 * floor_log2(x) as usize + 1
+}
+
+/// Writes the Elias gamma code of `x > 0`.
+///
+/// # Panics
+///
+/// If `x == 0`.
+#[inline]
+pub fn write_gamma<T: UnsignedWord>(w: &mut BitWriter, x: T) -> Result<(), Truncated> {
+    reserve(w, gamma_len(x))?;
+    let n = floor_log2(x);
+    write_unary(w, n)?;
+    w.write(x, n)
+}
+
+/// Reads an Elias gamma code.
+#[inline]
+pub fn read_gamma<T: UnsignedWord>(r: &mut BitReader) -> Result<T, Error> {
+    atomically(r, |r| {
+        let n: u32 = read_unary(r)?;
+        if n >= T::bit_size().to_u32() {
+            return Err(Error::Overflow);
+        }
+        let low: T = r.read(n)?;
+        Ok((T::one() << T::from_u32(n)) | low)
+    })
+}
+
+/// Length in bits of the Elias delta code of `x > 0`.
+///
+/// # Panics
+///
+/// If `x == 0`.
+#[inline]
+pub fn delta_len<T: UnsignedWord>(x: T) -> usize {
+    let n = floor_log2(x);
+    gamma_len(n + 1) + n as usize
+}
+
+/// Writes the Elias delta code of `x > 0`.
+///
+/// # Panics
+///
+/// If `x == 0`.
+#[inline]
+pub fn write_delta<T: UnsignedWord>(w: &mut BitWriter, x: T) -> Result<(), Truncated> {
+    reserve(w, delta_len(x))?;
+    let n = floor_log2(x);
+    write_gamma(w, n + 1)?;
+    w.write(x, n)
+}
+
+/// Reads an Elias delta code.
+#[inline]
+pub fn read_delta<T: UnsignedWord>(r: &mut BitReader) -> Result<T, Error> {
+    atomically(r, |r| {
+        let n = read_gamma::<u32>(r)? - 1;
+        if n >= T::bit_size().to_u32() {
+            return Err(Error::Overflow);
+        }
+        let low: T = r.read(n)?;
+        Ok((T::one() << T::from_u32(n)) | low)
+    })
+}
+
+/// Groups of the Elias omega code of `x > 0`, from the last one: each group
+/// is a value and its number of bits after its leading one.
+#[inline]
+fn omega_groups(x: u128, groups: &mut [(u128, u32); 8]) -> usize {
+    let mut len = 0;
+    let mut x = x;
+    while x > 1 {
+        let n = floor_log2(x);
+        groups[len] = (x, n);
+        len += 1;
+        x = n as u128;
+    }
+    len
+}
+
+/// Length in bits of the Elias omega code of `x > 0`.
+///
+/// # Panics
+///
+/// If `x == 0`.
+#[inline]
+pub fn omega_len<T: UnsignedWord>(x: T) -> usize {
+    floor_log2(x);
+    let mut groups = [(0, 0); 8];
+    let len = omega_groups(x.to_u128(), &mut groups);
+    groups[..len].iter().map(|&(_, n)| n as usize + 1).sum::<usize>() + 1
+}
+
+/// Writes the Elias omega code of `x > 0`.
+///
+/// # Panics
+///
+/// If `x == 0`.
+#[inline]
+pub fn write_omega<T: UnsignedWord>(w: &mut BitWriter, x: T) -> Result<(), Truncated> {
+    reserve(w, omega_len(x))?;
+    let mut groups = [(0, 0); 8];
+    let len = omega_groups(x.to_u128(), &mut groups);
+    for &(v, n) in groups[..len].iter().rev() {
+        w.write_bit(true)?;
+        w.write(v, n)?;
+    }
+    w.write_bit(false)
+}
+
+/// Reads an Elias omega code.
+#[inline]
+pub fn read_omega<T: UnsignedWord>(r: &mut BitReader) -> Result<T, Error> {
+    atomically(r, |r| {
+        let mut x = T::one();
+        while r.read_bit()? {
+            let n = x.to_u128();
+            if n >= T::bit_size().to_u128() {
+                return Err(Error::Overflow);
+            }
+            let low: T = read_bits(r, n as u32)?;
+            x = (T::one() << x) | low;
+        }
+        Ok(x)
+    })
+}
+
+/// Parameters of the truncated binary code of the remainders modulo `m > 0`:
+/// `b = ceil(log2(m))`, and the `cutoff = 2^b - m` smallest remainders are
+/// written in `b - 1` bits.
+#[inline]
+fn truncated_binary<T: UnsignedWord>(m: T) -> (u32, T) {
+    assert!(m > T::zero(), "the Golomb modulus must be greater than zero");
+    let bits = T::bit_size().to_u32();
+    let b = if m == T::one() { 0 } else { floor_log2(m - T::one()) + 1 };
+    let power = if b == bits { T::zero() } else { T::one() << T::from_u32(b) };
+    (b, power.wrapping_sub(m))
+}
+
+/// Length in bits of the Golomb code of `x` with modulus `m > 0`.
+///
+/// # Panics
+///
+/// If `m == 0`.
+#[inline]
+pub fn golomb_len<T: UnsignedWord>(x: T, m: T) -> usize {
+    let (b, cutoff) = truncated_binary(m);
+    let r = x % m;
+    let remainder = if b == 0 { 0 } else if r < cutoff { b - 1 } else { b };
+    unary_len(x / m).saturating_add(remainder as usize)
+}
+
+/// Writes the Golomb code of `x` with modulus `m > 0`.
+///
+/// # Panics
+///
+/// If `m == 0`.
+#[inline]
+pub fn write_golomb<T: UnsignedWord>(w: &mut BitWriter, x: T, m: T) -> Result<(), Truncated> {
+    reserve(w, golomb_len(x, m))?;
+    let (b, cutoff) = truncated_binary(m);
+    write_unary(w, x / m)?;
+    let r = x % m;
+    if b == 0 {
+        Ok(())
+    } else if r < cutoff {
+        w.write(r, b - 1)
+    } else {
+        // The last bit is written separately so that the first `b - 1` bits
+        // are also the first bits in `LsbFirst` streams:
+        let r = r.wrapping_add(cutoff);
+        w.write(r >> T::one(), b - 1)?;
+        w.write_bit(r & T::one() == T::one())
+    }
+}
+
+/// Reads a Golomb code with modulus `m > 0`.
+///
+/// # Panics
+///
+/// If `m == 0`.
+#[inline]
+pub fn read_golomb<T: UnsignedWord>(r: &mut BitReader, m: T) -> Result<T, Error> {
+    let (b, cutoff) = truncated_binary(m);
+    atomically(r, |r| {
+        let q: T = read_unary(r)?;
+        let mut rem = T::zero();
+        if b > 0 {
+            rem = r.read(b - 1)?;
+            if rem >= cutoff {
+                let bit = T::from_u32(r.read_bit()? as u32);
+                rem = ((rem << T::one()) | bit).wrapping_sub(cutoff);
+            }
+        }
+        let x = q.to_u128().checked_mul(m.to_u128())
+            .and_then(|x| x.checked_add(rem.to_u128()))
+            .ok_or(Error::Overflow)?;
+        if x > (!T::zero()).to_u128() {
+            return Err(Error::Overflow);
+        }
+        Ok(T::from_u128(x))
+    })
+}
+
+/// Quotient of `x` by `2^k`.
+#[inline]
+fn rice_quotient<T: UnsignedWord>(x: T, k: u32) -> T {
+    let bits = T::bit_size().to_u32();
+    assert!(k <= bits, "the Rice parameter {} is greater than the word size {}", k, bits);
+    if k == bits { T::zero() } else { x >> T::from_u32(k) }
+}
+
+/// Length in bits of the Rice code of `x` with parameter `k`.
+///
+/// # Panics
+///
+/// If `k > T::bit_size()`.
+#[inline]
+pub fn rice_len<T: UnsignedWord>(x: T, k: u32) -> usize {
+    unary_len(rice_quotient(x, k)).saturating_add(k as usize)
+}
+
+/// Writes the Rice code of `x` with parameter `k`.
+///
+/// # Panics
+///
+/// If `k > T::bit_size()`.
+#[inline]
+pub fn write_rice<T: UnsignedWord>(w: &mut BitWriter, x: T, k: u32) -> Result<(), Truncated> {
+    reserve(w, rice_len(x, k))?;
+    write_unary(w, rice_quotient(x, k))?;
+    w.write(x, k)
+}
+
+/// Reads a Rice code with parameter `k`.
+///
+/// # Panics
+///
+/// If `k > T::bit_size()`.
+#[inline]
+pub fn read_rice<T: UnsignedWord>(r: &mut BitReader, k: u32) -> Result<T, Error> {
+    let bits = T::bit_size().to_u32();
+    assert!(k <= bits, "the Rice parameter {} is greater than the word size {}", k, bits);
+    atomically(r, |r| {
+        let q: T = read_unary(r)?;
+        if q != T::zero() && (k == bits || q > (!T::zero() >> T::from_u32(k))) {
+            return Err(Error::Overflow);
+        }
+        let low: T = r.read(k)?;
+        Ok(if k == bits { low } else { (q << T::from_u32(k)) | low })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    /// Writes `x` with every code in a stream of `order`, then reads it back.
+    fn check<T: UnsignedWord + fmt::Debug>(x: T, m: T, k: u32, order: BitOrder) -> bool {
+        let mut bytes = [0u8; 256];
+        let small = x.to_u128() < 1000;
+        let mut total = 0;
+        {
+            let mut w = BitWriter::new(&mut bytes, order);
+            if small {
+                write_unary(&mut w, x).unwrap();
+                total += unary_len(x);
+            }
+            if x > T::zero() {
+                write_gamma(&mut w, x).unwrap();
+                write_delta(&mut w, x).unwrap();
+                write_omega(&mut w, x).unwrap();
+                total += gamma_len(x) + delta_len(x) + omega_len(x);
+            }
+            if golomb_len(x, m) < 300 {
+                write_golomb(&mut w, x, m).unwrap();
+                total += golomb_len(x, m);
+            }
+            if rice_len(x, k) < 300 {
+                write_rice(&mut w, x, k).unwrap();
+                total += rice_len(x, k);
+            }
+            if w.position() != total {
+                return false;
+            }
+        }
+        let mut r = BitReader::new(&bytes, order);
+        (!small || read_unary(&mut r) == Ok(x)) &&
+        (x == T::zero() ||
+         (read_gamma(&mut r) == Ok(x) && read_delta(&mut r) == Ok(x) &&
+          read_omega(&mut r) == Ok(x))) &&
+        (golomb_len(x, m) >= 300 || read_golomb(&mut r, m) == Ok(x)) &&
+        (rice_len(x, k) >= 300 || read_rice(&mut r, k) == Ok(x)) &&
+        r.position() == total
+    }
+
+    #[test]
+    fn prop_codes() {
+        fn inner(x: u64, m: u64, k: u8, shift: u8) -> bool {
+            let x = x >> (shift % 64);
+            let m = (m >> (shift / 4 % 64)).max(1);
+            [BitOrder::MsbFirst, BitOrder::LsbFirst].iter().all(|&o| {
+                check(x, m, k as u32 % 65, o) &&
+                check(x as u8, (m as u8).max(1), k as u32 % 9, o) &&
+                check(x as u16, (m as u16).max(1), k as u32 % 17, o) &&
+                check((x as u128) << 64 | x as u128, m as u128, k as u32 % 129, o)
+            })
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u8, u8) -> bool);
+    }
+
+    #[test]
+    fn textbook_codes() {
+        let mut bytes = [0u8; 8];
+        {
+            let mut w = BitWriter::new(&mut bytes, BitOrder::MsbFirst);
+            write_unary(&mut w, 3u8).unwrap(); // 0001
+            write_omega(&mut w, 17u32).unwrap(); // 10 100 10001 0
+            write_golomb(&mut w, 9u8, 10).unwrap(); // 1 1111
+            write_golomb(&mut w, 2u8, 10).unwrap(); // 1 010
+            write_gamma(&mut w, 1u64).unwrap(); // 1
+            write_omega(&mut w, 1u64).unwrap(); // 0
+        }
+        assert_eq!(bytes[..4], [0b0001_1010, 0b0100_0101, 0b1111_1010, 0b1000_0000]);
+    }
+
+    #[test]
+    fn extremes() {
+        for &order in &[BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            assert!(check(!0u8, 1, 0, order));
+            assert!(check(!0u64, !0, 64, order));
+            assert!(check(!0u128, !0, 128, order));
+            assert!(check(!0u128, 1 << 127, 127, order));
+            assert!(check(1u128 << 127, 3, 126, order));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut bytes = [0u8; 3];
+        {
+            let mut w = BitWriter::new(&mut bytes, BitOrder::MsbFirst);
+            write_gamma(&mut w, 300u16).unwrap(); // 0000_0000 1 0010_1100
+            assert_eq!(write_unary(&mut w, 7u8),
+                       Err(Truncated { position: 17, bits: 8, available: 7 }));
+            assert_eq!(w.position(), 17);
+        }
+        let mut r = BitReader::new(&bytes[..1], BitOrder::MsbFirst);
+        assert_eq!(read_unary::<u8>(&mut r),
+                   Err(Error::Truncated(Truncated { position: 8, bits: 1, available: 0 })));
+        assert_eq!(r.position(), 0);
+        let mut r = BitReader::new(&bytes, BitOrder::MsbFirst);
+        assert_eq!(read_gamma::<u8>(&mut r), Err(Error::Overflow));
+        assert_eq!(read_unary::<u8>(&mut r), Ok(8));
+        assert_eq!(r.position(), 9);
+        let mut r = BitReader::new(&bytes[..2], BitOrder::MsbFirst);
+        assert_eq!(read_gamma::<u16>(&mut r),
+                   Err(Error::Truncated(Truncated { position: 9, bits: 8, available: 7 })));
+        assert_eq!(read_rice::<u8>(&mut r, 7), Err(Error::Overflow));
+        assert_eq!(read_golomb::<u8>(&mut r, 200), Err(Error::Overflow));
+        assert_eq!(read_golomb::<u16>(&mut r, 200), Ok(8 * 200 + 0b001_0110));
+    }
+}
@@ -0,0 +1,453 @@
+//! Elias-Fano encoding of monotone sequences of integers.
+//!
+//! A sorted sequence of `n` integers smaller than `u` is stored in
+//! `2 + log2(u / n)` bits per element: the lower `l = floor(log2(u / n))`
+//! bits of each element are stored verbatim, and the upper bits are stored
+//! in unary as the gaps between consecutive elements in a bit vector of
+//! `n + u / 2^l` bits (Elias, "Efficient Storage and Retrieval by Content and
+//! Address of Static Files", 1974; Fano, "On the number of bits required to
+//! implement an associative memory", 1971).
+//!
+//! Random access and searches use a sample of the positions of every
+//! 256-th set and cleared bit of the upper bits, and
+//! [`select_in_word`](../../word/fn.select_in_word.html).
+//!
+//! Requires the `alloc` cargo feature.
+//!
+//! # Example
+//!
+//! ```
+//! use bitwise::bits::elias_fano::EliasFano;
+//!
+//! let keys = [3u64, 4, 7, 13, 14, 15, 21, 43];
+//! let ef = EliasFano::new(&keys);
+//! assert_eq!(ef.len(), 8);
+//! assert_eq!(ef.get(3), Some(13));
+//! assert_eq!(ef.next_geq(16), Some((6, 21)));
+//! assert_eq!(ef.next_geq(44), None);
+//! assert!(ef.iter().eq(keys.iter().cloned()));
+//!
+//! let bytes = ef.to_bytes();
+//! assert_eq!(EliasFano::<u64>::from_bytes(&bytes), Ok(ef));
+//! ```
+
+use std::fmt;
+use std::iter;
+use std::marker::PhantomData;
+
+use alloc::vec::Vec;
+
+use word::{self, UnsignedWord};
+
+/// One in `SAMPLE` set/cleared upper bits has its position stored.
+const SAMPLE: usize = 256;
+
+/// Error deserializing an Elias-Fano sequence.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ends before the end of the sequence.
+    Truncated,
+    /// The sequence was serialized with a different word type.
+    WordSize,
+    /// The buffer does not contain a valid sequence.
+    Invalid,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Error::Truncated => "the buffer is truncated",
+            Error::WordSize => "the sequence was serialized with a different word type",
+            Error::Invalid => "the buffer does not contain a valid Elias-Fano sequence",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::libstd::error::Error for Error {}
+
+/// Elias-Fano encoded sorted sequence of words of type `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EliasFano<T: UnsignedWord> {
+    len: usize,
+    /// Number of lower bits of each element stored in `lows`.
+    low_bits: u32,
+    lows: Vec<u64>,
+    /// Element `i` is stored as the set bit `(element >> low_bits) + i`.
+    highs: Vec<u64>,
+    high_len: usize,
+    /// Positions of the `i * SAMPLE`-th set bit of `highs`.
+    ones_samples: Vec<usize>,
+    /// Positions of the `i * SAMPLE`-th cleared bit of `highs`.
+    zeros_samples: Vec<usize>,
+    word: PhantomData<T>,
+}
+
+/// Mask of the lower `bits` bits of a `u128`.
+#[inline]
+fn low_mask(bits: u32) -> u128 {
+    if bits >= 128 {
+        !0
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+/// Reads the `width <= 128` bits at position `position` of `words`.
+#[inline]
+fn read_bits(words: &[u64], position: usize, width: u32) -> u128 {
+    let mut value = 0;
+    let mut done = 0;
+    while done < width {
+        let p = position + done as usize;
+        let (word, bit) = (words[p / 64], (p % 64) as u32);
+        let take = (64 - bit).min(width - done);
+        let bits = if take == 64 { word } else { word::extract_bits(word, bit, take) };
+        value |= (bits as u128) << done;
+        done += take;
+    }
+    value
+}
+
+/// Writes the `width <= 128` lower bits of `value` at position `position` of
+/// `words`, whose bits must be cleared.
+#[inline]
+fn write_bits(words: &mut [u64], position: usize, width: u32, value: u128) {
+    let mut done = 0;
+    while done < width {
+        let p = position + done as usize;
+        let bit = (p % 64) as u32;
+        let take = (64 - bit).min(width - done);
+        words[p / 64] |= (((value >> done) & low_mask(take)) as u64) << bit;
+        done += take;
+    }
+}
+
+/// Positions of every `SAMPLE`-th set bit (cleared bit if `ones` is false)
+/// of the first `len` bits of `words`.
+fn samples(words: &[u64], len: usize, ones: bool) -> Vec<usize> {
+    let mut samples = Vec::new();
+    let mut count = 0;
+    for (i, &w) in words.iter().enumerate() {
+        let mut w = if ones { w } else { !w };
+        if (i + 1) * 64 > len {
+            w = word::clear_bits_geq(w, (len % 64) as u32);
+        }
+        let c = w.count_ones() as usize;
+        // The next sample is the `k`-th bit of this word:
+        while samples.len() * SAMPLE < count + c {
+            let k = samples.len() * SAMPLE - count;
+            samples.push(i * 64 + word::select_in_word(w, k as u32) as usize);
+        }
+        count += c;
+    }
+    samples
+}
+
+impl<T: UnsignedWord> EliasFano<T> {
+    /// Encodes the sorted sequence `values`.
+    ///
+    /// # Panics
+    ///
+    /// If `values` is not sorted.
+    pub fn new(values: &[T]) -> Self {
+        assert!(values.windows(2).all(|w| w[0] <= w[1]), "the values are not sorted");
+        let len = values.len();
+        let last = values.last().map_or(0, |v| v.to_u128());
+        let quotient = if len == 0 { 0 } else { last / len as u128 };
+        let low_bits = if quotient == 0 {
+            0
+        } else {
+            127 - word::count_leading_zeros(quotient) as u32
+        };
+        let high_len = if len == 0 { 0 } else { len + (last >> low_bits) as usize + 1 };
+        let mut lows: Vec<u64> = iter::repeat(0).take((len * low_bits as usize + 63) / 64).collect();
+        let mut highs: Vec<u64> = iter::repeat(0).take((high_len + 63) / 64).collect();
+        for (i, v) in values.iter().enumerate() {
+            let v = v.to_u128();
+            write_bits(&mut lows, i * low_bits as usize, low_bits, v & low_mask(low_bits));
+            let position = (v >> low_bits) as usize + i;
+            highs[position / 64] |= 1 << (position % 64);
+        }
+        Self::from_parts(len, low_bits, lows, highs, high_len)
+    }
+
+    fn from_parts(len: usize, low_bits: u32, lows: Vec<u64>, highs: Vec<u64>, high_len: usize)
+                  -> Self {
+        EliasFano {
+            len,
+            low_bits,
+            lows,
+            ones_samples: samples(&highs, high_len, true),
+            zeros_samples: samples(&highs, high_len, false),
+            highs,
+            high_len,
+            word: PhantomData,
+        }
+    }
+
+    /// Number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the sequence empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Size of the encoded sequence in bits, without the samples.
+    #[inline]
+    pub fn size_in_bits(&self) -> usize {
+        (self.lows.len() + self.highs.len()) * 64
+    }
+
+    /// Position of the `k`-th set bit (`ones`) or cleared bit of `highs`,
+    /// which must exist.
+    #[inline]
+    fn select(&self, k: usize, ones: bool) -> usize {
+        let samples = if ones { &self.ones_samples } else { &self.zeros_samples };
+        let position = samples[k / SAMPLE];
+        let mut k = k % SAMPLE;
+        let mut i = position / 64;
+        let load = |i: usize| if ones { self.highs[i] } else { !self.highs[i] };
+        let mut w = load(i) & (!0 << (position % 64));
+        loop {
+            let c = w.count_ones() as usize;
+            if k < c {
+                return i * 64 + word::select_in_word(w, k as u32) as usize;
+            }
+            k -= c;
+            i += 1;
+            w = load(i);
+        }
+    }
+
+    /// Value of the element whose upper bits are stored at `position`.
+    #[inline]
+    fn value(&self, i: usize, position: usize) -> T {
+        let low = read_bits(&self.lows, i * self.low_bits as usize, self.low_bits);
+        T::from_u128((((position - i) as u128) << self.low_bits) | low)
+    }
+
+    /// Element `i`, or `None` if `i >= len()`.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<T> {
+        if i >= self.len {
+            return None;
+        }
+        Some(self.value(i, self.select(i, true)))
+    }
+
+    /// Index and value of the first element `>= x`, if any.
+    pub fn next_geq(&self, x: T) -> Option<(usize, T)> {
+        let x = x.to_u128();
+        let high = x >> self.low_bits;
+        let zeros = self.high_len - self.len;
+        if high >= zeros as u128 {
+            return None;
+        }
+        // The elements with upper bits `>= high` start after the
+        // `high - 1`-th cleared bit:
+        let position = if high == 0 { 0 } else { self.select(high as usize - 1, false) + 1 };
+        let mut iter = Iter { ef: self, index: position - high as usize, position };
+        while let Some(v) = iter.next() {
+            if v.to_u128() >= x {
+                return Some((iter.index - 1, v));
+            }
+        }
+        None
+    }
+
+    /// Iterator over the elements.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { ef: self, index: 0, position: 0 }
+    }
+
+    /// Serializes the sequence.
+    ///
+    /// The format is: the number of bits of `T` and the number of lower bits
+    /// (one byte each), the number of elements and the number of upper bits
+    /// (little-endian `u64` each), and the words of the lower and upper bits
+    /// (little-endian `u64`s).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(18 + 8 * (self.lows.len() + self.highs.len()));
+        bytes.push(T::bit_size().to_u32() as u8);
+        bytes.push(self.low_bits as u8);
+        bytes.extend_from_slice(&(self.len as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.high_len as u64).to_le_bytes());
+        for w in self.lows.iter().chain(self.highs.iter()) {
+            bytes.extend_from_slice(&w.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a sequence serialized with `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 18 {
+            return Err(Error::Truncated);
+        }
+        if bytes[0] != T::bit_size().to_u32() as u8 {
+            return Err(Error::WordSize);
+        }
+        let low_bits = bytes[1] as u32;
+        let mut u64s = bytes[2..].chunks(8).map(|c| {
+            let mut b = [0; 8];
+            b.copy_from_slice(c);
+            u64::from_le_bytes(b)
+        });
+        let len = u64s.next().unwrap() as usize;
+        let high_len = u64s.next().unwrap() as usize;
+        let bits = T::bit_size().to_u32();
+        // The largest upper bits, `high_len - len - 1`, must fit in `T`:
+        if (len == 0) != (high_len == 0) || (len > 0 && high_len <= len) ||
+           (low_bits > 0 && low_bits >= bits) ||
+           (len > 0 && ((high_len - len - 1) as u128).checked_shr(bits - low_bits).unwrap_or(0) != 0) {
+            return Err(Error::Invalid);
+        }
+        let words = |bits: usize| bits / 64 + (bits % 64 != 0) as usize;
+        let low_words = words(len.checked_mul(low_bits as usize).ok_or(Error::Invalid)?);
+        let high_words = words(high_len);
+        let expected = low_words.checked_add(high_words)
+            .and_then(|w| w.checked_mul(8))
+            .and_then(|b| b.checked_add(18))
+            .ok_or(Error::Invalid)?;
+        if bytes.len() < expected {
+            return Err(Error::Truncated);
+        }
+        if bytes.len() > expected {
+            return Err(Error::Invalid);
+        }
+        let lows: Vec<u64> = u64s.by_ref().take(low_words).collect();
+        let highs: Vec<u64> = u64s.collect();
+        let ones: usize = highs.iter().map(|w| w.count_ones() as usize).sum();
+        // The last element is followed by exactly one cleared bit:
+        let test = |i: usize| highs[i / 64] & (1 << (i % 64)) != 0;
+        if ones != len || (len > 0 && (!test(high_len - 2) || test(high_len - 1))) ||
+           (high_len % 64 != 0 && highs[high_words - 1] >> (high_len % 64) != 0) {
+            return Err(Error::Invalid);
+        }
+        Ok(Self::from_parts(len, low_bits, lows, highs, high_len))
+    }
+}
+
+/// Iterator over the elements of an [`EliasFano`](struct.EliasFano.html)
+/// sequence.
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T: UnsignedWord> {
+    ef: &'a EliasFano<T>,
+    /// Index of the next element.
+    index: usize,
+    /// Position of the upper bits to search the next set bit from.
+    position: usize,
+}
+
+impl<'a, T: UnsignedWord> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.index >= self.ef.len {
+            return None;
+        }
+        let mut i = self.position / 64;
+        let mut w = self.ef.highs[i] & (!0 << (self.position % 64));
+        while w == 0 {
+            i += 1;
+            w = self.ef.highs[i];
+        }
+        let position = i * 64 + w.trailing_zeros() as usize;
+        let value = self.ef.value(self.index, position);
+        self.index += 1;
+        self.position = position + 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.ef.len - self.index;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: UnsignedWord> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T: UnsignedWord> IntoIterator for &'a EliasFano<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    fn check<T: UnsignedWord + fmt::Debug>(values: &[T]) -> bool {
+        let ef = EliasFano::new(values);
+        let matches = |ef: &EliasFano<T>| {
+            ef.len() == values.len() && ef.iter().eq(values.iter().cloned()) &&
+            (0..values.len() + 1).all(|i| ef.get(i) == values.get(i).cloned())
+        };
+        let next_geq = |x: T| {
+            values.iter().position(|&v| v >= x).map(|i| (i, values[i]))
+        };
+        matches(&ef) &&
+        values.iter().all(|&v| {
+            ef.next_geq(v) == next_geq(v) &&
+            (v == T::zero() || ef.next_geq(v - T::one()) == next_geq(v - T::one())) &&
+            (v == !T::zero() || ef.next_geq(v + T::one()) == next_geq(v + T::one()))
+        }) &&
+        ef.next_geq(T::zero()) == next_geq(T::zero()) &&
+        EliasFano::from_bytes(&ef.to_bytes()).as_ref() == Ok(&ef)
+    }
+
+    #[test]
+    fn prop_elias_fano() {
+        fn inner(mut values: Vec<u64>, scale: u8) -> bool {
+            for v in &mut values {
+                *v = v.wrapping_mul(1 << (scale % 48));
+            }
+            values.sort();
+            let small: Vec<u8> = values.iter().map(|&v| v as u8).collect::<Vec<_>>();
+            let mut small = small;
+            small.sort();
+            let wide: Vec<u128> = values.iter().map(|&v| (v as u128) << 60).collect();
+            check(&values) && check(&small) && check(&wide)
+        }
+        QuickCheck::new().quickcheck(inner as fn(Vec<u64>, u8) -> bool);
+    }
+
+    #[test]
+    fn dense_and_extremes() {
+        let dense: Vec<u32> = (0..5000).collect();
+        assert!(check(&dense));
+        let repeated = [7u16; 1000];
+        assert!(check(&repeated));
+        assert!(check(&[0u64, u64::MAX]));
+        assert!(check(&[u128::MAX]));
+        assert!(check::<u8>(&[]));
+    }
+
+    #[test]
+    fn errors() {
+        let ef = EliasFano::new(&[1u32, 5, 9, 300]);
+        let bytes = ef.to_bytes();
+        assert_eq!(EliasFano::<u64>::from_bytes(&bytes), Err(Error::WordSize));
+        assert_eq!(EliasFano::<u32>::from_bytes(&bytes[..bytes.len() - 1]),
+                   Err(Error::Truncated));
+        assert_eq!(EliasFano::<u32>::from_bytes(&bytes[..5]), Err(Error::Truncated));
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert_eq!(EliasFano::<u32>::from_bytes(&longer), Err(Error::Invalid));
+        let mut corrupted = bytes;
+        *corrupted.last_mut().unwrap() ^= 0x80;
+        assert_eq!(EliasFano::<u32>::from_bytes(&corrupted), Err(Error::Invalid));
+    }
+}
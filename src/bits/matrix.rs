@@ -0,0 +1,532 @@
+//! Matrices over GF(2).
+//!
+//! A matrix is a slice of rows, each row stored in a word: the element
+//! `(r, c)` of the matrix `m` is the bit `c` of `m[r]`. A matrix with words
+//! of type `T` has at most `T::bit_size()` columns, and the columns past the
+//! last one are ignored. Vectors are words too: the element `i` of the vector
+//! `v` is the bit `i` of `v`.
+//!
+//! Addition is XOR and multiplication is AND, so that the product of a row
+//! and a column vector is the [`parity`](../../word/fn.parity.html) of their
+//! AND, and adding a multiple of a row to another is a single XOR.
+//!
+//! [`mul_four_russians`](fn.mul_four_russians.html) multiplies matrices with
+//! the Method of the Four Russians (Arlazarov et al., 1970):
+//! each group of 8 rows of the right-hand side is replaced by a table of its
+//! 256 linear combinations, so that every row of the product costs
+//! `bit_size / 8` look-ups instead of up to `bit_size` XORs.
+//! [`mul_64x64`](fn.mul_64x64.html) is the same algorithm specialized for
+//! 64x64 matrices, with groups of 4 rows.
+//!
+//! [`rank`](fn.rank.html), [`invert`](fn.invert.html) and
+//! [`solve`](fn.solve.html) use Gaussian elimination and need no heap
+//! allocation.
+//!
+//! # Example
+//!
+//! ```
+//! use bitwise::bits::matrix::*;
+//!
+//! // The 3x3 matrix
+//! //   1 1 0
+//! //   0 1 1
+//! //   0 0 1
+//! let m = [0b011u8, 0b110, 0b100];
+//! assert_eq!(mul_vec(&m, 0b101), 0b111);
+//! assert_eq!(rank(&m), 3);
+//!
+//! let mut inv = m;
+//! assert!(invert(&mut inv));
+//! let mut id = [0; 3];
+//! mul(&m, &inv, &mut id);
+//! assert_eq!(id, [0b001, 0b010, 0b100]);
+//!
+//! assert_eq!(solve(&m, 0b111), Some(0b101));
+//! assert_eq!(solve(&[0b11u8, 0b11], 0b01), None);
+//! ```
+
+use word::{self, UnsignedWord};
+
+/// Maximum number of rows or columns of a matrix that is copied on the
+/// stack: the number of bits of the widest word.
+const MAX_BITS: usize = 128;
+
+/// Number of columns of a matrix of `T` words.
+#[inline]
+fn bits<T: UnsignedWord>() -> usize {
+    T::bit_size().to_usize()
+}
+
+/// Whether the bit `i` of `x` is set.
+#[inline]
+fn test<T: UnsignedWord>(x: T, i: usize) -> bool {
+    word::test_bit(x, i as u32)
+}
+
+/// Product of the matrix `m` and the column vector `v`.
+///
+/// The bit `r` of the result is the parity of `m[r] & v`.
+///
+/// # Panics
+///
+/// If `m` has more than `T::bit_size()` rows.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// let m = [0b0011u8, 0b0101, 0b1111];
+/// assert_eq!(mul_vec(&m, 0b0001), 0b111);
+/// assert_eq!(mul_vec(&m, 0b0011), 0b010);
+/// ```
+#[inline]
+pub fn mul_vec<T: UnsignedWord>(m: &[T], v: T) -> T {
+    assert!(m.len() <= bits::<T>(), "the product has more than {} rows", bits::<T>());
+    m.iter().enumerate().fold(T::zero(), |acc, (r, &row)| {
+        acc | (word::parity(row & v) << T::from_u32(r as u32))
+    })
+}
+
+/// Product of the row vector `v` and the matrix `m`.
+///
+/// The result is the XOR of the rows `m[r]` for the set bits `r` of `v`.
+/// The bits of `v` past the number of rows of `m` are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// let m = [0b0011u8, 0b0101, 0b1111];
+/// assert_eq!(vec_mul(0b011, &m), 0b0110);
+/// assert_eq!(vec_mul(0b111, &m), 0b1001);
+/// ```
+#[inline]
+pub fn vec_mul<T: UnsignedWord>(v: T, m: &[T]) -> T {
+    let mut v = v;
+    let mut acc = T::zero();
+    while v != T::zero() {
+        let r = word::count_trailing_zeros(v).to_usize();
+        if r >= m.len() {
+            break;
+        }
+        acc = acc ^ m[r];
+        v = word::clear_least_significant_one(v);
+    }
+    acc
+}
+
+/// Product `c = a * b` of the matrices `a` and `b`.
+///
+/// The number of columns of `a` is the number of rows of `b`: the bits of
+/// the rows of `a` past `b.len()` are ignored, and so are the rows of `b`
+/// past `T::bit_size()`.
+///
+/// # Panics
+///
+/// If `c` does not have as many rows as `a`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// let a = [0b01u8, 0b11];
+/// let b = [0b10u8, 0b11];
+/// let mut c = [0; 2];
+/// mul(&a, &b, &mut c);
+/// assert_eq!(c, [0b10, 0b01]);
+/// ```
+#[inline]
+pub fn mul<T: UnsignedWord>(a: &[T], b: &[T], c: &mut [T]) {
+    assert_eq!(a.len(), c.len(), "the product does not have as many rows as the left-hand side");
+    for (c, &a) in c.iter_mut().zip(a) {
+        *c = vec_mul(a, b);
+    }
+}
+
+/// Product `c = a * b` of the matrices `a` and `b` with the Method of the
+/// Four Russians.
+///
+/// Equivalent to [`mul`](fn.mul.html), and faster when `a` has many more
+/// than 256 / 8 rows. The rows of `b` past `T::bit_size()` are ignored.
+///
+/// # Panics
+///
+/// If `c` does not have as many rows as `a`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// let a = [0b01u8, 0b11];
+/// let b = [0b10u8, 0b11];
+/// let mut c = [0; 2];
+/// mul_four_russians(&a, &b, &mut c);
+/// assert_eq!(c, [0b10, 0b01]);
+/// ```
+pub fn mul_four_russians<T: UnsignedWord>(a: &[T], b: &[T], c: &mut [T]) {
+    assert_eq!(a.len(), c.len(), "the product does not have as many rows as the left-hand side");
+    for c in c.iter_mut() {
+        *c = T::zero();
+    }
+    let mut table = [T::zero(); 256];
+    for (g, rows) in b.chunks(8).take(bits::<T>() / 8).enumerate() {
+        // The entry `i` is the XOR of the rows of the group selected by the
+        // bits of `i`:
+        for i in 1..256usize {
+            let k = i.trailing_zeros() as usize;
+            let row = if k < rows.len() { rows[k] } else { T::zero() };
+            table[i] = table[i & (i - 1)] ^ row;
+        }
+        let shift = T::from_u32(8 * g as u32);
+        for (c, &a) in c.iter_mut().zip(a) {
+            *c = *c ^ table[word::shift_logical_right(a, shift).to_u8() as usize];
+        }
+    }
+}
+
+/// Product of the 64x64 matrices `a` and `b`.
+///
+/// The Method of the Four Russians (see
+/// [`mul_four_russians`](fn.mul_four_russians.html)) with groups of 4 rows
+/// of `b`, whose 16 tables of 16 entries are cheaper to build than the 8
+/// tables of 256 entries of groups of 8 rows for a product of only 64 rows.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// let mut id = [0u64; 64];
+/// let mut a = [0u64; 64];
+/// for r in 0..64 {
+///     id[r] = 1 << r;
+///     a[r] = (r as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+/// }
+/// assert_eq!(mul_64x64(&a, &id)[..], a[..]);
+/// assert_eq!(mul_64x64(&id, &a)[..], a[..]);
+/// ```
+pub fn mul_64x64(a: &[u64; 64], b: &[u64; 64]) -> [u64; 64] {
+    let mut tables = [[0u64; 16]; 16];
+    for (table, rows) in tables.iter_mut().zip(b.chunks(4)) {
+        for i in 1..16usize {
+            table[i] = table[i & (i - 1)] ^ rows[i.trailing_zeros() as usize];
+        }
+    }
+    let mut c = [0u64; 64];
+    for (c, &a) in c.iter_mut().zip(a) {
+        *c = tables.iter().enumerate().fold(0, |acc, (g, table)| {
+            acc ^ table[(a >> (4 * g)) as usize & 0xF]
+        });
+    }
+    c
+}
+
+/// Rank of the matrix `m`.
+///
+/// `m` can have any number of rows. The rows are reduced one by one against
+/// a basis of the rows seen so far, indexed by their most significant bit.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// assert_eq!(rank(&[0b011u8, 0b110, 0b101]), 2);
+/// assert_eq!(rank(&[0b011u8, 0b110, 0b100, 0b111]), 3);
+/// assert_eq!(rank::<u32>(&[]), 0);
+/// ```
+pub fn rank<T: UnsignedWord>(m: &[T]) -> usize {
+    let mut basis = [T::zero(); MAX_BITS];
+    let mut rank = 0;
+    for &row in m {
+        let mut row = row;
+        while row != T::zero() {
+            let msb = bits::<T>() - 1 - word::count_leading_zeros(row).to_usize();
+            if basis[msb] == T::zero() {
+                basis[msb] = row;
+                rank += 1;
+                break;
+            }
+            row = row ^ basis[msb];
+        }
+    }
+    rank
+}
+
+/// Inverts the square matrix `m` in place.
+///
+/// The matrix has `m.len()` rows and columns, and the bits of the rows past
+/// `m.len()` are ignored (and cleared in the inverse).
+///
+/// Returns `false` and leaves `m` unchanged if `m` is singular.
+///
+/// # Panics
+///
+/// If `m` has more than `T::bit_size()` rows.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// let mut m = [0b01u8, 0b11];
+/// assert!(invert(&mut m));
+/// assert_eq!(m, [0b01, 0b11]);
+///
+/// let mut m = [0b011u16, 0b110, 0b101];
+/// assert!(!invert(&mut m));
+/// assert_eq!(m, [0b011, 0b110, 0b101]);
+/// ```
+pub fn invert<T: UnsignedWord>(m: &mut [T]) -> bool {
+    let n = m.len();
+    assert!(n <= bits::<T>(), "the matrix has more than {} rows", bits::<T>());
+    let mut a = [T::zero(); MAX_BITS];
+    let mut inv = [T::zero(); MAX_BITS];
+    a[..n].copy_from_slice(m);
+    for (r, row) in inv[..n].iter_mut().enumerate() {
+        *row = T::one() << T::from_u32(r as u32);
+    }
+    // Gauss-Jordan elimination on `[a | inv]`:
+    for col in 0..n {
+        let pivot = match (col..n).find(|&r| test(a[r], col)) {
+            Some(p) => p,
+            None => return false,
+        };
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+        for r in 0..n {
+            if r != col && test(a[r], col) {
+                a[r] = a[r] ^ a[col];
+                inv[r] = inv[r] ^ inv[col];
+            }
+        }
+    }
+    m.copy_from_slice(&inv[..n]);
+    true
+}
+
+/// Solves the linear system `mul_vec(a, x) == b`.
+///
+/// The system has `a.len()` equations and `T::bit_size()` unknowns. Returns
+/// the solution whose free unknowns are zero, or `None` if the system has no
+/// solution.
+///
+/// # Panics
+///
+/// If `a` has more than `T::bit_size()` rows.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::bits::matrix::*;
+///
+/// // x0 + x1 = 1, x1 + x2 = 0:
+/// let a = [0b011u8, 0b110];
+/// let x = solve(&a, 0b01).unwrap();
+/// assert_eq!(x, 0b001);
+/// assert_eq!(mul_vec(&a, x), 0b01);
+///
+/// // x0 = 1, x0 = 0:
+/// assert_eq!(solve(&[0b1u8, 0b1], 0b01), None);
+/// ```
+pub fn solve<T: UnsignedWord>(a: &[T], b: T) -> Option<T> {
+    let n = a.len();
+    assert!(n <= bits::<T>(), "the system has more than {} equations", bits::<T>());
+    let mut rows = [T::zero(); MAX_BITS];
+    let mut rhs = [false; MAX_BITS];
+    let mut pivots = [0usize; MAX_BITS];
+    rows[..n].copy_from_slice(a);
+    for (r, rhs) in rhs[..n].iter_mut().enumerate() {
+        *rhs = test(b, r);
+    }
+    // Reduced row echelon form of `[a | b]`:
+    let mut rank = 0;
+    for col in 0..bits::<T>() {
+        let pivot = match (rank..n).find(|&r| test(rows[r], col)) {
+            Some(p) => p,
+            None => continue,
+        };
+        rows.swap(rank, pivot);
+        rhs.swap(rank, pivot);
+        for r in 0..n {
+            if r != rank && test(rows[r], col) {
+                rows[r] = rows[r] ^ rows[rank];
+                rhs[r] ^= rhs[rank];
+            }
+        }
+        pivots[rank] = col;
+        rank += 1;
+    }
+    if rhs[rank..n].iter().any(|&r| r) {
+        return None;
+    }
+    Some((0..rank).filter(|&i| rhs[i]).fold(T::zero(), |x, i| {
+        word::set_bit(x, pivots[i] as u32)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use test_util::random;
+
+    /// Fills `m` with pseudo-random rows from `seed`, keeping `mask` bits.
+    fn fill<T: UnsignedWord>(seed: u64, mask: T, m: &mut [T]) {
+        for (row, r) in m.iter_mut().zip(random(seed)) {
+            *row = T::from_u128(r) & mask;
+        }
+    }
+
+    fn naive_mul<T: UnsignedWord>(a: &[T], b: &[T], c: &mut [T]) {
+        for (r, c) in c.iter_mut().enumerate() {
+            *c = T::zero();
+            for j in 0..bits::<T>() {
+                let column = b.iter().enumerate().fold(T::zero(), |v, (k, &row)| {
+                    if test(row, j) { word::set_bit(v, k as u32) } else { v }
+                });
+                if word::parity(a[r] & column) == T::one() {
+                    *c = word::set_bit(*c, j as u32);
+                }
+            }
+        }
+    }
+
+    fn check_mul<T: UnsignedWord>(seed: u64, rows: usize) -> bool {
+        let (mut a, mut b) = ([T::zero(); 300], [T::zero(); MAX_BITS]);
+        let (a, b) = (&mut a[..rows], &mut b[..bits::<T>()]);
+        let mask = !T::zero();
+        fill(seed, mask, a);
+        fill(seed ^ 0xFFFF, mask, b);
+        let (mut c0, mut c1, mut c2) = ([T::zero(); 300], [T::zero(); 300], [T::zero(); 300]);
+        naive_mul(a, b, &mut c0[..rows]);
+        mul(a, b, &mut c1[..rows]);
+        mul_four_russians(a, b, &mut c2[..rows]);
+        c0[..] == c1[..] && c1[..] == c2[..]
+    }
+
+    #[test]
+    fn prop_mul() {
+        fn inner(seed: u64, rows: u8) -> bool {
+            let rows = rows as usize;
+            check_mul::<u8>(seed, rows) && check_mul::<u16>(seed, rows) &&
+            check_mul::<u32>(seed, rows) && check_mul::<u64>(seed, rows) &&
+            check_mul::<u128>(seed, rows / 4)
+        }
+        QuickCheck::new().tests(20).quickcheck(inner as fn(u64, u8) -> bool);
+    }
+
+    #[test]
+    fn prop_mul_short_right_hand_side() {
+        fn inner(seed: u64, rows: u8) -> bool {
+            let (mut a, mut b) = ([0u32; 8], [0u32; 32]);
+            fill(seed, !0, &mut a);
+            fill(!seed, !0, &mut b);
+            let b = &b[..rows as usize % 33];
+            let (mut c0, mut c1) = ([0u32; 8], [0u32; 8]);
+            mul(&a, b, &mut c0);
+            mul_four_russians(&a, b, &mut c1);
+            c0 == c1 && a.iter().zip(&c0).all(|(&a, &c)| c == vec_mul(a, b))
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u8) -> bool);
+
+        // The rows of `b` past the number of bits of the words are ignored:
+        let (a, b) = ([1u8, 3], [2u8; 16]);
+        let (mut c0, mut c1) = ([0u8; 2], [0u8; 2]);
+        mul(&a, &b, &mut c0);
+        mul_four_russians(&a, &b, &mut c1);
+        assert_eq!(c0, [2, 0]);
+        assert_eq!(c1, c0);
+    }
+
+    #[test]
+    fn prop_mul_64x64() {
+        fn inner(seed: u64) -> bool {
+            let (mut a, mut b) = ([0u64; 64], [0u64; 64]);
+            fill(seed, !0, &mut a);
+            fill(!seed, !0, &mut b);
+            let mut c = [0u64; 64];
+            mul(&a, &b, &mut c);
+            mul_64x64(&a, &b)[..] == c[..]
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64) -> bool);
+    }
+
+    fn check_invert<T: UnsignedWord>(seed: u64, n: usize, sparse: bool) -> bool {
+        let n = n % (bits::<T>() + 1);
+        let mask = if n == bits::<T>() { !T::zero() } else { !(!T::zero() << T::from_u32(n as u32)) };
+        let mut m = [T::zero(); MAX_BITS];
+        let m = &mut m[..n];
+        fill(seed, mask, m);
+        if sparse {
+            // Singular more often:
+            let mut other = [T::zero(); MAX_BITS];
+            fill(!seed, mask, &mut other[..n]);
+            for (m, &o) in m.iter_mut().zip(&other[..n]) {
+                *m = *m & o;
+            }
+        }
+        let mut inv = [T::zero(); MAX_BITS];
+        let inv = &mut inv[..n];
+        inv.copy_from_slice(m);
+        if !invert(inv) {
+            return rank(m) < n && inv[..] == m[..];
+        }
+        let mut id = [T::zero(); MAX_BITS];
+        mul(m, inv, &mut id[..n]);
+        rank(m) == n &&
+        id[..n].iter().enumerate().all(|(r, &row)| row == T::one() << T::from_u32(r as u32))
+    }
+
+    #[test]
+    fn prop_invert() {
+        fn inner(seed: u64, n: u8, sparse: bool) -> bool {
+            let n = n as usize;
+            check_invert::<u8>(seed, n, sparse) && check_invert::<u16>(seed, n, sparse) &&
+            check_invert::<u32>(seed, n, sparse) && check_invert::<u64>(seed, n, sparse) &&
+            check_invert::<u128>(seed, n, sparse)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u8, bool) -> bool);
+    }
+
+    fn check_solve<T: UnsignedWord>(seed: u64, n: usize, x: T, b: T) -> bool {
+        let n = n % (bits::<T>() + 1);
+        // Low rank matrices, for which most right-hand sides have no
+        // solution. The highest column is zero, to append `b` below:
+        let mut basis = [T::zero(); 4];
+        fill(seed, word::shift_logical_right(!T::zero(), T::one()), &mut basis);
+        let mut selectors = [T::zero(); MAX_BITS];
+        fill(!seed, T::from_u8(0b1111), &mut selectors[..n]);
+        let mut a = [T::zero(); MAX_BITS];
+        let a = &mut a[..n];
+        mul(&selectors[..n], &basis, a);
+
+        // The system has a solution iff appending `b` as a column does not
+        // increase the rank:
+        let b = (0..n).filter(|&r| test(b, r)).fold(T::zero(), |v, r| word::set_bit(v, r as u32));
+        let mut augmented = [T::zero(); MAX_BITS];
+        for (r, row) in augmented[..n].iter_mut().enumerate() {
+            *row = a[r] << T::one() | if test(b, r) { T::one() } else { T::zero() };
+        }
+        let solvable = rank(a) == rank(&augmented[..n]);
+
+        solve(a, mul_vec(a, x)).map(|y| mul_vec(a, y)) == Some(mul_vec(a, x)) &&
+        solve(a, b).map_or(!solvable, |y| solvable && mul_vec(a, y) == b)
+    }
+
+    #[test]
+    fn prop_solve() {
+        fn inner(seed: u64, n: u8, x: u64, b: u64) -> bool {
+            let n = n as usize;
+            check_solve::<u8>(seed, n, x as u8, b as u8) &&
+            check_solve::<u16>(seed, n, x as u16, b as u16) &&
+            check_solve::<u32>(seed, n, x as u32, b as u32) &&
+            check_solve::<u64>(seed, n, x, b)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u8, u64, u64) -> bool);
+    }
+}
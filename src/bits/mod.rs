@@ -0,0 +1,134 @@
+//! Algorithms and data-structures for sequences of bits stored in words.
+//!
+//! The bits are numbered from the least significant bit of the first word:
+//! bit `i` is bit `i % W::bit_size()` of the word `i / W::bit_size()`.
+//!
+//! The heap-allocated [`BitVec`](struct.BitVec.html) and
+//! [`RankSelect`](struct.RankSelect.html) index, and the
+//! [`elias_fano`](elias_fano/index.html) encoding of monotone sequences
+//! require the `alloc` cargo feature.
+
+mod bit_array;
+pub use self::bit_array::*;
+
+mod bit_slice;
+pub use self::bit_slice::*;
+
+#[cfg(feature = "alloc")]
+mod bit_vec;
+#[cfg(feature = "alloc")]
+pub use self::bit_vec::*;
+
+#[cfg(feature = "alloc")]
+mod rank_select;
+#[cfg(feature = "alloc")]
+pub use self::rank_select::*;
+
+#[cfg(feature = "alloc")]
+pub mod elias_fano;
+
+mod ones;
+pub use self::ones::*;
+
+mod stream;
+pub use self::stream::*;
+
+pub mod codes;
+
+pub mod packing;
+
+pub mod matrix;
+
+use std::ops::{Bound, RangeBounds};
+
+use word::{self, UnsignedWord};
+
+/// Converts `range` into the half-open range `start..end` of bit positions.
+///
+/// # Panics
+///
+/// If `start > end` or `end > len`.
+#[inline]
+fn bounds<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e + 1,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range start {} is greater than its end {}", start, end);
+    assert!(end <= len, "range end {} is out of bounds ({} bits)", end, len);
+    (start, end)
+}
+
+/// Mask of the bits `start..end` of a word, with `start <= end <= W::bit_size()`.
+#[inline]
+fn word_mask<W: UnsignedWord>(start: usize, end: usize) -> W {
+    let bits = W::bit_size().to_usize();
+    let mask = if start < bits {
+        word::set_bits_geq(W::zero(), start as u32)
+    } else {
+        W::zero()
+    };
+    if end < bits {
+        word::clear_bits_geq(mask, end as u32)
+    } else {
+        mask
+    }
+}
+
+/// Iterator over the words that overlap with the bits `start..end`: yields
+/// the index of each word and the mask of its bits in the range.
+#[inline]
+fn word_masks<W: UnsignedWord>(start: usize, end: usize)
+                               -> impl DoubleEndedIterator<Item = (usize, W)> {
+    let bits = W::bit_size().to_usize();
+    let first = start / bits;
+    let last = (end + bits - 1) / bits;
+    (first..last).map(move |index| {
+        let word_start = index * bits;
+        let mask = word_mask(start.max(word_start) - word_start,
+                             end.min(word_start + bits) - word_start);
+        (index, mask)
+    })
+}
+
+/// Position of the first set bit of `words` in the bits `start..end`, after
+/// applying `f` to each word.
+#[inline]
+fn find_first<W: UnsignedWord, F: Fn(W) -> W>(words: &[W], start: usize, end: usize, f: F)
+                                              -> Option<usize> {
+    let bits = W::bit_size().to_usize();
+    word_masks(start, end).find_map(|(i, mask)| {
+        let w = f(words[i]) & mask;
+        if w == W::zero() {
+            None
+        } else {
+            Some(i * bits + word::count_trailing_zeros(w).to_usize())
+        }
+    })
+}
+
+/// Position of the last set bit of `words` in the bits `start..end`.
+#[inline]
+fn find_last<W: UnsignedWord>(words: &[W], start: usize, end: usize) -> Option<usize> {
+    let bits = W::bit_size().to_usize();
+    word_masks(start, end).rev().find_map(|(i, mask)| {
+        let w = words[i] & mask;
+        if w == W::zero() {
+            None
+        } else {
+            Some(i * bits + bits - 1 - word::count_leading_zeros(w).to_usize())
+        }
+    })
+}
+
+/// Number of set bits of `words` in the bits `start..end`.
+#[inline]
+fn count_ones<W: UnsignedWord>(words: &[W], start: usize, end: usize) -> usize {
+    word_masks(start, end).map(|(i, mask)| word::count_ones(words[i] & mask).to_usize()).sum()
+}
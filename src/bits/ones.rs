@@ -0,0 +1,79 @@
+use word::{self, UnsignedWord};
+use super::word_mask;
+
+/// Iterator over the positions of the set bits of a slice of words, in
+/// increasing order.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::Ones;
+///
+/// let words = [0b1001u8, 0b0100];
+/// let mut ones = Ones::new(&words);
+/// assert_eq!(ones.next(), Some(0));
+/// assert_eq!(ones.next(), Some(3));
+/// assert_eq!(ones.next(), Some(10));
+/// assert_eq!(ones.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ones<'a, W: UnsignedWord> {
+    words: &'a [W],
+    /// Bits of the current word in the range that have not been visited yet.
+    current: W,
+    /// Index of the current word.
+    index: usize,
+    /// Range of bits `start..end` of `words` that is visited.
+    start: usize,
+    end: usize,
+}
+
+impl<'a, W: UnsignedWord> Ones<'a, W> {
+    /// Iterator over the set bits of `words`.
+    #[inline]
+    pub fn new(words: &'a [W]) -> Self {
+        Self::from_range(words, 0, words.len() * W::bit_size().to_usize())
+    }
+
+    /// Iterator over the set bits `start..end` of `words`, whose positions
+    /// are relative to `start`.
+    #[inline]
+    pub(crate) fn from_range(words: &'a [W], start: usize, end: usize) -> Self {
+        let index = start / W::bit_size().to_usize();
+        let mut ones = Ones { words, current: W::zero(), index, start, end };
+        ones.current = ones.load();
+        ones
+    }
+
+    /// Bits of the current word in the range.
+    #[inline]
+    fn load(&self) -> W {
+        let bits = W::bit_size().to_usize();
+        let word_start = self.index * bits;
+        if word_start >= self.end {
+            return W::zero();
+        }
+        self.words[self.index] &
+        word_mask(self.start.max(word_start) - word_start,
+                  self.end.min(word_start + bits) - word_start)
+    }
+}
+
+impl<'a, W: UnsignedWord> Iterator for Ones<'a, W> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let bits = W::bit_size().to_usize();
+        while self.current == W::zero() {
+            if (self.index + 1) * bits >= self.end {
+                return None;
+            }
+            self.index += 1;
+            self.current = self.load();
+        }
+        let bit = word::count_trailing_zeros(self.current).to_usize();
+        self.current = word::clear_least_significant_one(self.current);
+        Some(self.index * bits + bit - self.start)
+    }
+}
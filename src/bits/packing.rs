@@ -0,0 +1,408 @@
+//! Bit-packing of blocks of integers, with frame of reference and patched
+//! exceptions.
+//!
+//! A block of 128 or 256 `u64`s whose values fit in `w` bits is packed into
+//! `w * len / 64` words. The block is split into `len / 64` lanes: value `i`
+//! belongs to lane `i % lanes`, each lane packs its 64 values one after the
+//! other into `w` words, and word `k` of lane `l` is stored at
+//! `packed[k * lanes + l]`. All lanes are packed in lockstep with the same
+//! shifts, which LLVM turns into SIMD instructions (2 lanes for SSE2/NEON, 4
+//! lanes for AVX2).
+//!
+//! [`pack_fixed`](fn.pack_fixed.html) and
+//! [`unpack_fixed`](fn.unpack_fixed.html) have a function for each width
+//! `0..=64`, whose shifts and masks are constants;
+//! [`pack`](fn.pack.html) and [`unpack`](fn.unpack.html) dispatch to them at
+//! run-time.
+//!
+//! Frame of reference (FOR) encoding subtracts the minimum of a block from
+//! its values before packing them, and patched frame of reference (PFor)
+//! encoding additionally packs the values at a smaller width and stores the
+//! high bits of the few values that do not fit as exceptions (Zukowski et
+//! al., "Super-Scalar RAM-CPU Cache Compression", 2006).
+//!
+//! # Example
+//!
+//! ```
+//! use bitwise::bits::packing::*;
+//!
+//! let mut values = [0u64; 128];
+//! for (i, v) in values.iter_mut().enumerate() {
+//!     *v = 1_000_000 + (i as u64 * 7) % 100;
+//! }
+//! values[42] = 5_000_000;
+//!
+//! // Frame of reference: 22 bits per value.
+//! let mut packed = [0u64; 128];
+//! let frame = encode_for(&values, &mut packed);
+//! assert_eq!(frame, Frame { reference: 1_000_000, width: 22, exceptions: 0 });
+//! let mut decoded = [0u64; 128];
+//! decode(&frame, &packed, &[], &[], &mut decoded);
+//! assert_eq!(decoded[..], values[..]);
+//!
+//! // Patched frame of reference: 7 bits per value plus one exception.
+//! let (mut positions, mut highs) = ([0u8; 128], [0u64; 128]);
+//! let frame = encode_pfor(&values, &mut packed, &mut positions, &mut highs);
+//! assert_eq!(frame, Frame { reference: 1_000_000, width: 7, exceptions: 1 });
+//! assert_eq!(packed_len(128, frame.width), 14);
+//! decode(&frame, &packed, &positions[..1], &highs[..1], &mut decoded);
+//! assert_eq!(decoded[..], values[..]);
+//! ```
+
+/// Number of words of a packed block of `len` values of `width` bits.
+#[inline]
+pub fn packed_len(len: usize, width: u32) -> usize {
+    len * width as usize / 64
+}
+
+/// Number of lanes of a block of `len` values.
+///
+/// # Panics
+///
+/// If `len` is not 128 or 256.
+#[inline]
+fn lanes(len: usize) -> usize {
+    assert!(len == 128 || len == 256, "blocks have 128 or 256 values, not {}", len);
+    len / 64
+}
+
+/// Mask of the lower `width` bits.
+#[inline(always)]
+fn mask(width: u32) -> u64 {
+    if width == 64 { !0 } else { (1 << width) - 1 }
+}
+
+/// Packs the `64 * L` values of `values` in `L` lanes.
+#[inline(always)]
+fn pack_lanes<const W: u32, const L: usize>(values: &[u64], packed: &mut [u64]) {
+    let (values, packed) = (&values[..64 * L], &mut packed[..W as usize * L]);
+    let mut acc = [0u64; L];
+    let mut filled = 0;
+    let mut k = 0;
+    for j in 0..64 {
+        for l in 0..L {
+            acc[l] |= (values[j * L + l] & mask(W)) << filled;
+        }
+        filled += W;
+        if filled >= 64 {
+            packed[k * L..(k + 1) * L].copy_from_slice(&acc);
+            k += 1;
+            filled -= 64;
+            // The bits of the values that did not fit:
+            for l in 0..L {
+                acc[l] = if filled == 0 {
+                    0
+                } else {
+                    (values[j * L + l] & mask(W)) >> (W - filled)
+                };
+            }
+        }
+    }
+}
+
+/// Unpacks the `64 * L` values of `L` lanes.
+#[inline(always)]
+fn unpack_lanes<const W: u32, const L: usize>(packed: &[u64], values: &mut [u64]) {
+    let values = &mut values[..64 * L];
+    if W == 0 {
+        for v in values.iter_mut() {
+            *v = 0;
+        }
+        return;
+    }
+    let packed = &packed[..W as usize * L];
+    for j in 0..64 {
+        let (k, offset) = (j * W as usize / 64, j as u32 * W % 64);
+        for l in 0..L {
+            let mut v = packed[k * L + l] >> offset;
+            if offset + W > 64 {
+                v |= packed[(k + 1) * L + l] << (64 - offset);
+            }
+            values[j * L + l] = v & mask(W);
+        }
+    }
+}
+
+/// Packs a block of values of `W` bits.
+///
+/// The bits of the values above `W` are ignored.
+///
+/// # Panics
+///
+/// If `values.len()` is not 128 or 256, or
+/// `packed.len() < packed_len(values.len(), W)`.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::packing::*;
+///
+/// let mut values = [7u64; 128];
+/// let mut packed = [0u64; 6];
+/// pack_fixed::<3>(&values, &mut packed);
+/// assert_eq!(packed, [!0; 6]);
+///
+/// for (i, v) in values.iter_mut().enumerate() {
+///     *v = i as u64 % 8;
+/// }
+/// pack_fixed::<3>(&values, &mut packed);
+/// let mut unpacked = [0u64; 128];
+/// unpack_fixed::<3>(&packed, &mut unpacked);
+/// assert_eq!(unpacked[..], values[..]);
+/// ```
+#[inline]
+pub fn pack_fixed<const W: u32>(values: &[u64], packed: &mut [u64]) {
+    assert!(W <= 64, "the width {} is greater than 64", W);
+    match lanes(values.len()) {
+        2 => pack_lanes::<W, 2>(values, packed),
+        _ => pack_lanes::<W, 4>(values, packed),
+    }
+}
+
+/// Unpacks a block of values of `W` bits.
+///
+/// # Panics
+///
+/// If `values.len()` is not 128 or 256, or
+/// `packed.len() < packed_len(values.len(), W)`.
+#[inline]
+pub fn unpack_fixed<const W: u32>(packed: &[u64], values: &mut [u64]) {
+    assert!(W <= 64, "the width {} is greater than 64", W);
+    match lanes(values.len()) {
+        2 => unpack_lanes::<W, 2>(packed, values),
+        _ => unpack_lanes::<W, 4>(packed, values),
+    }
+}
+
+macro_rules! width_tables {
+    ($($w:expr),*) => {
+        static PACK: [fn(&[u64], &mut [u64]); 65] = [$(pack_fixed::<$w>),*];
+        static UNPACK: [fn(&[u64], &mut [u64]); 65] = [$(unpack_fixed::<$w>),*];
+    }
+}
+
+width_tables!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
+              23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43,
+              44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64);
+
+/// Packs a block of values of `width` bits.
+///
+/// The bits of the values above `width` are ignored.
+///
+/// # Panics
+///
+/// If `width > 64`, `values.len()` is not 128 or 256, or
+/// `packed.len() < packed_len(values.len(), width)`.
+#[inline]
+pub fn pack(values: &[u64], width: u32, packed: &mut [u64]) {
+    assert!(width <= 64, "the width {} is greater than 64", width);
+    PACK[width as usize](values, packed)
+}
+
+/// Unpacks a block of values of `width` bits.
+///
+/// # Panics
+///
+/// If `width > 64`, `values.len()` is not 128 or 256, or
+/// `packed.len() < packed_len(values.len(), width)`.
+#[inline]
+pub fn unpack(packed: &[u64], width: u32, values: &mut [u64]) {
+    assert!(width <= 64, "the width {} is greater than 64", width);
+    UNPACK[width as usize](packed, values)
+}
+
+/// Number of bits of the largest value of `values`.
+#[inline]
+pub fn width_of(values: &[u64]) -> u32 {
+    64 - values.iter().fold(0, |acc, &v| acc | v).leading_zeros()
+}
+
+/// Parameters of a block encoded with [`encode_for`](fn.encode_for.html) or
+/// [`encode_pfor`](fn.encode_pfor.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Frame {
+    /// Value subtracted from every value before packing it.
+    pub reference: u64,
+    /// Number of bits of the packed values.
+    pub width: u32,
+    /// Number of exceptions.
+    pub exceptions: usize,
+}
+
+/// Packs a block into `packed` with frame of reference encoding: the
+/// minimum value is subtracted from all values, which are packed with the
+/// width of the largest difference.
+///
+/// # Panics
+///
+/// If `values.len()` is not 128 or 256, or `packed` is shorter than the
+/// packed block.
+pub fn encode_for(values: &[u64], packed: &mut [u64]) -> Frame {
+    lanes(values.len());
+    let reference = values.iter().cloned().min().unwrap_or(0);
+    let width = 64 - values.iter().fold(0, |acc, &v| acc | (v - reference)).leading_zeros();
+    pack_offset(values, reference, width, packed);
+    Frame { reference, width, exceptions: 0 }
+}
+
+/// Packs a block into `packed` with patched frame of reference encoding.
+///
+/// The differences between the values and the minimum value are packed at
+/// the width that minimizes the size of the block, and the positions and
+/// upper bits of the differences that do not fit are written to the
+/// beginning of `positions` and `highs`.
+///
+/// # Panics
+///
+/// If `values.len()` is not 128 or 256, or `packed`, `positions` or `highs`
+/// are shorter than the encoded block (at most `values.len()` words or
+/// exceptions).
+pub fn encode_pfor(values: &[u64], packed: &mut [u64], positions: &mut [u8], highs: &mut [u64])
+                   -> Frame {
+    let len = values.len();
+    lanes(len);
+    let reference = values.iter().cloned().min().unwrap_or(0);
+    // Number of differences of each width:
+    let mut histogram = [0usize; 65];
+    for &v in values {
+        histogram[64 - (v - reference).leading_zeros() as usize] += 1;
+    }
+    // Each exception takes a byte for its position and a word for its upper
+    // bits:
+    let mut best = (usize::MAX, 0);
+    let mut exceptions = 0;
+    for width in (0..65).rev() {
+        let size = len * width + exceptions * (8 + 64);
+        if size <= best.0 {
+            best = (size, width);
+        }
+        exceptions += histogram[width];
+    }
+    let width = best.1 as u32;
+    pack_offset(values, reference, width, packed);
+    let mut exceptions = 0;
+    for (i, &v) in values.iter().enumerate() {
+        let v = v - reference;
+        if width < 64 && v >> width != 0 {
+            positions[exceptions] = i as u8;
+            highs[exceptions] = v >> width;
+            exceptions += 1;
+        }
+    }
+    Frame { reference, width, exceptions }
+}
+
+/// Packs the differences between `values` and `reference`.
+#[inline]
+fn pack_offset(values: &[u64], reference: u64, width: u32, packed: &mut [u64]) {
+    let mut offsets = [0u64; 256];
+    let offsets = &mut offsets[..values.len()];
+    for (o, &v) in offsets.iter_mut().zip(values) {
+        *o = v - reference;
+    }
+    pack(offsets, width, packed)
+}
+
+/// Decodes a block encoded with [`encode_for`](fn.encode_for.html) or
+/// [`encode_pfor`](fn.encode_pfor.html) into `values`.
+///
+/// # Panics
+///
+/// If `values.len()` is not 128 or 256, or `packed`, `positions` or `highs`
+/// are shorter than the encoded block.
+pub fn decode(frame: &Frame, packed: &[u64], positions: &[u8], highs: &[u64],
+              values: &mut [u64]) {
+    unpack(packed, frame.width, values);
+    let exceptions = positions[..frame.exceptions].iter().zip(&highs[..frame.exceptions]);
+    for (&i, &high) in exceptions {
+        values[i as usize] |= high << frame.width;
+    }
+    for v in values.iter_mut() {
+        *v = v.wrapping_add(frame.reference);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use test_util::random;
+
+    /// Fills `values` with pseudo-random values of up to `width` bits.
+    fn values(seed: u64, width: u32, values: &mut [u64]) {
+        for (v, r) in values.iter_mut().zip(random(seed)) {
+            *v = r as u64 & mask(width);
+        }
+    }
+
+    /// Value `i` of the block packed in `packed` with the lane layout.
+    fn naive_get(packed: &[u64], len: usize, width: u32, i: usize) -> u64 {
+        let lanes = len / 64;
+        let (lane, j) = (i % lanes, i / lanes);
+        (0..width as usize).fold(0, |acc, b| {
+            let bit = j * width as usize + b;
+            let word = packed[bit / 64 * lanes + lane];
+            acc | ((word >> (bit % 64)) & 1) << b
+        })
+    }
+
+    #[test]
+    fn all_widths() {
+        for &len in &[128, 256] {
+            for width in 0..65 {
+                let mut v = [0u64; 256];
+                let v = &mut v[..len];
+                values(width as u64 + len as u64, width, v);
+                let mut packed = [!0u64; 257];
+                pack(v, width, &mut packed);
+                assert_eq!(packed[packed_len(len, width)], !0);
+                for (i, &x) in v.iter().enumerate() {
+                    assert_eq!(naive_get(&packed, len, width, i), x);
+                }
+                let mut unpacked = [!0u64; 256];
+                unpack(&packed, width, &mut unpacked[..len]);
+                assert_eq!(unpacked[..len], v[..]);
+            }
+        }
+        let mut packed = [0u64; 4];
+        pack_fixed::<1>(&[!0u64; 256], &mut packed);
+        assert_eq!(packed, [!0u64; 4]);
+    }
+
+    #[test]
+    fn prop_frame() {
+        fn inner(seed: u64, width: u8, reference: u64, outliers: u8) -> bool {
+            let width = width as u32 % 65;
+            let mut v = [0u64; 256];
+            values(seed, width, &mut v);
+            for (i, x) in v.iter_mut().enumerate() {
+                *x = x.wrapping_add(reference);
+                if i % 256 < (outliers % 8) as usize {
+                    *x = x.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                }
+            }
+            [128, 256].iter().all(|&len| {
+                let v = &v[..len];
+                let mut packed = [0u64; 256];
+                let mut decoded = [0u64; 256];
+                let frame = encode_for(v, &mut packed);
+                decode(&frame, &packed, &[], &[], &mut decoded[..len]);
+                let for_ok = decoded[..len] == v[..] && frame.exceptions == 0;
+
+                let (mut positions, mut highs) = ([0u8; 256], [0u64; 256]);
+                let pfor = encode_pfor(v, &mut packed, &mut positions, &mut highs);
+                decode(&pfor, &packed, &positions, &highs, &mut decoded[..len]);
+                let size = |f: &Frame| len * f.width as usize + 72 * f.exceptions;
+                for_ok && decoded[..len] == v[..] && size(&pfor) <= size(&frame) &&
+                pfor.reference == frame.reference
+            })
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u8, u64, u8) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn block_size() {
+        pack(&[0; 64], 3, &mut [0; 3]);
+    }
+}
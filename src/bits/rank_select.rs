@@ -0,0 +1,272 @@
+use alloc::vec::Vec;
+
+use word;
+use super::BitSlice;
+
+/// Number of bits of a block of the index.
+const BLOCK: usize = 512;
+
+/// One in `SAMPLE` set/cleared bits has its block stored to speed up select.
+const SAMPLE: usize = 512;
+
+/// Rank/select index over a sequence of bits stored in `u64` words.
+///
+/// This is the rank9 index of Vigna, "Broadword Implementation of
+/// Rank/Select Queries", 2008. For each block of 512 bits the index stores
+/// the number of set bits before the block and the number of set bits before
+/// each word of the block within the block, using 25% of the space of the
+/// bits. `rank1` is O(1). `select1` and `select0` use a sample of the blocks
+/// of every 512-th set/cleared bit, a binary search over the blocks between
+/// two samples, and [`select_in_word`](../word/fn.select_in_word.html).
+///
+/// Requires the `alloc` cargo feature.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::{BitSlice, RankSelect};
+///
+/// let words = [0b1011u64, 0, 1 << 63];
+/// let index = RankSelect::new(BitSlice::new(&words));
+/// assert_eq!(index.count_ones(), 4);
+/// assert_eq!(index.rank1(2), 2);
+/// assert_eq!(index.rank1(192), 4);
+/// assert_eq!(index.rank0(4), 1);
+/// assert_eq!(index.select1(2), Some(3));
+/// assert_eq!(index.select1(3), Some(191));
+/// assert_eq!(index.select1(4), None);
+/// assert_eq!(index.select0(0), Some(2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RankSelect<'a> {
+    bits: BitSlice<'a, u64>,
+    /// Two words per block, plus two for the end of the sequence: the number
+    /// of set bits before the block, and, in 9-bit fields, the number of set
+    /// bits of the words `0..j` of the block for `j = 1..8`.
+    counts: Vec<u64>,
+    /// Block of the `i * SAMPLE`-th set bit.
+    ones_samples: Vec<usize>,
+    /// Block of the `i * SAMPLE`-th cleared bit.
+    zeros_samples: Vec<usize>,
+}
+
+impl<'a> RankSelect<'a> {
+    /// Builds the index of `bits`.
+    pub fn new(bits: BitSlice<'a, u64>) -> Self {
+        let words = bits.word_len();
+        let blocks = (bits.len() + BLOCK - 1) / BLOCK;
+        let mut counts = Vec::with_capacity(2 * blocks + 2);
+        let (mut ones_samples, mut zeros_samples) = (Vec::new(), Vec::new());
+        let mut ones = 0;
+        for block in 0..blocks {
+            counts.push(ones as u64);
+            let mut sub_counts = 0;
+            let mut block_ones = 0;
+            for j in 0..8 {
+                if j > 0 {
+                    sub_counts |= (block_ones as u64) << (9 * (j - 1));
+                }
+                let i = block * 8 + j;
+                if i < words {
+                    block_ones += bits.load_word(i).count_ones() as usize;
+                }
+            }
+            counts.push(sub_counts);
+
+            // Blocks of the sampled bits in this block:
+            let block_bits = BLOCK.min(bits.len() - block * BLOCK);
+            let zeros = block * BLOCK - ones;
+            let block_zeros = block_bits - block_ones;
+            while ones_samples.len() * SAMPLE < ones + block_ones {
+                ones_samples.push(block);
+            }
+            while zeros_samples.len() * SAMPLE < zeros + block_zeros {
+                zeros_samples.push(block);
+            }
+            ones += block_ones;
+        }
+        counts.push(ones as u64);
+        counts.push(0);
+        RankSelect { bits, counts, ones_samples, zeros_samples }
+    }
+
+    /// The indexed bits.
+    #[inline]
+    pub fn bits(&self) -> BitSlice<'a, u64> {
+        self.bits
+    }
+
+    /// Number of indexed bits.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Is the sequence empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Number of blocks.
+    #[inline]
+    fn blocks(&self) -> usize {
+        self.counts.len() / 2 - 1
+    }
+
+    /// Number of set bits.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.counts[self.counts.len() - 2] as usize
+    }
+
+    /// Number of cleared bits.
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len() - self.count_ones()
+    }
+
+    /// Number of set bits before the block `block`.
+    #[inline]
+    fn block_ones(&self, block: usize) -> usize {
+        self.counts[2 * block] as usize
+    }
+
+    /// Number of set bits of the words `0..j` of the block `block`.
+    #[inline]
+    fn sub_ones(&self, block: usize, j: usize) -> usize {
+        if j == 0 {
+            0
+        } else {
+            (self.counts[2 * block + 1] >> (9 * (j - 1))) as usize & 0x1FF
+        }
+    }
+
+    /// Number of set bits at positions `< i`.
+    ///
+    /// # Panics
+    ///
+    /// If `i > len()`.
+    #[inline]
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len(), "position {} is out of bounds ({} bits)", i, self.len());
+        let (block, word, bit) = (i / BLOCK, i / 64, i % 64);
+        let mut rank = self.block_ones(block) + self.sub_ones(block, word % 8);
+        if bit != 0 {
+            rank += word::clear_bits_geq(self.bits.load_word(word), bit as u32).count_ones()
+                as usize;
+        }
+        rank
+    }
+
+    /// Number of cleared bits at positions `< i`.
+    ///
+    /// # Panics
+    ///
+    /// If `i > len()`.
+    #[inline]
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    /// Position of the `k`-th set bit (starting at zero), if any.
+    #[inline]
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        if k >= self.count_ones() {
+            return None;
+        }
+        let block = self.find_block(k, &self.ones_samples, |b| self.block_ones(b));
+        let k = k - self.block_ones(block);
+        let j = (1..8).take_while(|&j| self.sub_ones(block, j) <= k).last().unwrap_or(0);
+        let word = block * 8 + j;
+        let bit = word::select_in_word(self.bits.load_word(word), (k - self.sub_ones(block, j))
+                                       as u32);
+        Some(word * 64 + bit as usize)
+    }
+
+    /// Position of the `k`-th cleared bit (starting at zero), if any.
+    #[inline]
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        if k >= self.count_zeros() {
+            return None;
+        }
+        let block_zeros = |b: usize| b * BLOCK - self.block_ones(b);
+        let block = self.find_block(k, &self.zeros_samples, block_zeros);
+        let k = k - block_zeros(block);
+        let sub_zeros = |j: usize| j * 64 - self.sub_ones(block, j);
+        let j = (1..8).take_while(|&j| sub_zeros(j) <= k).last().unwrap_or(0);
+        let word = block * 8 + j;
+        let bit = word::select_in_word(!self.bits.load_word(word), (k - sub_zeros(j)) as u32);
+        Some(word * 64 + bit as usize)
+    }
+
+    /// Last block whose number of bits before it, `before(block)`, is `<= k`.
+    #[inline]
+    fn find_block<F: Fn(usize) -> usize>(&self, k: usize, samples: &[usize], before: F)
+                                         -> usize {
+        let sample = k / SAMPLE;
+        let mut low = samples[sample];
+        let mut high = samples.get(sample + 1).map_or(self.blocks() - 1, |&b| b);
+        while low < high {
+            let middle = (low + high + 1) / 2;
+            if before(middle) <= k {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+        low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn prop_rank_select() {
+        fn inner(words: Vec<u64>, density: u8, start: usize) -> bool {
+            // Repeat the words to span several blocks, with a varying density:
+            let words: Vec<u64> = words.iter().cycle().take(words.len() * 9).enumerate()
+                .map(|(i, &w)| match (density as usize + i / 16) % 4 {
+                    0 => w,
+                    1 => !w,
+                    2 => w & (w >> 7),
+                    _ => 0,
+                })
+                .collect();
+            let all = BitSlice::new(&words);
+            let bits = all.slice(start % (all.len() + 1)..);
+            let index = RankSelect::new(bits);
+            let ones: Vec<usize> = bits.iter_ones().collect();
+            let zeros: Vec<usize> = (0..bits.len()).filter(|&i| !bits.test_bit(i)).collect();
+            let mut rank = 0;
+            for i in 0..bits.len() + 1 {
+                if index.rank1(i) != rank || index.rank0(i) != i - rank {
+                    return false;
+                }
+                if i < bits.len() && bits.test_bit(i) {
+                    rank += 1;
+                }
+            }
+            index.count_ones() == ones.len() &&
+            (0..ones.len() + 1).all(|k| index.select1(k) == ones.get(k).cloned()) &&
+            (0..zeros.len() + 1).all(|k| index.select0(k) == zeros.get(k).cloned())
+        }
+        QuickCheck::new().tests(50).quickcheck(inner as fn(Vec<u64>, u8, usize) -> bool);
+    }
+
+    #[test]
+    fn dense() {
+        let words = [!0u64; 100];
+        let index = RankSelect::new(BitSlice::new(&words));
+        for k in (0..6400).step_by(7) {
+            assert_eq!(index.rank1(k), k);
+            assert_eq!(index.select1(k), Some(k));
+        }
+        assert_eq!(index.select0(0), None);
+        let index = RankSelect::new(BitSlice::new(&[]));
+        assert_eq!((index.rank1(0), index.select1(0), index.select0(0)), (0, None, None));
+    }
+}
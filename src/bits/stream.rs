@@ -0,0 +1,455 @@
+use std::fmt;
+
+use word::{self, Word};
+
+/// Order in which the bits of a value are stored in a byte stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// The most significant bit of a value is stored first, starting at the
+    /// most significant bit of each byte (e.g. JPEG, H.264).
+    MsbFirst,
+    /// The least significant bit of a value is stored first, starting at the
+    /// least significant bit of each byte (e.g. DEFLATE, LZW in GIF).
+    LsbFirst,
+}
+
+/// Error returned when reading, peeking, skipping, or writing past the end of
+/// a bit stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Truncated {
+    /// Bit position of the access.
+    pub position: usize,
+    /// Number of bits accessed.
+    pub bits: usize,
+    /// Number of bits of the stream after `position`.
+    pub available: usize,
+}
+
+impl fmt::Display for Truncated {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bit stream truncated: accessing {} bits at bit {} but only {} are available",
+               self.bits, self.position, self.available)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::libstd::error::Error for Truncated {}
+
+/// Checks that `bits` bits are available at `position` of a stream of
+/// `len` bits.
+#[inline]
+fn check(position: usize, len: usize, bits: usize) -> Result<(), Truncated> {
+    let available = len - position;
+    if bits > available {
+        Err(Truncated { position, bits, available })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that a value of type `T` has at least `bits` bits.
+#[inline]
+fn check_width<T: Word>(bits: u32) {
+    assert!(bits <= T::bit_size().to_u32(), "cannot access {} bits with a {}-bit word",
+            bits, T::bit_size().to_u32());
+}
+
+/// Bits `start..start + length` of `x`, with `start + length <=
+/// T::bit_size()`.
+#[inline]
+fn bits_of<T: Word>(x: T, start: u32, length: u32) -> T {
+    if length == T::bit_size().to_u32() {
+        x
+    } else {
+        word::extract_bits(x, start, length)
+    }
+}
+
+/// Mask of the `length` bits of a byte starting at bit `start`, with
+/// `0 < length` and `start + length <= 8`.
+#[inline]
+fn byte_mask(start: u32, length: u32) -> u8 {
+    (!0u8 >> (8 - length)) << start
+}
+
+/// Position in a byte of the `length` bits that start `offset` bits into the
+/// byte in `order`.
+#[inline]
+fn byte_start(order: BitOrder, offset: u32, length: u32) -> u32 {
+    match order {
+        BitOrder::LsbFirst => offset,
+        BitOrder::MsbFirst => 8 - offset - length,
+    }
+}
+
+/// Reads `bits` bits at bit `position` of `bytes`, which must be in bounds.
+#[inline]
+fn read<T: Word>(bytes: &[u8], order: BitOrder, position: usize, bits: u32) -> T {
+    let mut value = T::zero();
+    let mut done = 0;
+    while done < bits {
+        let p = position + done as usize;
+        let offset = (p % 8) as u32;
+        let take = (8 - offset).min(bits - done);
+        let chunk = T::from_u8(bits_of(bytes[p / 8], byte_start(order, offset, take), take));
+        let shift = match order {
+            BitOrder::LsbFirst => done,
+            BitOrder::MsbFirst => bits - done - take,
+        };
+        value = value | (chunk << T::from_u32(shift));
+        done += take;
+    }
+    value
+}
+
+/// Reads fields of arbitrary bit width from a byte buffer.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::{BitOrder, BitReader};
+///
+/// let bytes = [0b1011_1110, 0b0100_0000];
+/// let mut r = BitReader::new(&bytes, BitOrder::MsbFirst);
+/// assert_eq!(r.read::<u8>(3), Ok(0b101));
+/// assert_eq!(r.peek::<u16>(7), Ok(0b11_1100_1));
+/// assert_eq!(r.read::<u16>(7), Ok(0b11_1100_1));
+/// r.align_to_byte();
+/// assert_eq!(r.position(), 16);
+/// assert!(r.read_bit().is_err());
+///
+/// let mut r = BitReader::new(&bytes, BitOrder::LsbFirst);
+/// assert_eq!(r.read::<u8>(3), Ok(0b110));
+/// assert_eq!(r.read::<u8>(6), Ok(0b0_10111));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    order: BitOrder,
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Reader of the bits of `bytes` in `order`.
+    #[inline]
+    pub fn new(bytes: &'a [u8], order: BitOrder) -> Self {
+        BitReader { bytes, order, position: 0 }
+    }
+
+    /// The underlying bytes.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Bit order of the stream.
+    #[inline]
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Number of bits read or skipped.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of bits of the stream.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() * 8
+    }
+
+    /// Is the stream empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Number of bits left to read.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.len() - self.position
+    }
+
+    /// Reads the next `bits` bits without advancing the stream.
+    ///
+    /// # Panics
+    ///
+    /// If `bits > T::bit_size()`.
+    #[inline]
+    pub fn peek<T: Word>(&self, bits: u32) -> Result<T, Truncated> {
+        check_width::<T>(bits);
+        check(self.position, self.len(), bits as usize)?;
+        Ok(read(self.bytes, self.order, self.position, bits))
+    }
+
+    /// Reads the next `bits` bits.
+    ///
+    /// # Panics
+    ///
+    /// If `bits > T::bit_size()`.
+    #[inline]
+    pub fn read<T: Word>(&mut self, bits: u32) -> Result<T, Truncated> {
+        let value = self.peek(bits)?;
+        self.position += bits as usize;
+        Ok(value)
+    }
+
+    /// Reads the next bit.
+    #[inline]
+    pub fn read_bit(&mut self) -> Result<bool, Truncated> {
+        self.read::<u8>(1).map(|b| b != 0)
+    }
+
+    /// Skips the next `bits` bits.
+    #[inline]
+    pub fn skip(&mut self, bits: usize) -> Result<(), Truncated> {
+        check(self.position, self.len(), bits)?;
+        self.position += bits;
+        Ok(())
+    }
+
+    /// Skips the bits up to the next byte boundary.
+    #[inline]
+    pub fn align_to_byte(&mut self) {
+        self.position = (self.position + 7) / 8 * 8;
+    }
+}
+
+/// Writes fields of arbitrary bit width into a byte buffer.
+///
+/// Writing overwrites the bits of the buffer; skipping leaves them
+/// unmodified.
+///
+/// # Example
+///
+/// ```
+/// use bitwise::bits::{BitOrder, BitWriter};
+///
+/// let mut bytes = [0; 2];
+/// let mut w = BitWriter::new(&mut bytes, BitOrder::MsbFirst);
+/// w.write(0b101u8, 3).unwrap();
+/// w.write(0b11_1100_1u16, 7).unwrap();
+/// w.align_to_byte().unwrap();
+/// assert_eq!(w.byte_len(), 2);
+/// assert!(w.write_bit(true).is_err());
+/// assert_eq!(bytes, [0b1011_1110, 0b0100_0000]);
+///
+/// let mut w = BitWriter::new(&mut bytes, BitOrder::LsbFirst);
+/// w.write(0b110u8, 3).unwrap();
+/// w.write(-1i32, 6).unwrap();
+/// assert_eq!(w.position(), 9);
+/// assert_eq!(bytes, [0b1111_1110, 0b0100_0001]);
+/// ```
+#[derive(Debug)]
+pub struct BitWriter<'a> {
+    bytes: &'a mut [u8],
+    order: BitOrder,
+    position: usize,
+}
+
+impl<'a> BitWriter<'a> {
+    /// Writer of the bits of `bytes` in `order`.
+    #[inline]
+    pub fn new(bytes: &'a mut [u8], order: BitOrder) -> Self {
+        BitWriter { bytes, order, position: 0 }
+    }
+
+    /// The underlying bytes.
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes
+    }
+
+    /// Consumes the writer, returning the underlying bytes.
+    #[inline]
+    pub fn into_bytes(self) -> &'a mut [u8] {
+        self.bytes
+    }
+
+    /// Bit order of the stream.
+    #[inline]
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Number of bits written or skipped.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of bytes that contain the bits written or skipped.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        (self.position + 7) / 8
+    }
+
+    /// Number of bits of the stream.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() * 8
+    }
+
+    /// Is the stream empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Number of bits left to write.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.len() - self.position
+    }
+
+    /// Writes the `bits` least significant bits of `value`.
+    ///
+    /// # Panics
+    ///
+    /// If `bits > T::bit_size()`.
+    #[inline]
+    pub fn write<T: Word>(&mut self, value: T, bits: u32) -> Result<(), Truncated> {
+        check_width::<T>(bits);
+        check(self.position, self.len(), bits as usize)?;
+        let value = value.to_unsigned();
+        let mut done = 0;
+        while done < bits {
+            let p = self.position + done as usize;
+            let offset = (p % 8) as u32;
+            let take = (8 - offset).min(bits - done);
+            let shift = match self.order {
+                BitOrder::LsbFirst => done,
+                BitOrder::MsbFirst => bits - done - take,
+            };
+            let chunk = bits_of(value, shift, take).to_u8();
+            let start = byte_start(self.order, offset, take);
+            let byte = &mut self.bytes[p / 8];
+            *byte = (*byte & !byte_mask(start, take)) | (chunk << start);
+            done += take;
+        }
+        self.position += bits as usize;
+        Ok(())
+    }
+
+    /// Writes a bit.
+    #[inline]
+    pub fn write_bit(&mut self, bit: bool) -> Result<(), Truncated> {
+        self.write(bit as u8, 1)
+    }
+
+    /// Skips the next `bits` bits.
+    #[inline]
+    pub fn skip(&mut self, bits: usize) -> Result<(), Truncated> {
+        check(self.position, self.len(), bits)?;
+        self.position += bits;
+        Ok(())
+    }
+
+    /// Writes zeros up to the next byte boundary.
+    #[inline]
+    pub fn align_to_byte(&mut self) -> Result<(), Truncated> {
+        let padding = (8 - self.position % 8) % 8;
+        self.write(0u8, padding as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use test_util::random;
+
+    /// Bit `i` of the stream of `bytes` in `order`.
+    fn bit(bytes: &[u8], order: BitOrder, i: usize) -> bool {
+        let shift = match order {
+            BitOrder::LsbFirst => i % 8,
+            BitOrder::MsbFirst => 7 - i % 8,
+        };
+        bytes[i / 8] & (1 << shift) != 0
+    }
+
+    /// Bit `i` of the field `value` of width `bits` in `order`.
+    fn field_bit(value: u64, bits: u32, order: BitOrder, i: u32) -> bool {
+        let shift = match order {
+            BitOrder::LsbFirst => i,
+            BitOrder::MsbFirst => bits - 1 - i,
+        };
+        value & (1 << shift) != 0
+    }
+
+    #[test]
+    fn prop_stream() {
+        fn inner(seed: u64, lsb: bool) -> bool {
+            let order = if lsb { BitOrder::LsbFirst } else { BitOrder::MsbFirst };
+            let mut fields = [(0u64, 0u32); 32];
+            for (f, r) in fields.iter_mut().zip(random(seed)) {
+                let (v, b) = ((r >> 64) as u64, r as u32 % 65);
+                *f = (if b == 64 { v } else { v & ((1 << b) - 1) }, b);
+            }
+            let total: usize = fields.iter().map(|&(_, b)| b as usize).sum();
+            let mut bytes = [0xA5u8; 32 * 8];
+            let bytes = &mut bytes[..(total + 7) / 8];
+            {
+                let mut w = BitWriter::new(bytes, order);
+                for &(v, b) in &fields {
+                    match b {
+                        0..=8 => w.write(v as u8, b).unwrap(),
+                        9..=16 => w.write(v as i16, b).unwrap(),
+                        17..=32 => w.write(v as u32, b).unwrap(),
+                        _ => w.write(v, b).unwrap(),
+                    }
+                }
+                if w.write_bit(false).is_ok() != (total % 8 != 0) {
+                    return false;
+                }
+            }
+            // The bits of the stream are the bits of the fields in order:
+            let mut i = 0;
+            for &(v, b) in &fields {
+                for j in 0..b {
+                    if bit(bytes, order, i) != field_bit(v, b, order, j) {
+                        return false;
+                    }
+                    i += 1;
+                }
+            }
+            let mut r = BitReader::new(bytes, order);
+            fields.iter().all(|&(v, b)| {
+                r.peek::<u64>(b) == Ok(v) && r.read::<u128>(b) == Ok(v as u128)
+            }) && r.position() == total && r.read::<u8>(r.remaining() as u32 + 1).is_err()
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, bool) -> bool);
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = [0xFFu8; 3];
+        let mut r = BitReader::new(&bytes, BitOrder::MsbFirst);
+        r.skip(5).unwrap();
+        let error = Truncated { position: 5, bits: 20, available: 19 };
+        assert_eq!(r.read::<u32>(20), Err(error));
+        assert_eq!(r.peek::<u32>(20), Err(error));
+        assert_eq!(r.skip(20), Err(error));
+        assert_eq!(r.position(), 5);
+        assert_eq!(r.read::<i32>(19), Ok(0x7FFFF));
+        assert_eq!(r.read_bit(), Err(Truncated { position: 24, bits: 1, available: 0 }));
+        assert_eq!(r.read::<u8>(0), Ok(0));
+
+        let mut bytes = [0u8; 1];
+        let mut w = BitWriter::new(&mut bytes, BitOrder::LsbFirst);
+        w.skip(3).unwrap();
+        assert_eq!(w.write(!0u16, 6), Err(Truncated { position: 3, bits: 6, available: 5 }));
+        w.write(!0u16, 5).unwrap();
+        w.align_to_byte().unwrap();
+        assert_eq!(bytes, [0b1111_1000]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_wide() {
+        let bytes = [0u8; 4];
+        let _ = BitReader::new(&bytes, BitOrder::LsbFirst).read::<u16>(17);
+    }
+}
@@ -0,0 +1,348 @@
+//! Run-time selection of the CPU-specific implementations.
+//!
+//! By default, the algorithms of this crate select their implementation at
+//! compile-time, e.g., the Morton encoding uses the BMI2 `pdep`/`pext`
+//! instructions only if the `bmi2` target feature is enabled (see the
+//! [`morton`](../word/morton/index.html) module).
+//!
+//! With the `std` cargo feature, the `pdep`/`pext` intrinsics of
+//! [`Word`](../word/trait.Word.html) and the Morton encoding/decoding
+//! functions detect whether the CPU supports BMI2 the first time they are
+//! called, cache the result, and use the BMI2 instructions if it does. The
+//! run-time selection is only available on `x86_64` targets, and is not done
+//! if the `bmi2` target feature is enabled, in which case the BMI2
+//! instructions are always used.
+//!
+//! On some CPUs, like AMD CPUs before Zen 3, `pdep` and `pext` are microcoded
+//! and are much slower than the portable implementations. By default
+//! ([`Bmi2Policy::Auto`](enum.Bmi2Policy.html)) BMI2 is not used on these
+//! CPUs. This can be changed with [`set_bmi2_policy`](fn.set_bmi2_policy.html).
+//!
+//! [`clmul`](../word/fn.clmul.html) and the prefix XOR scans use the
+//! carry-less multiplication instruction PCLMULQDQ only if the `pclmulqdq`
+//! target feature is enabled at compile-time (see
+//! [`has_pclmulqdq`](fn.has_pclmulqdq.html)). Selecting it at run-time is not
+//! worth it: the scans take a handful of shift-XOR steps, which cost about as
+//! much as the detection and a call to a function that cannot be inlined.
+//!
+//! # Example
+//!
+//! ```
+//! use bitwise::cpu::{self, Bmi2Policy};
+//!
+//! cpu::set_bmi2_policy(Bmi2Policy::Never);
+//! assert!(!cpu::has_fast_bmi2());
+//! cpu::set_bmi2_policy(Bmi2Policy::Auto);
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Policy for the run-time selection of the BMI2 implementations.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Bmi2Policy {
+    /// Use BMI2 if the CPU supports it and `pdep`/`pext` are fast (default).
+    Auto,
+    /// Use BMI2 if the CPU supports it, even if `pdep`/`pext` are slow.
+    Always,
+    /// Never use BMI2 at run-time.
+    Never,
+}
+
+const UNKNOWN: u8 = 0;
+const DISABLED: u8 = 1;
+const ENABLED: u8 = 2;
+
+static POLICY: AtomicU8 = AtomicU8::new(0);
+static BMI2: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// Sets the policy for the run-time selection of the BMI2 implementations.
+///
+/// The policy applies to all threads. It is meant to be set once at start-up,
+/// calls to the algorithms that race with this function might use either
+/// policy.
+pub fn set_bmi2_policy(policy: Bmi2Policy) {
+    let p = match policy {
+        Bmi2Policy::Auto => 0,
+        Bmi2Policy::Always => 1,
+        Bmi2Policy::Never => 2,
+    };
+    POLICY.store(p, Ordering::Relaxed);
+    BMI2.store(UNKNOWN, Ordering::Relaxed);
+}
+
+/// Returns the policy for the run-time selection of the BMI2 implementations.
+pub fn bmi2_policy() -> Bmi2Policy {
+    match POLICY.load(Ordering::Relaxed) {
+        0 => Bmi2Policy::Auto,
+        1 => Bmi2Policy::Always,
+        _ => Bmi2Policy::Never,
+    }
+}
+
+/// Are the BMI2 implementations selected at run-time?
+///
+/// This detects whether the CPU supports BMI2 on the first call and caches
+/// the result. Without the `std` feature, or on targets other than `x86_64`,
+/// this always returns `false`.
+#[inline]
+pub fn has_fast_bmi2() -> bool {
+    match BMI2.load(Ordering::Relaxed) {
+        UNKNOWN => {
+            let enabled = detect_bmi2(bmi2_policy());
+            BMI2.store(if enabled { ENABLED } else { DISABLED }, Ordering::Relaxed);
+            enabled
+        }
+        state => state == ENABLED,
+    }
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+fn detect_bmi2(policy: Bmi2Policy) -> bool {
+    match policy {
+        Bmi2Policy::Never => false,
+        Bmi2Policy::Always => ::libstd::is_x86_feature_detected!("bmi2"),
+        Bmi2Policy::Auto => ::libstd::is_x86_feature_detected!("bmi2") && !has_slow_pdep(),
+    }
+}
+
+#[cfg(not(all(feature = "std", target_arch = "x86_64")))]
+fn detect_bmi2(_: Bmi2Policy) -> bool {
+    false
+}
+
+/// Are `pdep`/`pext` microcoded on this CPU?
+///
+/// This is the case on AMD (and Hygon) CPUs before Zen 3 (family `19h`).
+#[cfg(target_arch = "x86_64")]
+pub fn has_slow_pdep() -> bool {
+    #[allow(unused_unsafe)]
+    let (vendor, version) = unsafe {
+        use std::arch::x86_64::__cpuid;
+        (__cpuid(0), __cpuid(1).eax)
+    };
+    let amd = (vendor.ebx, vendor.edx, vendor.ecx) == (0x6874_7541, 0x6974_6e65, 0x444d_4163);
+    let hygon = (vendor.ebx, vendor.edx, vendor.ecx) == (0x6f67_7948, 0x6e65_476e, 0x656e_6975);
+    let mut family = (version >> 8) & 0xF;
+    if family == 0xF {
+        family += (version >> 20) & 0xFF;
+    }
+    (amd || hygon) && family < 0x19
+}
+
+/// Is the run-time selection of the BMI2 implementations enabled?
+///
+/// It is not needed if BMI2 is already used at compile-time.
+#[inline]
+fn runtime_selection() -> bool {
+    cfg!(all(feature = "std", target_arch = "x86_64", not(target_feature = "bmi2")))
+}
+
+/// Use the BMI2 implementations?
+///
+/// True if BMI2 is selected at compile-time or at run-time.
+#[inline]
+pub(crate) fn use_bmi2() -> bool {
+    cfg!(all(target_arch = "x86_64", target_feature = "bmi2")) ||
+    (runtime_selection() && has_fast_bmi2())
+}
+
+/// The BMI2 `pdep` instruction, if it is selected at compile-time or at
+/// run-time.
+#[inline]
+pub(crate) fn pdep(x: u64, mask: u64) -> Option<u64> {
+    if use_bmi2() {
+        bmi2::pdep(x, mask)
+    } else {
+        None
+    }
+}
+
+/// The BMI2 `pext` instruction, if it is selected at compile-time or at
+/// run-time.
+#[inline]
+pub(crate) fn pext(x: u64, mask: u64) -> Option<u64> {
+    if use_bmi2() {
+        bmi2::pext(x, mask)
+    } else {
+        None
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+mod bmi2 {
+    use std::arch::x86_64::{_pdep_u64, _pext_u64};
+
+    #[inline]
+    pub fn pdep(x: u64, mask: u64) -> Option<u64> {
+        #[allow(unused_unsafe)]
+        Some(unsafe { _pdep_u64(x, mask) })
+    }
+
+    #[inline]
+    pub fn pext(x: u64, mask: u64) -> Option<u64> {
+        #[allow(unused_unsafe)]
+        Some(unsafe { _pext_u64(x, mask) })
+    }
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64", not(target_feature = "bmi2")))]
+mod bmi2 {
+    use std::arch::x86_64::{_pdep_u64, _pext_u64};
+
+    #[target_feature(enable = "bmi2")]
+    unsafe fn pdep_bmi2(x: u64, mask: u64) -> u64 {
+        _pdep_u64(x, mask)
+    }
+
+    #[target_feature(enable = "bmi2")]
+    unsafe fn pext_bmi2(x: u64, mask: u64) -> u64 {
+        _pext_u64(x, mask)
+    }
+
+    // Only called after BMI2 support has been detected.
+
+    #[inline]
+    pub fn pdep(x: u64, mask: u64) -> Option<u64> {
+        Some(unsafe { pdep_bmi2(x, mask) })
+    }
+
+    #[inline]
+    pub fn pext(x: u64, mask: u64) -> Option<u64> {
+        Some(unsafe { pext_bmi2(x, mask) })
+    }
+}
+
+#[cfg(not(all(target_arch = "x86_64", any(feature = "std", target_feature = "bmi2"))))]
+mod bmi2 {
+    #[inline]
+    pub fn pdep(_: u64, _: u64) -> Option<u64> {
+        None
+    }
+
+    #[inline]
+    pub fn pext(_: u64, _: u64) -> Option<u64> {
+        None
+    }
+}
+
+/// Is the PCLMULQDQ carry-less multiplication instruction used?
+///
+/// True if the `pclmulqdq` target feature is enabled at compile-time on
+/// `x86_64` targets.
+#[inline]
+pub fn has_pclmulqdq() -> bool {
+    cfg!(all(target_arch = "x86_64", target_feature = "pclmulqdq"))
+}
+
+/// The carry-less product of `a` and `b` computed with PCLMULQDQ, if it is
+/// used.
+#[inline]
+pub(crate) fn clmul(a: u64, b: u64) -> Option<u128> {
+    pclmulqdq::clmul(a, b)
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "pclmulqdq"))]
+mod pclmulqdq {
+    use std::arch::x86_64::{__m128i, _mm_clmulepi64_si128, _mm_set_epi64x};
+    use std::mem::transmute;
+
+    #[inline]
+    pub fn clmul(a: u64, b: u64) -> Option<u128> {
+        #[allow(unused_unsafe)]
+        let product = unsafe {
+            _mm_clmulepi64_si128(_mm_set_epi64x(0, a as i64), _mm_set_epi64x(0, b as i64), 0)
+        };
+        Some(unsafe { transmute::<__m128i, u128>(product) })
+    }
+}
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "pclmulqdq")))]
+mod pclmulqdq {
+    #[inline]
+    pub fn clmul(_: u64, _: u64) -> Option<u128> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use word::Word;
+
+    fn pdep_ref(x: u64, mask: u64) -> u64 {
+        let (mut r, mut k) = (0, 0);
+        for i in 0..64 {
+            if mask & (1 << i) != 0 {
+                r |= ((x >> k) & 1) << i;
+                k += 1;
+            }
+        }
+        r
+    }
+
+    fn pext_ref(x: u64, mask: u64) -> u64 {
+        let (mut r, mut k) = (0, 0);
+        for i in 0..64 {
+            if mask & (1 << i) != 0 {
+                r |= ((x >> i) & 1) << k;
+                k += 1;
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn prop_pdep_pext() {
+        fn inner(x: u64, mask: u64) -> bool {
+            [mask, !mask, mask << 32, !0].iter().all(|&m| {
+                Word::pdep(x, m) == pdep_ref(x, m) &&
+                Word::pext(x, m) == pext_ref(x, m) &&
+                Word::pdep(x as u8, m as u8) as u64 == pdep_ref(x, m as u8 as u64) &&
+                Word::pext(x as u16, m as u16) as u64 == pext_ref(x as u16 as u64, m as u16 as u64) &&
+                Word::pdep(x as u32, m as u32) as u64 == pdep_ref(x, m as u32 as u64)
+            })
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+
+    #[test]
+    fn policy() {
+        set_bmi2_policy(Bmi2Policy::Never);
+        assert_eq!(bmi2_policy(), Bmi2Policy::Never);
+        assert!(!has_fast_bmi2());
+        if !cfg!(target_feature = "bmi2") {
+            assert_eq!(pdep(0b11, 0b1010), None);
+        }
+
+        set_bmi2_policy(Bmi2Policy::Always);
+        assert_eq!(bmi2_policy(), Bmi2Policy::Always);
+        if has_fast_bmi2() {
+            assert_eq!(pdep(0b11, 0b1010), Some(0b1010));
+            assert_eq!(pext(0b1110, 0b1010), Some(0b11));
+        }
+
+        set_bmi2_policy(Bmi2Policy::Auto);
+        assert_eq!(bmi2_policy(), Bmi2Policy::Auto);
+    }
+
+    #[test]
+    fn compile_time_bmi2() {
+        // Also without the `std` feature, and whatever the policy:
+        if cfg!(all(target_arch = "x86_64", target_feature = "bmi2")) {
+            assert!(use_bmi2());
+            assert_eq!(pdep(0b11, 0b1010), Some(0b1010));
+            assert_eq!(pext(0b1110, 0b1010), Some(0b11));
+        }
+    }
+
+    #[test]
+    fn prop_clmul() {
+        fn inner(a: u64, b: u64) -> bool {
+            let naive = (0..64).filter(|&i| b & (1 << i) != 0)
+                .fold(0u128, |acc, i| acc ^ (a as u128) << i);
+            clmul(a, b).is_some() == has_pclmulqdq() && clmul(a, b).map_or(true, |c| c == naive)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+}
@@ -5,9 +5,15 @@
 //! The algorithms:
 //!
 //! - have descriptive names to ease reading code that performs bit manipulations,
-//! - often optimize to perfect assembly code (and _always_ on nightly by using
-//!   the [`bitintr`](https://crates.io/crates/bitintr) crate),
-//! - works on ~~stable~~ unstable only :( due to specialization for now.
+//! - often optimize to perfect assembly code,
+//! - work on stable Rust.
+//!
+//! ## Cargo features
+//!
+//! - `std`: select the BMI2 implementations at run-time (see the
+//!   [`cpu`](cpu/index.html) module). Implies `alloc`.
+//! - `alloc`: heap-allocated data-structures like
+//!   [`bits::BitVec`](bits/struct.BitVec.html).
 //!
 //! ## Example
 //!
@@ -23,15 +29,14 @@
 //! }
 //! ```
 
-#![cfg_attr(RUSTC_IS_NIGHTLY, feature(specialization))]
-#![cfg_attr(RUSTC_IS_NIGHTLY, feature(cfg_target_feature))]
-#![cfg_attr(RUSTC_IS_NIGHTLY, feature(test))]
-
 #![no_std]
 extern crate core as std;
 
-#[cfg(RUSTC_IS_NIGHTLY)]
-extern crate test;
+#[cfg(feature = "std")]
+extern crate std as libstd;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 #[cfg(test)]
 extern crate quickcheck;
@@ -40,5 +45,11 @@ extern crate quickcheck;
 pub extern crate bitintr;
 
 pub mod word;
+pub mod ptr;
+pub mod cpu;
+pub mod bits;
+
+#[cfg(test)]
+mod test_util;
 
 pub use word::*;
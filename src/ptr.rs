@@ -0,0 +1,181 @@
+//! Alignment algorithms for pointers (`*const T`, `*mut T`, `NonNull<T>`).
+//!
+//! These operate on the address of the pointer using the word algorithms
+//! [`align_up`](../word/fn.align_up.html),
+//! [`align_down`](../word/fn.align_down.html), and
+//! [`is_aligned`](../word/fn.is_aligned.html). The resulting pointers are
+//! derived from the original pointer by offsetting it, so they keep its
+//! provenance.
+//!
+//! # Example
+//!
+//! ```
+//! use bitwise::ptr::*;
+//!
+//! let buf = [0u8; 64];
+//! let p = buf[1..].as_ptr();
+//! let q = p.align_up(16usize);
+//! assert!(is_aligned(q, 16usize));
+//! assert!(q >= p && q.align_down(16usize) == q);
+//! ```
+
+use std::ptr::NonNull;
+
+use word::{self, UnsignedWord};
+
+/// Pointer trait used to parametrize the alignment algorithms for all
+/// pointer types.
+pub trait Pointer: Copy {
+    /// The address of the pointer.
+    fn address(self) -> usize;
+    /// Offsets the pointer so that its address becomes `address`.
+    ///
+    /// # Panics
+    ///
+    /// If `Self` is `NonNull<T>` and `address == 0`.
+    fn with_address(self, address: usize) -> Self;
+}
+
+impl<T> Pointer for *const T {
+    #[inline]
+    fn address(self) -> usize {
+        self as usize
+    }
+    #[inline]
+    fn with_address(self, address: usize) -> Self {
+        let p = self as *const u8;
+        p.wrapping_add(address.wrapping_sub(self as usize)) as Self
+    }
+}
+
+impl<T> Pointer for *mut T {
+    #[inline]
+    fn address(self) -> usize {
+        self as usize
+    }
+    #[inline]
+    fn with_address(self, address: usize) -> Self {
+        let p = self as *mut u8;
+        p.wrapping_add(address.wrapping_sub(self as usize)) as Self
+    }
+}
+
+impl<T> Pointer for NonNull<T> {
+    #[inline]
+    fn address(self) -> usize {
+        self.as_ptr().address()
+    }
+    #[inline]
+    fn with_address(self, address: usize) -> Self {
+        NonNull::new(self.as_ptr().with_address(address)).expect("null pointer")
+    }
+}
+
+/// Align the pointer `p` up to `alignment` bytes.
+///
+/// # Panics
+///
+/// `alignment` must be a power of two.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::ptr::*;
+///
+/// let p = 0x1001 as *const u32;
+/// assert_eq!(align_up(p, 4u8) as usize, 0x1004);
+/// assert_eq!(p.align_up(0x1000usize) as usize, 0x2000);
+/// ```
+#[inline]
+pub fn align_up<P: Pointer, U: UnsignedWord>(p: P, alignment: U) -> P {
+    p.with_address(word::align_up(p.address(), alignment))
+}
+
+/// Align the pointer `p` down to `alignment` bytes.
+///
+/// # Panics
+///
+/// `alignment` must be a power of two. If `P` is `NonNull<T>`, the aligned
+/// address must not be zero.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::ptr::*;
+///
+/// let p = 0x1007 as *mut u32;
+/// assert_eq!(align_down(p, 4u8) as usize, 0x1004);
+/// assert_eq!(p.align_down(0x1000usize) as usize, 0x1000);
+/// ```
+#[inline]
+pub fn align_down<P: Pointer, U: UnsignedWord>(p: P, alignment: U) -> P {
+    p.with_address(word::align_down(p.address(), alignment))
+}
+
+/// Is the pointer `p` aligned to `alignment` bytes.
+///
+/// # Panics
+///
+/// If `alignment < 1`.
+///
+/// # Examples
+///
+/// ```
+/// use std::ptr::NonNull;
+/// use bitwise::ptr::*;
+///
+/// let p = NonNull::new(0x1008 as *mut u64).unwrap();
+/// assert!(is_aligned(p, 8u8));
+/// assert!(!is_aligned(p, 16u8));
+/// ```
+#[inline]
+pub fn is_aligned<P: Pointer, U: UnsignedWord>(p: P, alignment: U) -> bool {
+    word::is_aligned(p.address(), alignment)
+}
+
+/// Method version of [`align_up`](fn.align_up.html) and
+/// [`align_down`](fn.align_down.html).
+///
+/// There is no method version of [`is_aligned`](fn.is_aligned.html) because
+/// it would be shadowed by the inherent `is_aligned` method of pointers.
+pub trait AlignPointer {
+    fn align_up<U: UnsignedWord>(self, alignment: U) -> Self;
+    fn align_down<U: UnsignedWord>(self, alignment: U) -> Self;
+}
+
+impl<P: Pointer> AlignPointer for P {
+    #[inline]
+    fn align_up<U: UnsignedWord>(self, alignment: U) -> Self {
+        align_up(self, alignment)
+    }
+    #[inline]
+    fn align_down<U: UnsignedWord>(self, alignment: U) -> Self {
+        align_down(self, alignment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::{TestResult, QuickCheck};
+
+    #[test]
+    fn prop_align_pointer() {
+        fn inner(address: usize, shift: u8) -> TestResult {
+            let alignment = 1usize << (shift as usize % 16);
+            if address == 0 || address.checked_add(alignment).is_none() {
+                return TestResult::discard();
+            }
+            let p = address as *const u16;
+            let up = p.align_up(alignment);
+            let down = (p as *mut u16).align_down(alignment);
+            let nn = NonNull::new(p as *mut u16).unwrap().align_up(alignment);
+            TestResult::from_bool(
+                is_aligned(up, alignment) && is_aligned(down, alignment) &&
+                up as usize >= address && (up as usize) - address < alignment &&
+                down as usize <= address && address - (down as usize) < alignment &&
+                std::ptr::eq(nn.as_ptr(), up))
+        }
+        QuickCheck::new().quickcheck(inner as fn(usize, u8) -> TestResult);
+    }
+}
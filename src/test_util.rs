@@ -0,0 +1,15 @@
+//! Helpers shared by the tests.
+
+/// Pseudo-random 128-bit values generated from `seed`, for the tests that
+/// need more inputs than the arguments of a quickcheck property.
+///
+/// The generator is a multiplicative congruential generator modulo `2^128`.
+/// The low bits of its state have short periods, so the high half of the
+/// state is folded into the low half of the values.
+pub fn random(seed: u64) -> impl Iterator<Item = u128> {
+    let mut state = seed as u128 | 1;
+    ::std::iter::repeat_with(move || {
+        state = state.wrapping_mul(0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645);
+        state ^ state >> 64
+    })
+}
@@ -28,17 +28,19 @@ use word::{Word, UnsignedWord, ToWord, IsPow2};
 /// assert_eq!(4.align_down(2u8), 4);
 /// assert_eq!(4.align_down(4u8), 4);
 /// assert_eq!(4.align_down(8u8), 0);
+///
+/// assert_eq!(0x1234usize.align_down(0x100u16), 0x1200);
 /// ```
 #[inline]
 pub fn align_down<T: Word, U: UnsignedWord>(x: T, alignment: U) -> T {
     debug_assert!(alignment.is_pow2());
-    (x & (!(alignment - U::one())).to()).to()
+    let a: T = (alignment - U::one()).to();
+    x & !a
 }
 
 /// Method version of [`align_down`](fn.align_down.html).
 pub trait AlignDown {
-    #[inline]
-    fn align_down<U: UnsignedWord>(self, U) -> Self;
+    fn align_down<U: UnsignedWord>(self, alignment: U) -> Self;
 }
 
 impl<T: Word> AlignDown for T {
@@ -28,19 +28,20 @@ use word::{Word, UnsignedWord, ToWord, IsPow2};
 /// assert_eq!(4.align_up(2u8), 4);
 /// assert_eq!(4.align_up(4u8), 4);
 /// assert_eq!(4.align_up(8u8), 8);
+///
+/// assert_eq!(0x1234usize.align_up(0x100u16), 0x1300);
 /// ```
 #[inline]
 pub fn align_up<T: Word, U: UnsignedWord>(x: T, alignment: U) -> T {
     debug_assert!(alignment.is_pow2());
     let x = x.to_unsigned();
-    let a = alignment - U::one();
-    ((x + a.to()) & (!a).to()).to()
+    let a: T::Unsigned = (alignment - U::one()).to();
+    ((x + a) & !a).to()
 }
 
 /// Method version of [`align_up`](fn.align_up.html).
 pub trait AlignUp {
-    #[inline]
-    fn align_up<U: UnsignedWord>(self, U) -> Self;
+    fn align_up<U: UnsignedWord>(self, alignment: U) -> Self;
 }
 
 impl<T: Word> AlignUp for T {
@@ -0,0 +1,290 @@
+use std::mem;
+
+use word::{Word, delta_swap};
+
+/// Maximum number of stages of a Beneš network: `2 * log2(128) - 1`.
+const MAX_STAGES: usize = 13;
+
+/// Masks of the Beneš network that routes the bit `permutation[i]` to the
+/// bit `i` of a word of `permutation.len()` bits.
+///
+/// The stage `s` of the network swaps the bits at distance `n >> (s + 1)`
+/// for `s < log2(n)` and `1 << (s + 1 - log2(n))` otherwise. The masks are
+/// computed level by level with the looping algorithm: at each level, every
+/// block of `m` bits is split into two halves, each routed by a Beneš network
+/// of `m / 2` bits, such that the two bits of each input and output pair go
+/// through different halves.
+const fn benes_masks(permutation: &[u8]) -> [u128; MAX_STAGES] {
+    let n = permutation.len();
+    let k = n.trailing_zeros() as usize;
+    let mut src = [0u8; 128];
+    let mut seen = [false; 128];
+    let mut i = 0;
+    while i < n {
+        let p = permutation[i] as usize;
+        assert!(p < n && !seen[p], "the table is not a permutation of the bits");
+        seen[p] = true;
+        src[i] = p as u8;
+        i += 1;
+    }
+
+    let mut masks = [0u128; MAX_STAGES];
+    let mut level = 0;
+    while level + 1 < k {
+        let m = n >> level;
+        let h = m / 2;
+        let mut next = [0u8; 128];
+        let mut base = 0;
+        while base < n {
+            // Output of the block receiving each input of the block:
+            let mut inv = [0u8; 128];
+            let mut j = 0;
+            while j < m {
+                inv[src[base + j] as usize - base] = j as u8;
+                j += 1;
+            }
+            // Half (0 lower, 1 upper) routing each input of the block:
+            let mut half = [0u8; 128];
+            let mut routed = [false; 64];
+            let mut start = 0;
+            while start < h {
+                let mut j = start;
+                while !routed[j % h] {
+                    routed[j % h] = true;
+                    // The output `j` receives its input from the lower half,
+                    // the other input of the pair goes through the upper half,
+                    // and the other output of the pair it reaches must
+                    // receive its input from the lower half:
+                    let s = src[base + j] as usize - base;
+                    half[s] = 0;
+                    half[s ^ h] = 1;
+                    j = inv[s ^ h] as usize ^ h;
+                }
+                start += 1;
+            }
+            let mut i = 0;
+            while i < h {
+                if half[i] == 1 {
+                    masks[level] |= 1 << (base + i);
+                }
+                if half[src[base + h + i] as usize - base] == 0 {
+                    masks[2 * k - 2 - level] |= 1 << (base + i);
+                }
+                i += 1;
+            }
+            let mut j = 0;
+            while j < m {
+                let s = src[base + j] as usize - base;
+                let offset = base + h * half[s] as usize;
+                next[offset + j % h] = (offset + s % h) as u8;
+                j += 1;
+            }
+            base += m;
+        }
+        src = next;
+        level += 1;
+    }
+    // The middle stage swaps adjacent bits:
+    let mut i = 0;
+    while i < n {
+        if src[i] as usize != i {
+            masks[k - 1] |= 1 << i;
+        }
+        i += 2;
+    }
+    masks
+}
+
+/// Arbitrary permutation of the bits of a word.
+///
+/// The permutation is a table of `bit_size()` indices: the bit `i` of the
+/// permuted word is the bit `table[i]` of the word. The masks of the Beneš
+/// network implementing it are computed once, and then every permutation
+/// costs `2 * log2(bit_size()) - 1`
+/// [`delta_swap`](fn.delta_swap.html)s, that is, 5 for `u8` and 11 for `u64`.
+///
+/// The `from_array` constructors of the concrete word types are `const fn`s,
+/// so that the masks of a fixed permutation can be computed at compile time.
+///
+/// # Keywords:
+///
+/// Bit permutation, Beneš network, butterfly network, bit shuffle.
+///
+/// # Panics
+///
+/// If the table is not a permutation of `0..bit_size()`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// // Swap the nibbles and reverse the bits of the upper nibble:
+/// let p = BitPermutation::<u8>::new(&[4, 5, 6, 7, 3, 2, 1, 0]);
+/// assert_eq!(p.apply(0b1101_0011), 0b1100_1101);
+/// assert_eq!(p.inverse().apply(0b1100_1101), 0b1101_0011);
+///
+/// // Compile-time bit reversal:
+/// const REVERSE: BitPermutation<u16> = BitPermutation::<u16>::from_array(
+///     [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]
+/// );
+/// assert_eq!(REVERSE.apply(0x1234), 0x1234u16.reverse_bits());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BitPermutation<T: Word> {
+    masks: [T; MAX_STAGES],
+}
+
+impl<T: Word> BitPermutation<T> {
+    /// Computes the masks of the permutation `table`.
+    ///
+    /// # Panics
+    ///
+    /// If `table` is not a permutation of `0..T::bit_size()`.
+    pub fn new(table: &[u8]) -> Self {
+        assert_eq!(table.len(), T::bit_size().to_usize(),
+                   "the table length is not the number of bits of the word");
+        let m = benes_masks(table);
+        let mut masks = [T::zero(); MAX_STAGES];
+        for (mask, &m) in masks.iter_mut().zip(m.iter()) {
+            *mask = T::from_u128(m);
+        }
+        BitPermutation { masks }
+    }
+
+    /// Number of stages of the network: `2 * log2(T::bit_size()) - 1`.
+    #[inline]
+    fn stages() -> usize {
+        2 * T::bit_size().trailing_zeros().to_usize() - 1
+    }
+
+    /// Masks of the `delta_swap` stages of the network.
+    ///
+    /// The stage `s` swaps the bits at distance `bit_size >> (s + 1)` for
+    /// `s < log2(bit_size)`, and `1 << (s + 1 - log2(bit_size))` otherwise.
+    #[inline]
+    pub fn masks(&self) -> &[T] {
+        &self.masks[..Self::stages()]
+    }
+
+    /// Permutes the bits of `x`.
+    #[inline]
+    pub fn apply(&self, x: T) -> T {
+        let bits = T::bit_size().to_u32();
+        let k = bits.trailing_zeros();
+        let mut x = x;
+        for (s, &mask) in self.masks().iter().enumerate() {
+            let s = s as u32;
+            let delta = if s < k { bits >> (s + 1) } else { 1 << (s + 1 - k) };
+            x = delta_swap(x, mask, delta);
+        }
+        x
+    }
+
+    /// Inverse permutation.
+    ///
+    /// The network of the inverse permutation is the network of the
+    /// permutation run backwards.
+    #[inline]
+    pub fn inverse(&self) -> Self {
+        let mut p = *self;
+        let stages = Self::stages();
+        p.masks[..stages].reverse();
+        p
+    }
+}
+
+macro_rules! const_constructors {
+    ($($T:ty),*) => {$(
+        impl BitPermutation<$T> {
+            /// Computes the masks of the permutation `table` at compile time.
+            ///
+            /// # Panics
+            ///
+            /// If `table` is not a permutation of the bits of the word.
+            pub const fn from_array(table: [u8; 8 * mem::size_of::<$T>()]) -> Self {
+                let m = benes_masks(&table);
+                let mut masks = [0; MAX_STAGES];
+                let mut i = 0;
+                while i < MAX_STAGES {
+                    masks[i] = m[i] as $T;
+                    i += 1;
+                }
+                BitPermutation { masks }
+            }
+        }
+    )*}
+}
+
+const_constructors!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use test_util::random;
+
+    /// Random permutation of `0..table.len()` from `seed`.
+    fn shuffle(seed: u64, table: &mut [u8]) {
+        for (i, t) in table.iter_mut().enumerate() {
+            *t = i as u8;
+        }
+        for (i, r) in (1..table.len()).rev().zip(random(seed)) {
+            table.swap(i, r as usize % (i + 1));
+        }
+    }
+
+    fn naive<T: Word>(x: T, table: &[u8]) -> T {
+        let mut r = T::zero();
+        for (i, &t) in table.iter().enumerate() {
+            r = r | (((x >> T::from_u8(t)) & T::one()) << T::from_u32(i as u32));
+        }
+        r
+    }
+
+    fn check<T: Word>(seed: u64, x: T) -> bool {
+        let mut table = [0u8; 128];
+        let table = &mut table[..T::bit_size().to_usize()];
+        shuffle(seed, table);
+        let p = BitPermutation::<T>::new(table);
+        let y = p.apply(x);
+        y == naive(x, table) && p.inverse().apply(y) == x
+    }
+
+    #[test]
+    fn prop_bit_permutation() {
+        fn inner(seed: u64, x: u64, y: u64) -> bool {
+            let z = (x as u128) << 64 | y as u128;
+            check(seed, x as u8) && check(seed, x as u16) && check(seed, x as u32) &&
+            check(seed, x) && check(seed, z) && check(seed, x as usize) &&
+            check(seed, x as i8) && check(seed, x as i32) && check(seed, z as i128)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u64) -> bool);
+    }
+
+    #[test]
+    fn const_tables() {
+        const ROTATE: BitPermutation<u32> = BitPermutation::<u32>::from_array([
+            29, 30, 31, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12,
+            13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28,
+        ]);
+        let mut identity = [0u8; 64];
+        for (i, t) in identity.iter_mut().enumerate() {
+            *t = i as u8;
+        }
+        let id = BitPermutation::<u64>::new(&identity);
+        assert!(id.masks().iter().all(|&m| m == 0));
+        assert_eq!(id, BitPermutation::<u64>::from_array(identity));
+        for &x in &[0u32, 1, 0xDEAD_BEEF, u32::MAX, 0x8000_0001] {
+            assert_eq!(ROTATE.apply(x), x.rotate_left(3));
+            assert_eq!(ROTATE.inverse().apply(x), x.rotate_right(3));
+        }
+        assert_eq!(ROTATE.masks().len(), 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_a_permutation() {
+        BitPermutation::<u8>::new(&[0, 1, 2, 3, 4, 5, 6, 6]);
+    }
+}
@@ -21,6 +21,7 @@ use word::Word;
 /// assert_eq!(2u32.pow(30).ceil_pow2(), 2u32.pow(30));
 /// assert_eq!((2u32.pow(30) + 1).ceil_pow2(), 2u32.pow(31));
 /// assert_eq!(2u32.pow(31).ceil_pow2(), 2u32.pow(31));
+/// assert_eq!((2u128.pow(100) + 1).ceil_pow2(), 2u128.pow(101));
 /// // panics:
 /// // assert_eq!((2u32.pow(31) + 1).ceil_pow2(), 2u32.pow(32));
 /// ```
@@ -37,6 +38,9 @@ pub fn ceil_pow2<T: Word>(x: T) -> T {
             x = x | (x >> T::from_u8(16));
             if s > T::from_u8(4) {
                 x = x | (x >> T::from_u8(32));
+                if s > T::from_u8(8) {
+                    x = x | (x >> T::from_u8(64));
+                }
             }
         }
     }
@@ -45,7 +49,6 @@ pub fn ceil_pow2<T: Word>(x: T) -> T {
 
 /// Method version of [`ceil_pow2`](fn.ceil_pow2.html).
 pub trait CeilPow2 {
-    #[inline]
     fn ceil_pow2(self) -> Self;
 }
 
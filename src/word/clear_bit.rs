@@ -24,7 +24,6 @@ pub fn clear_bit<T: Word, U: UnsignedWord>(x: T, bit: U) -> T {
 
 /// Method version of [`clear_bit`](fn.clear_bit.html).
 pub trait ClearBit {
-    #[inline]
     fn clear_bit<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -25,7 +25,6 @@ pub fn clear_bits_geq<T: Word, U: UnsignedWord>(x: T, bit: U) -> T {
 
 /// Method version of [`clear_bits_geq`](fn.clear_bits_geq.html).
 pub trait ClearBitsGeq {
-    #[inline]
     fn clear_bits_geq<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -15,16 +15,16 @@ use word::{Word, ToWord, UnsignedWord};
 /// let s = 0b1100_0000u8;
 /// assert_eq!(n.clear_bits_leq(5u8), s);
 /// assert_eq!(clear_bits_leq(n, 5u8), s);
+/// assert_eq!(clear_bits_leq(n, 7u8), 0);
 /// ```
 #[inline]
 pub fn clear_bits_leq<T: Word, U: UnsignedWord>(x: T, bit: U) -> T {
     debug_assert!(T::bit_size() > bit.to());
-    x & !((T::one() << (T::one() + bit.to())) - T::one())
+    x & ((!T::zero() << bit.to()) << T::one())
 }
 
 /// Method version of [`clear_bits_leq`](fn.clear_bits_leq.html).
 pub trait ClearBitsLeq {
-    #[inline]
     fn clear_bits_leq<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -34,3 +34,17 @@ impl<T: Word> ClearBitsLeq for T {
         clear_bits_leq(self, n)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use word::*;
+
+    #[test]
+    fn highest_bit() {
+        // The mask of the bits <= `bit_size - 1` is every bit:
+        assert_eq!(clear_bits_leq(0b1011_0010u8, 7u32), 0);
+        let n = -0x0123_4567_89AB_CDEFi64;
+        assert_eq!(clear_bits_leq(n, 63u32), 0);
+        assert_eq!(n.clear_bits_leq(63u32), 0);
+    }
+}
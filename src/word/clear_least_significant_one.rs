@@ -25,7 +25,6 @@ pub fn clear_least_significant_one<T: Word>(x: T) -> T {
 
 /// Method version of [`clear_least_significant_one`](fn.clear_least_significant_one.html).
 pub trait ClearLeastSignificantOne {
-    #[inline]
     fn clear_least_significant_one(self) -> Self;
 }
 
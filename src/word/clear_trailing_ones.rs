@@ -25,7 +25,6 @@ pub fn clear_trailing_ones<T: Word>(x: T) -> T {
 
 /// Method version of [`clear_trailing_ones`](fn.clear_trailing_ones.html).
 pub trait ClearTrailingOnes {
-    #[inline]
     fn clear_trailing_ones(self) -> Self;
 }
 
@@ -0,0 +1,133 @@
+use word::UnsignedWord;
+use cpu;
+
+/// Carry-less product of two 64-bit words, with PCLMULQDQ if the
+/// `pclmulqdq` target feature is enabled.
+#[inline]
+fn clmul64(a: u64, b: u64) -> u128 {
+    cpu::clmul(a, b).unwrap_or_else(|| clmul64_portable(a, b))
+}
+
+/// Portable carry-less product of two 64-bit words.
+///
+/// `a` is multiplied by the 16 polynomials of degree less than 4 up front,
+/// and `b` is processed 4 bits at a time, from the most significant ones.
+/// The table look-ups depend on `b`: this is not constant-time.
+#[inline]
+fn clmul64_portable(a: u64, b: u64) -> u128 {
+    let a = a as u128;
+    let mut table = [0u128; 16];
+    for i in 1..16usize {
+        table[i] = table[i & (i - 1)] ^ (a << i.trailing_zeros());
+    }
+    (0..16).rev().fold(0, |acc, nibble| {
+        (acc << 4) ^ table[(b >> (4 * nibble)) as usize & 0xF]
+    })
+}
+
+/// Carry-less multiplication of `a` and `b`.
+///
+/// Multiplies `a` and `b` as polynomials over GF(2): the bit `i` of a word
+/// is the coefficient of `x^i`, and the partial products are added with XOR
+/// instead of addition. Returns the low and high halves of the product,
+/// that is, `(product mod x^n, product / x^n)` for `n = T::bit_size()`.
+///
+/// This uses the PCLMULQDQ instruction if the `pclmulqdq` target feature is
+/// enabled (see the [`cpu`](../cpu/index.html) module), with three
+/// multiplications for 128-bit words (Karatsuba), and a portable table-based
+/// multiplication otherwise.
+///
+/// The [`galois`](galois/index.html) module builds finite field arithmetic
+/// on top of it.
+///
+/// # Keywords:
+///
+/// Carry-less multiplication, polynomial multiplication, XOR multiplication.
+///
+/// # Intrinsics:
+/// - PCLMULQDQ: pclmulqdq.
+/// - ARMv8 crypto extensions: pmull.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// // (x + 1) * (x + 1) = x^2 + 1:
+/// assert_eq!(clmul(0b11u8, 0b11u8), (0b101, 0));
+/// // (x^7 + 1) * x^2 = x^9 + x^2:
+/// assert_eq!(0b1000_0001u8.clmul(0b100), (0b0000_0100, 0b10));
+/// assert_eq!(clmul(!0u128, 1 << 127), (1 << 127, !0 >> 1));
+/// ```
+#[inline]
+pub fn clmul<T: UnsignedWord>(a: T, b: T) -> (T, T) {
+    let bits = T::bit_size().to_u32();
+    if bits <= 64 {
+        let p = clmul64(a.to_u64(), b.to_u64());
+        return (T::from_u128(p), T::from_u128(p >> bits));
+    }
+    let (a, b) = (a.to_u128(), b.to_u128());
+    let (a0, a1, b0, b1) = (a as u64, (a >> 64) as u64, b as u64, (b >> 64) as u64);
+    let low = clmul64(a0, b0);
+    let high = clmul64(a1, b1);
+    let middle = clmul64(a0 ^ a1, b0 ^ b1) ^ low ^ high;
+    (T::from_u128(low ^ (middle << 64)), T::from_u128(high ^ (middle >> 64)))
+}
+
+/// Method version of [`clmul`](fn.clmul.html).
+pub trait Clmul: Sized {
+    fn clmul(self, b: Self) -> (Self, Self);
+}
+
+impl<T: UnsignedWord> Clmul for T {
+    #[inline]
+    fn clmul(self, b: Self) -> (Self, Self) {
+        clmul(self, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    /// Bit-by-bit carry-less product of `a` and `b` as `(low, high)` words
+    /// of 128 bits.
+    fn naive(a: u128, b: u128) -> (u128, u128) {
+        (0..128).filter(|&i| b & (1 << i) != 0).fold((0, 0), |(low, high), i| {
+            let high = if i == 0 { high } else { high ^ (a >> (128 - i)) };
+            (low ^ (a << i), high)
+        })
+    }
+
+    fn check<T: UnsignedWord>(a: T, b: T) -> bool {
+        let bits = T::bit_size().to_u32();
+        let (low, high) = clmul(a, b);
+        let (l, h) = naive(a.to_u128(), b.to_u128());
+        let expected = if bits == 128 {
+            (T::from_u128(l), T::from_u128(h))
+        } else {
+            (T::from_u128(l), T::from_u128(l >> bits))
+        };
+        (low, high) == expected && clmul(b, a) == (low, high)
+    }
+
+    #[test]
+    fn prop_clmul() {
+        fn inner(a: u64, b: u64, c: u64) -> bool {
+            let (x, y) = ((a as u128) << 64 | c as u128, (c as u128) << 64 | b as u128);
+            check(a as u8, b as u8) && check(a as u16, b as u16) && check(a as u32, b as u32) &&
+            check(a, b) && check(a as usize, b as usize) && check(x, y) && check(!x, y) &&
+            check(a, !0) && check(!x, !0)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u64) -> bool);
+    }
+
+    #[test]
+    fn prop_clmul_portable() {
+        fn inner(a: u64, b: u64) -> bool {
+            clmul64_portable(a, b) == naive(a as u128, b as u128).0
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+}
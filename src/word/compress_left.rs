@@ -0,0 +1,46 @@
+use word::{Word, ToWord};
+
+/// Compresses the bits of `x` selected by `mask` into the most significant
+/// bits of the result, preserving their order, and clears the other bits.
+///
+/// This is [`parallel_bits_extract`](fn.parallel_bits_extract.html) with the
+/// result at the high end of the word instead of the low end.
+///
+/// # Keywords:
+///
+/// Compress left, gather, pext.
+///
+/// # Intrinsics:
+/// - BMI 2.0: pext.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let n = 0b1011_0110u8;
+/// assert_eq!(n.compress_left(0b0000_1111u8), 0b0110_0000);
+/// assert_eq!(compress_left(n, 0b1100_0011u8), 0b1010_0000);
+/// assert_eq!(compress_left(n, 0u8), 0);
+/// ```
+#[inline]
+pub fn compress_left<T: Word, U: Word>(x: T, mask: U) -> T {
+    let mask: T = mask.to();
+    let n = mask.count_ones();
+    if n == T::zero() {
+        return T::zero();
+    }
+    x.pext(mask) << (T::bit_size() - n)
+}
+
+/// Method version of [`compress_left`](fn.compress_left.html).
+pub trait CompressLeft {
+    fn compress_left<U: Word>(self, mask: U) -> Self;
+}
+
+impl<T: Word> CompressLeft for T {
+    #[inline]
+    fn compress_left<U: Word>(self, mask: U) -> Self {
+        compress_left(self, mask)
+    }
+}
@@ -0,0 +1,158 @@
+use word::{Word, ToWord, prefix_xor, shift_logical_right};
+
+/// Precomputed mask for repeated compress and expand operations.
+///
+/// Computes once the `log2(bit_size)` move masks of the Hacker's Delight
+/// compress algorithm for a fixed `mask`, after which every
+/// [`compress`](#method.compress) and [`expand`](#method.expand) costs
+/// `log2(bit_size)` shift-and-mask steps. This is faster than the software
+/// fallbacks of `parallel_bits_extract`/`parallel_bits_deposit` when the same
+/// mask is used many times on CPUs without BMI2, or with slow BMI2 (AMD
+/// before Zen 3).
+///
+/// See also:
+/// [Hacker's Delight: compress, or generalized extract](http://icodeguru.com/Embedded/Hacker's-Delight/043.htm).
+///
+/// # Keywords:
+///
+/// Compress, expand, gather, scatter, pext, pdep.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let m = CompressMask::new(0b0110_0011_1000_0101u16);
+/// let n = 0b1011_1110_1001_0011u16;
+/// assert_eq!(m.compress(n), 0b0000_0000_0011_0101);
+/// assert_eq!(m.compress(n), n.parallel_bits_extract(m.mask()));
+/// assert_eq!(m.expand(0b0011_0101), n & m.mask());
+/// assert_eq!(m.compress_left(n), n.compress_left(m.mask()));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CompressMask<T: Word> {
+    mask: T,
+    moves: [T; 7],
+}
+
+impl<T: Word> CompressMask<T> {
+    /// Precomputes the move masks of `mask`.
+    pub fn new<U: Word>(mask: U) -> Self {
+        let mask: T = mask.to();
+        let mut moves = [T::zero(); 7];
+        let mut m = mask;
+        // Bits with an odd number of zeros of `mask` below them, not yet moved:
+        let mut mk = !m << T::one();
+        let mut shift = 1;
+        for mv in moves.iter_mut().take(Self::steps()) {
+            let mp = prefix_xor(mk);
+            *mv = mp & m;
+            m = (m ^ *mv) | shift_logical_right(*mv, T::from_u32(shift));
+            mk = mk & !mp;
+            shift *= 2;
+        }
+        CompressMask { mask, moves }
+    }
+
+    /// Number of steps: `log2(T::bit_size())`.
+    #[inline]
+    fn steps() -> usize {
+        T::bit_size().trailing_zeros().to_usize()
+    }
+
+    /// The mask.
+    #[inline]
+    pub fn mask(&self) -> T {
+        self.mask
+    }
+
+    /// Compresses the bits of `x` selected by the mask into the least
+    /// significant bits of the result.
+    ///
+    /// Equivalent to `parallel_bits_extract(x, mask)`.
+    #[inline]
+    pub fn compress(&self, x: T) -> T {
+        let mut x = x & self.mask;
+        let mut shift = 1;
+        for &mv in &self.moves[..Self::steps()] {
+            let t = x & mv;
+            x = (x ^ t) | shift_logical_right(t, T::from_u32(shift));
+            shift *= 2;
+        }
+        x
+    }
+
+    /// Deposits the least significant bits of `x` into the bits selected by
+    /// the mask.
+    ///
+    /// Equivalent to `parallel_bits_deposit(x, mask)`.
+    #[inline]
+    pub fn expand(&self, x: T) -> T {
+        let mut x = x;
+        let steps = Self::steps();
+        for (i, &mv) in self.moves[..steps].iter().enumerate().rev() {
+            x = (x & !mv) | ((x << T::from_u32(1 << i)) & mv);
+        }
+        x & self.mask
+    }
+
+    /// Compresses the bits of `x` selected by the mask into the most
+    /// significant bits of the result.
+    ///
+    /// Equivalent to `compress_left(x, mask)`.
+    #[inline]
+    pub fn compress_left(&self, x: T) -> T {
+        let n = self.mask.count_ones();
+        if n == T::zero() {
+            return T::zero();
+        }
+        self.compress(x) << (T::bit_size() - n)
+    }
+
+    /// Deposits the most significant bits of `x` into the bits selected by
+    /// the mask.
+    ///
+    /// Equivalent to `expand_left(x, mask)`.
+    #[inline]
+    pub fn expand_left(&self, x: T) -> T {
+        let n = self.mask.count_ones();
+        if n == T::zero() {
+            return T::zero();
+        }
+        self.expand(shift_logical_right(x, T::bit_size() - n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use word::*;
+
+    fn check<T: Word>(x: T, mask: T) -> bool {
+        let m = CompressMask::new(mask);
+        let c = x.parallel_bits_extract(mask);
+        // The `bit_size - count_ones(mask)` most significant bits:
+        let n = mask.count_zeros();
+        let top = if n == T::bit_size() { T::zero() } else { !T::zero() << n };
+        m.compress(x) == c && m.expand(c) == x & mask &&
+        m.expand(x) == x.parallel_bits_deposit(mask) &&
+        m.compress_left(x) == x.compress_left(mask) &&
+        m.expand_left(x) == x.expand_left(mask) &&
+        x.expand_left(mask).compress_left(mask) == x.compress_left(top)
+    }
+
+    #[test]
+    fn prop_compress_mask() {
+        fn inner(x: u64, mask: u64, y: u64) -> bool {
+            let z = (x as u128) << 64 | y as u128;
+            let w = (mask as u128) << 64 | (x ^ y) as u128;
+            check(x, mask) && check(x as u8, mask as u8) && check(x as u16, mask as u16) &&
+            check(x as u32, mask as u32) && check(z, w) && check(x as usize, mask as usize) &&
+            check(x as i8, mask as i8) && check(x as i32, mask as i32) &&
+            check(z as i128, w as i128) && check(x, 0) && check(x, !0) &&
+            check(x as i16, !0) && check(z, 0)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u64) -> bool);
+    }
+}
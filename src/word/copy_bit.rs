@@ -30,8 +30,7 @@ pub fn copy_bit<T: Word, U: UnsignedWord>(x: T, from_bit: U, y: T, to_bit: U) ->
 
 /// Method version of [`copy_bit`](fn.copy_bit.html).
 pub trait CopyBit {
-    #[inline]
-    fn copy_bit<U: UnsignedWord>(self, f: U, Self, t: U) -> Self;
+    fn copy_bit<U: UnsignedWord>(self, f: U, y: Self, t: U) -> Self;
 }
 
 impl<T: Word> CopyBit for T {
@@ -26,7 +26,6 @@ pub fn count_leading_ones<T: Word>(x: T) -> T {
 
 /// Method version of [`count_leading_ones`](fn.count_leading_ones.html).
 pub trait CountLeadingOnes {
-    #[inline]
     fn count_leading_ones(self) -> Self;
 }
 
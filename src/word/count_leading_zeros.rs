@@ -30,7 +30,6 @@ pub fn count_leading_zeros<T: Word>(x: T) -> T {
 
 /// Method version of [`count_leading_zeros`](fn.count_leading_zeros.html).
 pub trait CountLeadingZeros {
-    #[inline]
     fn count_leading_zeros(self) -> Self;
 }
 
@@ -30,7 +30,6 @@ pub fn count_ones<T: Word>(x: T) -> T {
 
 /// Method version of [`count_ones`](fn.count_ones.html).
 pub trait CountOnes {
-    #[inline]
     fn count_ones(self) -> Self;
 }
 
@@ -23,7 +23,6 @@ pub fn count_trailing_ones<T: Word>(x: T) -> T {
 
 /// Method version of [`count_trailing_ones`](fn.count_trailing_ones.html).
 pub trait CountTrailingOnes {
-    #[inline]
     fn count_trailing_ones(self) -> Self;
 }
 
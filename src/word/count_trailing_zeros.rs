@@ -26,9 +26,7 @@ pub fn count_trailing_zeros<T: Word>(x: T) -> T {
 }
 
 /// Method version of [`count_trailing_zeros`](fn.count_trailing_zeros.html).
-
 pub trait CountTrailingZeros {
-    #[inline]
     fn count_trailing_zeros(self) -> Self;
 }
 
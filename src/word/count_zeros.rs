@@ -19,7 +19,6 @@ pub fn count_zeros<T: Word>(x: T) -> T {
 
 /// Method version of [`count_zeros`](fn.count_zeros.html).
 pub trait CountZeros {
-    #[inline]
     fn count_zeros(self) -> Self;
 }
 
@@ -0,0 +1,45 @@
+use word::{Word, UnsignedWord, shift_logical_right};
+
+/// Swaps the bits `i` and `i + delta` of `x` for each set bit `i` of `mask`.
+///
+/// The bits `i` and `i + delta` of `mask` must not both be set, and `mask`
+/// must not have set bits `i >= bit_size - delta`.
+///
+/// This is the building block of the bit permutations: every permutation
+/// of the bits of a word is a sequence of `2 * log2(bit_size) - 1` delta
+/// swaps (see [`BitPermutation`](struct.BitPermutation.html)).
+///
+/// # Keywords:
+///
+/// Delta swap, bit permute step, masked swap, butterfly.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let n = 0b1010_0110u8;
+///
+/// // Swap the nibbles:
+/// assert_eq!(n.delta_swap(0b0000_1111, 4u32), 0b0110_1010);
+/// // Swap the bits 0 and 3, and 1 and 4:
+/// assert_eq!(delta_swap(n, 0b0000_0011, 3u32), 0b1011_0100);
+/// ```
+#[inline]
+pub fn delta_swap<T: Word, U: UnsignedWord>(x: T, mask: T, delta: U) -> T {
+    let delta = T::from_u32(delta.to_u32());
+    let t = (shift_logical_right(x, delta) ^ x) & mask;
+    x ^ t ^ (t << delta)
+}
+
+/// Method version of [`delta_swap`](fn.delta_swap.html).
+pub trait DeltaSwap {
+    fn delta_swap<U: UnsignedWord>(self, mask: Self, delta: U) -> Self;
+}
+
+impl<T: Word> DeltaSwap for T {
+    #[inline]
+    fn delta_swap<U: UnsignedWord>(self, mask: Self, delta: U) -> Self {
+        delta_swap(self, mask, delta)
+    }
+}
@@ -0,0 +1,47 @@
+use word::{Word, ToWord, shift_logical_right};
+
+/// Deposits the most significant bits of `x` into the bits selected by
+/// `mask`, preserving their order, and clears the other bits.
+///
+/// This is [`parallel_bits_deposit`](fn.parallel_bits_deposit.html) taking
+/// the bits from the high end of the word instead of the low end, and the
+/// inverse of [`compress_left`](fn.compress_left.html).
+///
+/// # Keywords:
+///
+/// Expand left, scatter, pdep.
+///
+/// # Intrinsics:
+/// - BMI 2.0: pdep.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let n = 0b1011_0110u8;
+/// assert_eq!(n.expand_left(0b0000_1111u8), 0b0000_1011);
+/// assert_eq!(expand_left(n, 0b1100_0011u8), 0b1000_0011);
+/// assert_eq!(expand_left(n.compress_left(0b0101_1010u8), 0b0101_1010u8), n & 0b0101_1010);
+/// ```
+#[inline]
+pub fn expand_left<T: Word, U: Word>(x: T, mask: U) -> T {
+    let mask: T = mask.to();
+    let n = mask.count_ones();
+    if n == T::zero() {
+        return T::zero();
+    }
+    shift_logical_right(x, T::bit_size() - n).pdep(mask)
+}
+
+/// Method version of [`expand_left`](fn.expand_left.html).
+pub trait ExpandLeft {
+    fn expand_left<U: Word>(self, mask: U) -> Self;
+}
+
+impl<T: Word> ExpandLeft for T {
+    #[inline]
+    fn expand_left<U: Word>(self, mask: U) -> Self {
+        expand_left(self, mask)
+    }
+}
@@ -26,8 +26,7 @@ pub fn extract_bits<T: Word, U: Word>(x: T, start: U, length: U) -> T {
 
 /// Method version of [`extract_bits`](fn.extract_bits.html).
 pub trait ExtractBits {
-    #[inline]
-    fn extract_bits<U: Word>(self, U, U) -> Self;
+    fn extract_bits<U: Word>(self, start: U, length: U) -> Self;
 }
 
 impl<T: Word> ExtractBits for T {
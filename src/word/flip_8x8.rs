@@ -0,0 +1,194 @@
+use word::{reverse_bits, transpose_8x8};
+
+// The functions in this file treat a `u64` as an 8x8 board: the byte `r` is
+// the row `r`, counted from the bottom, and its bit `c` is the column `c`,
+// counted from the left. That is, the bit 0 is the bottom left square (a1 in
+// chess programming terms) and the bit 63 the top right square (h8).
+
+/// Flips the 8x8 board `x` vertically: the row `r` becomes the row `7 - r`.
+///
+/// See also:
+/// [Chess Programming Wiki: flipping, mirroring and rotating](https://www.chessprogramming.org/Flipping_Mirroring_and_Rotating).
+///
+/// # Keywords:
+///
+/// Bitboard, flip vertical, mirror rows.
+///
+/// # Intrinsics:
+/// - x86_64: bswap.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(flip_vertical_8x8(0x0000_0000_0000_00FF), 0xFF00_0000_0000_0000);
+/// assert_eq!(flip_vertical_8x8(0x0000_0000_0000_0102), 0x0201_0000_0000_0000);
+/// ```
+#[inline]
+pub fn flip_vertical_8x8(x: u64) -> u64 {
+    x.swap_bytes()
+}
+
+/// Flips the 8x8 board `x` horizontally: the column `c` becomes the column
+/// `7 - c`.
+///
+/// # Keywords:
+///
+/// Bitboard, flip horizontal, mirror columns.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(flip_horizontal_8x8(0x0101_0101_0101_0101), 0x8080_8080_8080_8080);
+/// assert_eq!(flip_horizontal_8x8(0x0000_0000_0000_0106), 0x0000_0000_0000_8060);
+/// ```
+#[inline]
+pub fn flip_horizontal_8x8(x: u64) -> u64 {
+    reverse_bits(x).swap_bytes()
+}
+
+/// Flips the 8x8 board `x` about its diagonal: the square `(r, c)` becomes
+/// the square `(c, r)`.
+///
+/// Equivalent to [`transpose_8x8`](fn.transpose_8x8.html).
+///
+/// # Keywords:
+///
+/// Bitboard, flip diagonal, transpose.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(flip_diagonal_8x8(0x0000_0000_0000_00FF), 0x0101_0101_0101_0101);
+/// ```
+#[inline]
+pub fn flip_diagonal_8x8(x: u64) -> u64 {
+    transpose_8x8(x)
+}
+
+/// Flips the 8x8 board `x` about its anti-diagonal: the square `(r, c)`
+/// becomes the square `(7 - c, 7 - r)`.
+///
+/// # Keywords:
+///
+/// Bitboard, flip anti-diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(flip_anti_diagonal_8x8(0x0000_0000_0000_00FF), 0x8080_8080_8080_8080);
+/// assert_eq!(flip_anti_diagonal_8x8(0x0102_0408_1020_4080), 0x0102_0408_1020_4080);
+/// ```
+#[inline]
+pub fn flip_anti_diagonal_8x8(x: u64) -> u64 {
+    reverse_bits(transpose_8x8(x))
+}
+
+/// Rotates the 8x8 board `x` by 90 degrees clockwise: the square `(r, c)`
+/// becomes the square `(7 - c, r)`.
+///
+/// # Keywords:
+///
+/// Bitboard, rotate clockwise.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// // The bottom row becomes the left column:
+/// assert_eq!(rotate_clockwise_8x8(0x0000_0000_0000_00FF), 0x0101_0101_0101_0101);
+/// // The left column becomes the top row:
+/// assert_eq!(rotate_clockwise_8x8(0x0101_0101_0101_0101), 0xFF00_0000_0000_0000);
+/// ```
+#[inline]
+pub fn rotate_clockwise_8x8(x: u64) -> u64 {
+    flip_vertical_8x8(transpose_8x8(x))
+}
+
+/// Rotates the 8x8 board `x` by 90 degrees anticlockwise: the square
+/// `(r, c)` becomes the square `(c, 7 - r)`.
+///
+/// # Keywords:
+///
+/// Bitboard, rotate anticlockwise, rotate counterclockwise.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// // The bottom row becomes the right column:
+/// assert_eq!(rotate_anticlockwise_8x8(0x0000_0000_0000_00FF), 0x8080_8080_8080_8080);
+/// let n = 0x0000_1234_5678_9ABC;
+/// assert_eq!(rotate_anticlockwise_8x8(rotate_clockwise_8x8(n)), n);
+/// ```
+#[inline]
+pub fn rotate_anticlockwise_8x8(x: u64) -> u64 {
+    transpose_8x8(flip_vertical_8x8(x))
+}
+
+/// Rotates the 8x8 board `x` by 180 degrees: the square `(r, c)` becomes the
+/// square `(7 - r, 7 - c)`.
+///
+/// Equivalent to [`reverse_bits`](fn.reverse_bits.html).
+///
+/// # Keywords:
+///
+/// Bitboard, rotate 180.
+///
+/// # Intrinsics:
+/// - ARM: rbit (ARMv8).
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(rotate_180_8x8(0x0000_0000_0000_0001), 0x8000_0000_0000_0000);
+/// let n = 0x0000_1234_5678_9ABC;
+/// assert_eq!(rotate_180_8x8(n), rotate_clockwise_8x8(rotate_clockwise_8x8(n)));
+/// ```
+#[inline]
+pub fn rotate_180_8x8(x: u64) -> u64 {
+    reverse_bits(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    /// Moves every square `(r, c)` of `x` to `f(r, c)`.
+    fn naive<F: Fn(u32, u32) -> (u32, u32)>(x: u64, f: F) -> u64 {
+        let mut y = 0;
+        for i in 0..64 {
+            if x & (1 << i) != 0 {
+                let (r, c) = f(i / 8, i % 8);
+                y |= 1 << (8 * r + c);
+            }
+        }
+        y
+    }
+
+    #[test]
+    fn prop_flip_8x8() {
+        fn inner(x: u64) -> bool {
+            flip_vertical_8x8(x) == naive(x, |r, c| (7 - r, c)) &&
+            flip_horizontal_8x8(x) == naive(x, |r, c| (r, 7 - c)) &&
+            flip_diagonal_8x8(x) == naive(x, |r, c| (c, r)) &&
+            flip_anti_diagonal_8x8(x) == naive(x, |r, c| (7 - c, 7 - r)) &&
+            rotate_clockwise_8x8(x) == naive(x, |r, c| (7 - c, r)) &&
+            rotate_anticlockwise_8x8(x) == naive(x, |r, c| (c, 7 - r)) &&
+            rotate_180_8x8(x) == naive(x, |r, c| (7 - r, 7 - c))
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64) -> bool);
+    }
+}
@@ -24,7 +24,6 @@ pub fn flip_bit<T: Word, U: UnsignedWord>(x: T, bit: U) -> T {
 
 /// Method version of [`flip_bit`](fn.flip_bit.html).
 pub trait FlipBit {
-    #[inline]
     fn flip_bit<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -25,7 +25,6 @@ pub fn flip_bits_geq<T: Word, U: UnsignedWord>(x: T, bit: U) -> T {
 
 /// Method version of [`flip_bits_geq`](fn.flip_bits_geq.html).
 pub trait FlipBitsGeq {
-    #[inline]
     fn flip_bits_geq<U: UnsignedWord>(self, n: U) -> Self;
 }
 
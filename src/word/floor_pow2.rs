@@ -18,6 +18,7 @@ use word::Word;
 /// assert_eq!(6.floor_pow2(), 4);
 /// assert_eq!(7.floor_pow2(), 4);
 /// assert_eq!(8.floor_pow2(), 8);
+/// assert_eq!((2u128.pow(100) + 1).floor_pow2(), 2u128.pow(100));
 /// ```
 #[inline]
 pub fn floor_pow2<T: Word>(x: T) -> T {
@@ -33,6 +34,9 @@ pub fn floor_pow2<T: Word>(x: T) -> T {
             x = x | (x >> T::from_u8(16));
             if s > T::from_u8(4) {
                 x = x | (x >> T::from_u8(32));
+                if s > T::from_u8(8) {
+                    x = x | (x >> T::from_u8(64));
+                }
             }
         }
     }
@@ -41,7 +45,6 @@ pub fn floor_pow2<T: Word>(x: T) -> T {
 
 /// Method version of [`floor_pow2`](fn.floor_pow2.html).
 pub trait FloorPow2 {
-    #[inline]
     fn floor_pow2(self) -> Self;
 }
 
@@ -0,0 +1,46 @@
+use word::{Word, shift_logical_right};
+
+/// Integer whose reflected binary Gray code is `x`.
+///
+/// Bit `i` of the result is the parity of the bits `i..` of `x`, which is
+/// computed with `log2(bit_size)` shift-XOR steps.
+///
+/// # Keywords:
+///
+/// Gray code, reflected binary code, prefix XOR.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(from_gray(0b000u8), 0);
+/// assert_eq!(from_gray(0b011u8), 2);
+/// assert_eq!(0b110u8.from_gray(), 4);
+/// assert_eq!(0b1000_0000u8.from_gray(), u8::MAX);
+/// assert_eq!(0x1234_5678u32.to_gray().from_gray(), 0x1234_5678);
+/// ```
+#[inline]
+pub fn from_gray<T: Word>(x: T) -> T {
+    let bits = T::bit_size().to_u32();
+    let mut x = x;
+    let mut shift = 1;
+    while shift < bits {
+        x = x ^ shift_logical_right(x, T::from_u32(shift));
+        shift *= 2;
+    }
+    x
+}
+
+/// Method version of [`from_gray`](fn.from_gray.html).
+pub trait FromGray {
+    #[allow(clippy::wrong_self_convention)]
+    fn from_gray(self) -> Self;
+}
+
+impl<T: Word> FromGray for T {
+    #[inline]
+    fn from_gray(self) -> Self {
+        from_gray(self)
+    }
+}
@@ -0,0 +1,213 @@
+//! Arithmetic in the finite fields GF(2^n) for `n = 8, 16, 32, 64, 128`.
+//!
+//! An element of GF(2^n) is a polynomial over GF(2) of degree less than `n`,
+//! stored in an unsigned word of `n` bits: the bit `i` is the coefficient of
+//! `x^i`. Addition is XOR, and multiplication is the
+//! [`clmul`](../fn.clmul.html) carry-less product reduced modulo an
+//! irreducible polynomial `x^n + p` of degree `n`. The polynomial is passed
+//! to the functions as the word `p`, without its `x^n` term.
+//!
+//! The `POLY_*` constants are irreducible polynomials with few terms, for
+//! which the reduction takes two carry-less multiplications. Other
+//! applications use other polynomials, e.g., Reed-Solomon codes often use
+//! `x^8 + x^4 + x^3 + x^2 + 1` (`0x1D`), for which `x` (`2`) generates the
+//! multiplicative group.
+//!
+//! GHASH (AES-GCM) uses `POLY_128` with the bits of each byte in reverse
+//! order: its product of the blocks `a` and `b` read as big-endian `u128`s
+//! is `reverse_bits(mul(reverse_bits(a), reverse_bits(b), POLY_128))`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bitwise::word::galois::{self, POLY_8};
+//!
+//! // The AES field (FIPS-197, section 4.2):
+//! assert_eq!(galois::mul(0x57u8, 0x83, POLY_8), 0xC1);
+//! assert_eq!(galois::mul(0x57u8, 0x13, POLY_8), 0xFE);
+//! assert_eq!(galois::inverse(0x53u8, POLY_8), 0xCA);
+//! assert_eq!(galois::pow(0x03u8, 255, POLY_8), 1);
+//! ```
+
+use word::{UnsignedWord, clmul};
+
+/// `x^8 + x^4 + x^3 + x + 1`, the polynomial of the AES field.
+pub const POLY_8: u8 = 0x1B;
+
+/// `x^16 + x^12 + x^3 + x + 1`.
+pub const POLY_16: u16 = 0x100B;
+
+/// `x^32 + x^7 + x^3 + x^2 + 1`.
+pub const POLY_32: u32 = 0x8D;
+
+/// `x^64 + x^4 + x^3 + x + 1`.
+pub const POLY_64: u64 = 0x1B;
+
+/// `x^128 + x^7 + x^2 + x + 1`, the polynomial of GHASH.
+pub const POLY_128: u128 = 0x87;
+
+/// Reduces the polynomial `high * x^n + low` modulo `x^n + poly`.
+///
+/// `low` and `high` are the halves of a carry-less product (see
+/// [`clmul`](../fn.clmul.html)). The high half is folded into the low half
+/// with `x^n = poly`, which takes `ceil(n / (n - deg(poly)))` carry-less
+/// multiplications at most.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+/// use bitwise::word::galois::{self, POLY_8};
+///
+/// // x^8 = x^4 + x^3 + x + 1:
+/// assert_eq!(galois::reduce(0u8, 1, POLY_8), POLY_8);
+/// let (low, high) = clmul(0x57u8, 0x83);
+/// assert_eq!(galois::reduce(low, high, POLY_8), 0xC1);
+/// ```
+#[inline]
+pub fn reduce<T: UnsignedWord>(low: T, high: T, poly: T) -> T {
+    let (mut low, mut high) = (low, high);
+    while high != T::zero() {
+        let (l, h) = clmul(high, poly);
+        low = low ^ l;
+        high = h;
+    }
+    low
+}
+
+/// Product of `a` and `b` in GF(2^n) modulo `x^n + poly`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::galois::{self, POLY_16, POLY_128};
+///
+/// assert_eq!(galois::mul(0x8000u16, 2, POLY_16), POLY_16);
+/// assert_eq!(galois::mul(1u128 << 127, 1 << 1, POLY_128), 0x87);
+/// assert_eq!(galois::mul(0xDEADu16, 1, POLY_16), 0xDEAD);
+/// ```
+#[inline]
+pub fn mul<T: UnsignedWord>(a: T, b: T, poly: T) -> T {
+    let (low, high) = clmul(a, b);
+    reduce(low, high, poly)
+}
+
+/// `a` raised to the power `exp` in GF(2^n) modulo `x^n + poly`.
+///
+/// Computed by square-and-multiply, with `0^0 = 1`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::galois::{self, POLY_8};
+///
+/// assert_eq!(galois::pow(2u8, 8, POLY_8), POLY_8);
+/// assert_eq!(galois::pow(0u8, 0, POLY_8), 1);
+/// assert_eq!(galois::pow(0x53u8, 254, POLY_8), 0xCA);
+/// ```
+#[inline]
+pub fn pow<T: UnsignedWord>(a: T, exp: T, poly: T) -> T {
+    let mut r = T::one();
+    let mut i = T::bit_size().to_u32();
+    while i > 0 {
+        i -= 1;
+        r = mul(r, r, poly);
+        if (exp >> T::from_u32(i)) & T::one() == T::one() {
+            r = mul(r, a, poly);
+        }
+    }
+    r
+}
+
+/// Multiplicative inverse of `a` in GF(2^n) modulo `x^n + poly`.
+///
+/// Computed as `a^(2^n - 2)`. The inverse of zero is zero, as in the AES
+/// S-box.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::galois::{self, POLY_8, POLY_64};
+///
+/// assert_eq!(galois::inverse(0x53u8, POLY_8), 0xCA);
+/// assert_eq!(galois::inverse(0u8, POLY_8), 0);
+/// let a = 0x0123_4567_89AB_CDEFu64;
+/// assert_eq!(galois::mul(a, galois::inverse(a, POLY_64), POLY_64), 1);
+/// ```
+#[inline]
+pub fn inverse<T: UnsignedWord>(a: T, poly: T) -> T {
+    pow(a, !T::one(), poly)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use word::reverse_bits;
+
+    /// Bit-by-bit product modulo `x^n + poly`.
+    fn naive<T: UnsignedWord>(a: T, b: T, poly: T) -> T {
+        let top = T::one() << (T::bit_size() - T::one());
+        let (mut a, mut r) = (a, T::zero());
+        for i in 0..T::bit_size().to_u32() {
+            if (b >> T::from_u32(i)) & T::one() == T::one() {
+                r = r ^ a;
+            }
+            a = if a & top == T::zero() { a << T::one() } else { (a << T::one()) ^ poly };
+        }
+        r
+    }
+
+    fn check_mul<T: UnsignedWord>(a: T, b: T, poly: T) -> bool {
+        let p = mul(a, b, poly);
+        p == naive(a, b, poly) && p == mul(b, a, poly)
+    }
+
+    fn check<T: UnsignedWord>(a: T, b: T, poly: T) -> bool {
+        check_mul(a, b, poly) &&
+        (a == T::zero() || mul(a, inverse(a, poly), poly) == T::one())
+    }
+
+    #[test]
+    fn prop_mul_inverse() {
+        fn inner(a: u64, b: u64, c: u64) -> bool {
+            let (x, y) = ((a as u128) << 64 | c as u128, (c as u128) << 64 | b as u128);
+            check(a as u8, b as u8, POLY_8) && check(a as u8, b as u8, 0x1D) &&
+            check(a as u16, b as u16, POLY_16) && check(a as u32, b as u32, POLY_32) &&
+            check(a, b, POLY_64) && check(x, y, POLY_128) && check(a, 0, POLY_64) &&
+            // Dense polynomials, which take more reduction steps:
+            check_mul(a, b, 0xF7FF_FFFF_FFFF_FFFF) && check_mul(x, y, !0 >> 1) &&
+            check_mul(a as u32, b as u32, c as u32)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u64) -> bool);
+    }
+
+    #[test]
+    fn fields() {
+        // Every non-zero element has an inverse, and the RS polynomial is
+        // primitive:
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, inverse(a, POLY_8), POLY_8), 1);
+            assert_eq!(mul(a, inverse(a, 0x1D), 0x1D), 1);
+        }
+        for a in 1..=65535u16 {
+            assert_eq!(mul(a, inverse(a, POLY_16), POLY_16), 1);
+        }
+        assert!((1..255u8).all(|e| pow(2u8, e, 0x1D) != 1));
+        assert_eq!(pow(2u8, 255, 0x1D), 1);
+    }
+
+    #[test]
+    fn ghash() {
+        // AES-GCM test case 2: GHASH of one block of ciphertext.
+        let gmul = |a: u128, b: u128| {
+            reverse_bits(mul(reverse_bits(a), reverse_bits(b), POLY_128))
+        };
+        let h = 0x66e9_4bd4_ef8a_2c3b_884c_fa59_ca34_2b2e;
+        let c = 0x0388_dace_60b6_a392_f328_c2b9_71b2_fe78;
+        let lengths = 0x80;
+        let x = gmul(c, h);
+        assert_eq!(x, 0x5e2e_c746_9170_6288_2c85_b068_5353_deb7);
+        assert_eq!(gmul(x ^ lengths, h), 0xf38c_bb1a_d692_23dc_c345_7ae5_b6b0_f885);
+    }
+}
@@ -63,11 +63,11 @@ mod tests {
     use super::*;
     #[test]
     fn test_u8() {
-        test_util::run_u8(0, u8::max_value(), euclid::recursive);
-        test_util::run_u8(0, u8::max_value(), euclid::iterative);
-        test_util::run_u8(0, u8::max_value(), steins::recursive);
-        test_util::run_u8(0, u8::max_value(), steins::iterative);
-        test_util::run_u8(0, u8::max_value(), steins::iterative_xor);
+        test_util::run_u8(0, u8::MAX, euclid::recursive);
+        test_util::run_u8(0, u8::MAX, euclid::iterative);
+        test_util::run_u8(0, u8::MAX, steins::recursive);
+        test_util::run_u8(0, u8::MAX, steins::iterative);
+        test_util::run_u8(0, u8::MAX, steins::iterative_xor);
     }
 
 }
@@ -195,7 +195,6 @@ pub fn greatest_common_divisor<T: Word>(x: T, y: T) -> T {
 
 /// Method version of [`greatest_common_divisor`](fn.greatest_common_divisor.html).
 pub trait GCD {
-    #[inline]
     fn greatest_common_divisor(self, Self) -> Self;
 }
 
@@ -0,0 +1,46 @@
+use word::{Word, parity};
+
+/// Index of the bit that changes between the Gray code `x` and the next
+/// Gray code.
+///
+/// If `x` has an even number of set bits this is bit zero, otherwise it is
+/// the bit to the left of the least significant set bit of `x` (the most
+/// significant bit for the code of the largest integer, which is followed by
+/// the code of zero).
+///
+/// # Keywords:
+///
+/// Gray code, rotary encoder.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(gray_changed_bit(0b000u8), 0); // 0b000 -> 0b001
+/// assert_eq!(gray_changed_bit(0b001u8), 1); // 0b001 -> 0b011
+/// assert_eq!(0b011u8.gray_changed_bit(), 0); // 0b011 -> 0b010
+/// assert_eq!(0b010u8.gray_changed_bit(), 2); // 0b010 -> 0b110
+/// assert_eq!(0b1000_0000u8.gray_changed_bit(), 7); // 0b1000_0000 -> 0b0
+/// ```
+#[inline]
+pub fn gray_changed_bit<T: Word>(x: T) -> T {
+    if parity(x) == T::zero() {
+        return T::zero();
+    }
+    let bit = x.trailing_zeros() + T::one();
+    let last = T::bit_size() - T::one();
+    if bit > last { last } else { bit }
+}
+
+/// Method version of [`gray_changed_bit`](fn.gray_changed_bit.html).
+pub trait GrayChangedBit {
+    fn gray_changed_bit(self) -> Self;
+}
+
+impl<T: Word> GrayChangedBit for T {
+    #[inline]
+    fn gray_changed_bit(self) -> Self {
+        gray_changed_bit(self)
+    }
+}
@@ -0,0 +1,87 @@
+use word::{Word, gray_changed_bit};
+
+/// Gray code that follows the Gray code `x`, that is,
+/// `to_gray(from_gray(x) + 1)`, wrapping around to zero after the code of
+/// the largest integer.
+///
+/// # Keywords:
+///
+/// Gray code, increment, counter.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let mut code = 0u8;
+/// for i in 1..=255u8 {
+///     code = gray_increment(code);
+///     assert_eq!(code, to_gray(i));
+/// }
+/// assert_eq!(code.gray_increment(), 0);
+/// assert_eq!(0b011i32.gray_increment(), 0b010);
+/// ```
+#[inline]
+pub fn gray_increment<T: Word>(x: T) -> T {
+    x ^ (T::one() << gray_changed_bit(x))
+}
+
+/// Method version of [`gray_increment`](fn.gray_increment.html).
+pub trait GrayIncrement {
+    fn gray_increment(self) -> Self;
+}
+
+impl<T: Word> GrayIncrement for T {
+    #[inline]
+    fn gray_increment(self) -> Self {
+        gray_increment(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use word::*;
+    use quickcheck::QuickCheck;
+
+    macro_rules! prop_gray_tests {
+        ($($name:ident: $WordType:ty,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    fn inner(x: $WordType) -> bool {
+                        let g = x.to_gray();
+                        let next = x.wrapping_add(1).to_gray();
+                        g.from_gray() == x && x.from_gray().to_gray() == x &&
+                        (g ^ next).count_ones() == 1 &&
+                        g.gray_increment() == next &&
+                        (g ^ next).trailing_zeros() == g.gray_changed_bit() as u32
+                    }
+                    QuickCheck::new().quickcheck(inner as fn($WordType) -> bool);
+                }
+            )*
+        }
+    }
+
+    prop_gray_tests! {
+        prop_gray_u8: u8,
+        prop_gray_i8: i8,
+        prop_gray_u16: u16,
+        prop_gray_i16: i16,
+        prop_gray_u32: u32,
+        prop_gray_i32: i32,
+        prop_gray_u64: u64,
+        prop_gray_i64: i64,
+        prop_gray_usize: usize,
+        prop_gray_isize: isize,
+    }
+
+    #[test]
+    fn gray_128() {
+        for &x in &[0u128, 1, 2, u128::MAX, u128::MAX - 1, 1 << 127, (1 << 127) - 1] {
+            let g = x.to_gray();
+            assert_eq!(g.from_gray(), x);
+            assert_eq!(g.gray_increment(), x.wrapping_add(1).to_gray());
+        }
+        assert_eq!(from_gray(1i128 << 127), -1);
+    }
+}
@@ -42,8 +42,7 @@ pub fn hamming_distance<T: Word>(x: T, y: T) -> T {
 
 /// Method version of [`hamming_distance`](fn.hamming_distance.html).
 pub trait HammingDistance {
-    #[inline]
-    fn hamming_distance(self, Self) -> Self;
+    fn hamming_distance(self, y: Self) -> Self;
 }
 
 impl<T: Word> HammingDistance for T {
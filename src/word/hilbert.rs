@@ -0,0 +1,738 @@
+//! Encoding/decoding of Hilbert curve indices.
+//!
+//! The Hilbert curve visits the cells of a `2^b x 2^b` (2D) or
+//! `2^b x 2^b x 2^b` (3D) grid such that consecutive indices always map to
+//! neighboring cells, which gives it better locality than the Morton Z-curve.
+//! Both curves start at the origin and end at `(2^b - 1, 0)` (2D) and
+//! `(2^b - 1, 0, 0)` (3D).
+//!
+//! The number of bits `b` used per coordinate is `bit_size / 2` in 2D and
+//! `bit_size / 3` in 3D, where `bit_size` is the number of bits of the index.
+//! The higher bits of the coordinates are ignored when encoding. In 3D, the
+//! `bit_size % 3` highest bits of the index are ignored when decoding and are
+//! zero when encoding.
+//!
+//! The encode/decode 2d/3d functions in this module use the `lut`
+//! implementation, which is significantly faster than the `skilling`
+//! implementation in the micro-benchmarks (see `benches/hilbert.rs`). Both
+//! implementations are provided in the sub-modules and produce the same curve.
+
+use word::Word;
+
+/// Mask of the lower `bits` bits of a word.
+#[inline]
+fn low_bits<T: Word>(bits: u32) -> T {
+    (T::one() << T::from_u32(bits)) - T::one()
+}
+
+pub mod lut {
+    //! Encoding/decoding of Hilbert curve indices using a look-up table.
+    //!
+    //! The tables implement the state machine of the curve: each entry maps
+    //! the current state and a group of coordinate (index) bits to the
+    //! corresponding index (coordinate) bits and the next state. The 2D
+    //! tables process 4 levels of the curve per look-up, and the 3D tables
+    //! process 2 levels per look-up.
+    //!
+    //! Encoding table entries are indexed by `state << 8 | x << 4 | y` (2D)
+    //! and `state << 6 | x << 4 | y << 2 | z` (3D). Decoding table entries are
+    //! indexed by `state << 8 | index bits` (2D) and `state << 6 | index bits`
+    //! (3D). The lower bits of an entry store the result and the higher bits
+    //! the next state.
+
+    use word::{Word, ToWord};
+    use super::low_bits;
+
+    const ENCODE_2D: [u16; 1024] =
+        [0, 515, 260, 5, 570, 315, 60, 575, 320, 65, 590, 335, 80, 595, 340, 85, 257, 258, 775, 6,
+         569, 824, 317, 318, 835, 66, 589, 844, 337, 338, 855, 86, 782, 781, 264, 9, 566, 311, 818,
+         817, 68, 583, 72, 587, 862, 861, 344, 89, 15, 524, 779, 10, 565, 820, 51, 560, 325, 326,
+         329, 330, 95, 604, 859, 90, 272, 17, 542, 287, 288, 33, 558, 303, 890, 889, 886, 885, 96,
+         611, 356, 101, 787, 18, 541, 796, 803, 34, 557, 812, 123, 632, 119, 628, 353, 354, 871,
+         102, 20, 535, 24, 539, 36, 551, 40, 555, 380, 125, 626, 371, 878, 877, 360, 105, 277, 278,
+         281, 282, 293, 294, 297, 298, 895, 126, 625, 880, 111, 620, 875, 106, 1002, 1001, 998, 997,
+         986, 985, 982, 981, 384, 129, 654, 399, 144, 659, 404, 149, 235, 744, 231, 740, 219, 728,
+         215, 724, 899, 130, 653, 908, 401, 402, 919, 150, 492, 237, 738, 483, 476, 221, 722, 467,
+         132, 647, 136, 651, 926, 925, 408, 153, 1007, 238, 737, 992, 991, 222, 721, 976, 389, 390,
+         393, 394, 159, 668, 923, 154, 240, 755, 500, 245, 714, 459, 204, 719, 954, 953, 950, 949,
+         160, 675, 420, 165, 497, 498, 1015, 246, 713, 968, 461, 462, 187, 696, 183, 692, 417, 418,
+         935, 166, 1022, 1021, 504, 249, 710, 455, 962, 961, 444, 189, 690, 435, 942, 941, 424, 169,
+         255, 764, 1019, 250, 709, 964, 195, 704, 959, 190, 689, 944, 175, 684, 939, 170, 256, 1,
+         526, 271, 16, 531, 276, 21, 746, 491, 236, 751, 496, 241, 766, 511, 771, 2, 525, 780, 273,
+         274, 791, 22, 745, 1000, 493, 494, 1011, 242, 765, 1020, 4, 519, 8, 523, 798, 797, 280, 25,
+         742, 487, 994, 993, 244, 759, 248, 763, 261, 262, 265, 266, 31, 540, 795, 26, 741, 996,
+         227, 736, 501, 502, 505, 506, 826, 825, 822, 821, 32, 547, 292, 37, 730, 475, 220, 735,
+         970, 969, 966, 965, 59, 568, 55, 564, 289, 290, 807, 38, 729, 984, 477, 478, 203, 712, 199,
+         708, 316, 61, 562, 307, 814, 813, 296, 41, 726, 471, 978, 977, 460, 205, 706, 451, 831, 62,
+         561, 816, 47, 556, 811, 42, 725, 980, 211, 720, 975, 206, 705, 960, 64, 579, 324, 69, 634,
+         379, 124, 639, 128, 643, 388, 133, 698, 443, 188, 703, 321, 322, 839, 70, 633, 888, 381,
+         382, 385, 386, 903, 134, 697, 952, 445, 446, 846, 845, 328, 73, 630, 375, 882, 881, 910,
+         909, 392, 137, 694, 439, 946, 945, 79, 588, 843, 74, 629, 884, 115, 624, 143, 652, 907,
+         138, 693, 948, 179, 688, 336, 81, 606, 351, 352, 97, 622, 367, 400, 145, 670, 415, 416,
+         161, 686, 431, 851, 82, 605, 860, 867, 98, 621, 876, 915, 146, 669, 924, 931, 162, 685,
+         940, 84, 599, 88, 603, 100, 615, 104, 619, 148, 663, 152, 667, 164, 679, 168, 683, 341,
+         342, 345, 346, 357, 358, 361, 362, 405, 406, 409, 410, 421, 422, 425, 426, 682, 427, 172,
+         687, 432, 177, 702, 447, 192, 707, 452, 197, 762, 507, 252, 767, 681, 936, 429, 430, 947,
+         178, 701, 956, 449, 450, 967, 198, 761, 1016, 509, 510, 678, 423, 930, 929, 180, 695, 184,
+         699, 974, 973, 456, 201, 758, 503, 1010, 1009, 677, 932, 163, 672, 437, 438, 441, 442, 207,
+         716, 971, 202, 757, 1012, 243, 752, 666, 411, 156, 671, 906, 905, 902, 901, 464, 209, 734,
+         479, 480, 225, 750, 495, 665, 920, 413, 414, 139, 648, 135, 644, 979, 210, 733, 988, 995,
+         226, 749, 1004, 662, 407, 914, 913, 396, 141, 642, 387, 212, 727, 216, 731, 228, 743, 232,
+         747, 661, 916, 147, 656, 911, 142, 641, 896, 469, 470, 473, 474, 485, 486, 489, 490, 618,
+         363, 108, 623, 368, 113, 638, 383, 810, 809, 806, 805, 794, 793, 790, 789, 617, 872, 365,
+         366, 883, 114, 637, 892, 43, 552, 39, 548, 27, 536, 23, 532, 614, 359, 866, 865, 116, 631,
+         120, 635, 300, 45, 546, 291, 284, 29, 530, 275, 613, 868, 99, 608, 373, 374, 377, 378, 815,
+         46, 545, 800, 799, 30, 529, 784, 602, 347, 92, 607, 842, 841, 838, 837, 48, 563, 308, 53,
+         522, 267, 12, 527, 601, 856, 349, 350, 75, 584, 71, 580, 305, 306, 823, 54, 521, 776, 269,
+         270, 598, 343, 850, 849, 332, 77, 578, 323, 830, 829, 312, 57, 518, 263, 770, 769, 597,
+         852, 83, 592, 847, 78, 577, 832, 63, 572, 827, 58, 517, 772, 3, 512, 938, 937, 934, 933,
+         922, 921, 918, 917, 874, 873, 870, 869, 858, 857, 854, 853, 171, 680, 167, 676, 155, 664,
+         151, 660, 107, 616, 103, 612, 91, 600, 87, 596, 428, 173, 674, 419, 412, 157, 658, 403,
+         364, 109, 610, 355, 348, 93, 594, 339, 943, 174, 673, 928, 927, 158, 657, 912, 879, 110,
+         609, 864, 863, 94, 593, 848, 176, 691, 436, 181, 650, 395, 140, 655, 112, 627, 372, 117,
+         586, 331, 76, 591, 433, 434, 951, 182, 649, 904, 397, 398, 369, 370, 887, 118, 585, 840,
+         333, 334, 958, 957, 440, 185, 646, 391, 898, 897, 894, 893, 376, 121, 582, 327, 834, 833,
+         191, 700, 955, 186, 645, 900, 131, 640, 127, 636, 891, 122, 581, 836, 67, 576, 448, 193,
+         718, 463, 208, 723, 468, 213, 554, 299, 44, 559, 304, 49, 574, 319, 963, 194, 717, 972,
+         465, 466, 983, 214, 553, 808, 301, 302, 819, 50, 573, 828, 196, 711, 200, 715, 990, 989,
+         472, 217, 550, 295, 802, 801, 52, 567, 56, 571, 453, 454, 457, 458, 223, 732, 987, 218,
+         549, 804, 35, 544, 309, 310, 313, 314, 1018, 1017, 1014, 1013, 224, 739, 484, 229, 538,
+         283, 28, 543, 778, 777, 774, 773, 251, 760, 247, 756, 481, 482, 999, 230, 537, 792, 285,
+         286, 11, 520, 7, 516, 508, 253, 754, 499, 1006, 1005, 488, 233, 534, 279, 786, 785, 268,
+         13, 514, 259, 1023, 254, 753, 1008, 239, 748, 1003, 234, 533, 788, 19, 528, 783, 14, 513,
+         768];
+
+    const DECODE_2D: [u16; 1024] =
+        [0, 272, 273, 513, 258, 3, 19, 786, 290, 35, 51, 818, 561, 801, 800, 48, 320, 65, 81, 848,
+         96, 368, 369, 609, 98, 370, 371, 611, 851, 594, 578, 323, 324, 69, 85, 852, 100, 372, 373,
+         613, 102, 374, 375, 615, 855, 598, 582, 327, 567, 807, 806, 54, 821, 564, 548, 293, 789,
+         532, 516, 261, 6, 278, 279, 519, 264, 9, 25, 792, 40, 312, 313, 553, 42, 314, 315, 555,
+         795, 538, 522, 267, 12, 284, 285, 525, 270, 15, 31, 798, 302, 47, 63, 830, 573, 813, 812,
+         60, 76, 348, 349, 589, 334, 79, 95, 862, 366, 111, 127, 894, 637, 877, 876, 124, 891, 634,
+         618, 363, 603, 843, 842, 90, 601, 841, 840, 88, 360, 105, 121, 888, 392, 137, 153, 920,
+         168, 440, 441, 681, 170, 442, 443, 683, 923, 666, 650, 395, 140, 412, 413, 653, 398, 143,
+         159, 926, 430, 175, 191, 958, 701, 941, 940, 188, 204, 476, 477, 717, 462, 207, 223, 990,
+         494, 239, 255, 1022, 765, 1005, 1004, 252, 1019, 762, 746, 491, 731, 971, 970, 218, 729,
+         969, 968, 216, 488, 233, 249, 1016, 759, 999, 998, 246, 1013, 756, 740, 485, 981, 724, 708,
+         453, 198, 470, 471, 711, 951, 694, 678, 423, 663, 903, 902, 150, 661, 901, 900, 148, 420,
+         165, 181, 948, 947, 690, 674, 419, 659, 899, 898, 146, 657, 897, 896, 144, 416, 161, 177,
+         944, 192, 464, 465, 705, 450, 195, 211, 978, 482, 227, 243, 1010, 753, 993, 992, 240, 256,
+         1, 17, 784, 32, 304, 305, 545, 34, 306, 307, 547, 787, 530, 514, 259, 4, 276, 277, 517,
+         262, 7, 23, 790, 294, 39, 55, 822, 565, 805, 804, 52, 68, 340, 341, 581, 326, 71, 87, 854,
+         358, 103, 119, 886, 629, 869, 868, 116, 883, 626, 610, 355, 595, 835, 834, 82, 593, 833,
+         832, 80, 352, 97, 113, 880, 128, 400, 401, 641, 386, 131, 147, 914, 418, 163, 179, 946,
+         689, 929, 928, 176, 448, 193, 209, 976, 224, 496, 497, 737, 226, 498, 499, 739, 979, 722,
+         706, 451, 452, 197, 213, 980, 228, 500, 501, 741, 230, 502, 503, 743, 983, 726, 710, 455,
+         695, 935, 934, 182, 949, 692, 676, 421, 917, 660, 644, 389, 134, 406, 407, 647, 136, 408,
+         409, 649, 394, 139, 155, 922, 426, 171, 187, 954, 697, 937, 936, 184, 456, 201, 217, 984,
+         232, 504, 505, 745, 234, 506, 507, 747, 987, 730, 714, 459, 460, 205, 221, 988, 236, 508,
+         509, 749, 238, 510, 511, 751, 991, 734, 718, 463, 703, 943, 942, 190, 957, 700, 684, 429,
+         925, 668, 652, 397, 142, 414, 415, 655, 895, 638, 622, 367, 607, 847, 846, 94, 605, 845,
+         844, 92, 364, 109, 125, 892, 635, 875, 874, 122, 889, 632, 616, 361, 857, 600, 584, 329,
+         74, 346, 347, 587, 571, 811, 810, 58, 825, 568, 552, 297, 793, 536, 520, 265, 10, 282, 283,
+         523, 268, 13, 29, 796, 44, 316, 317, 557, 46, 318, 319, 559, 799, 542, 526, 271, 767, 1007,
+         1006, 254, 1021, 764, 748, 493, 989, 732, 716, 461, 206, 478, 479, 719, 959, 702, 686, 431,
+         671, 911, 910, 158, 669, 909, 908, 156, 428, 173, 189, 956, 955, 698, 682, 427, 667, 907,
+         906, 154, 665, 905, 904, 152, 424, 169, 185, 952, 200, 472, 473, 713, 458, 203, 219, 986,
+         490, 235, 251, 1018, 761, 1001, 1000, 248, 1015, 758, 742, 487, 727, 967, 966, 214, 725,
+         965, 964, 212, 484, 229, 245, 1012, 755, 995, 994, 242, 1009, 752, 736, 481, 977, 720, 704,
+         449, 194, 466, 467, 707, 691, 931, 930, 178, 945, 688, 672, 417, 913, 656, 640, 385, 130,
+         402, 403, 643, 388, 133, 149, 916, 164, 436, 437, 677, 166, 438, 439, 679, 919, 662, 646,
+         391, 887, 630, 614, 359, 599, 839, 838, 86, 597, 837, 836, 84, 356, 101, 117, 884, 627,
+         867, 866, 114, 881, 624, 608, 353, 849, 592, 576, 321, 66, 338, 339, 579, 563, 803, 802,
+         50, 817, 560, 544, 289, 785, 528, 512, 257, 2, 274, 275, 515, 260, 5, 21, 788, 36, 308,
+         309, 549, 38, 310, 311, 551, 791, 534, 518, 263, 8, 280, 281, 521, 266, 11, 27, 794, 298,
+         43, 59, 826, 569, 809, 808, 56, 328, 73, 89, 856, 104, 376, 377, 617, 106, 378, 379, 619,
+         859, 602, 586, 331, 332, 77, 93, 860, 108, 380, 381, 621, 110, 382, 383, 623, 863, 606,
+         590, 335, 575, 815, 814, 62, 829, 572, 556, 301, 797, 540, 524, 269, 14, 286, 287, 527,
+         1023, 766, 750, 495, 735, 975, 974, 222, 733, 973, 972, 220, 492, 237, 253, 1020, 763,
+         1003, 1002, 250, 1017, 760, 744, 489, 985, 728, 712, 457, 202, 474, 475, 715, 699, 939,
+         938, 186, 953, 696, 680, 425, 921, 664, 648, 393, 138, 410, 411, 651, 396, 141, 157, 924,
+         172, 444, 445, 685, 174, 446, 447, 687, 927, 670, 654, 399, 639, 879, 878, 126, 893, 636,
+         620, 365, 861, 604, 588, 333, 78, 350, 351, 591, 831, 574, 558, 303, 543, 783, 782, 30,
+         541, 781, 780, 28, 300, 45, 61, 828, 827, 570, 554, 299, 539, 779, 778, 26, 537, 777, 776,
+         24, 296, 41, 57, 824, 72, 344, 345, 585, 330, 75, 91, 858, 362, 107, 123, 890, 633, 873,
+         872, 120, 631, 871, 870, 118, 885, 628, 612, 357, 853, 596, 580, 325, 70, 342, 343, 583,
+         823, 566, 550, 295, 535, 775, 774, 22, 533, 773, 772, 20, 292, 37, 53, 820, 819, 562, 546,
+         291, 531, 771, 770, 18, 529, 769, 768, 16, 288, 33, 49, 816, 64, 336, 337, 577, 322, 67,
+         83, 850, 354, 99, 115, 882, 625, 865, 864, 112, 384, 129, 145, 912, 160, 432, 433, 673,
+         162, 434, 435, 675, 915, 658, 642, 387, 132, 404, 405, 645, 390, 135, 151, 918, 422, 167,
+         183, 950, 693, 933, 932, 180, 196, 468, 469, 709, 454, 199, 215, 982, 486, 231, 247, 1014,
+         757, 997, 996, 244, 1011, 754, 738, 483, 723, 963, 962, 210, 721, 961, 960, 208, 480, 225,
+         241, 1008];
+
+    const ENCODE_3D: [u16; 1536] =
+        [64, 903, 136, 9, 321, 774, 975, 846, 1438, 601, 208, 273, 1501, 1498, 1491, 18, 1283, 260,
+         1355, 266, 194, 197, 204, 269, 351, 792, 1111, 1046, 1180, 27, 148, 21, 508, 1083, 436,
+         1077, 1149, 1146, 1139, 1074, 352, 807, 232, 297, 1187, 36, 1515, 42, 1471, 632, 1527, 54,
+         382, 825, 560, 881, 97, 934, 1135, 1070, 162, 165, 172, 45, 192, 257, 1374, 1245, 1475, 2,
+         287, 1436, 572, 893, 288, 1443, 1407, 318, 225, 354, 1095, 1030, 409, 1242, 132, 5, 1048,
+         91, 1019, 890, 1063, 100, 440, 1081, 1126, 357, 328, 783, 16, 1235, 1163, 12, 855, 340,
+         692, 883, 40, 1259, 1271, 752, 879, 364, 73, 910, 145, 82, 138, 141, 982, 85, 1013, 1010,
+         169, 106, 118, 945, 1006, 109, 256, 1411, 636, 1343, 193, 322, 829, 254, 1310, 223, 224,
+         289, 1181, 1500, 1507, 34, 1031, 68, 955, 504, 1094, 325, 826, 1145, 473, 1112, 1127, 1062,
+         1178, 155, 164, 37, 8, 1227, 756, 1207, 847, 332, 819, 688, 336, 791, 360, 815, 1171, 20,
+         1195, 44, 137, 74, 949, 182, 974, 77, 946, 1009, 81, 918, 105, 942, 146, 149, 170, 173, 0,
+         1219, 764, 1215, 839, 324, 827, 696, 264, 1419, 628, 1335, 201, 330, 821, 246, 129, 66,
+         957, 190, 966, 69, 954, 1017, 1039, 76, 947, 496, 1102, 333, 818, 1137, 1182, 159, 160, 33,
+         665, 984, 999, 870, 80, 919, 104, 943, 337, 790, 361, 814, 1309, 1372, 1379, 290, 1306,
+         219, 228, 293, 1299, 276, 1323, 300, 210, 213, 234, 237, 320, 775, 200, 265, 1155, 4, 1483,
+         10, 700, 891, 564, 885, 1279, 760, 1399, 310, 65, 902, 1103, 1038, 130, 133, 140, 13, 1021,
+         1018, 1011, 882, 126, 953, 432, 1073, 1246, 729, 144, 17, 95, 920, 983, 854, 96, 935, 168,
+         41, 353, 806, 1007, 878, 1373, 1370, 1363, 274, 1308, 283, 212, 277, 1315, 292, 1387, 298,
+         226, 229, 236, 301, 128, 1, 1502, 1437, 967, 838, 537, 1434, 72, 911, 272, 1427, 329, 782,
+         209, 338, 1347, 258, 31, 1244, 196, 261, 856, 347, 1291, 268, 1047, 84, 202, 205, 1110,
+         341, 444, 1085, 32, 1251, 1147, 1082, 871, 356, 500, 1075, 296, 1451, 1141, 1138, 233, 362,
+         1535, 62, 161, 98, 568, 889, 998, 101, 1463, 624, 1071, 108, 374, 817, 1134, 365, 402, 405,
+         426, 429, 467, 1044, 491, 1068, 474, 411, 420, 1061, 477, 1116, 1123, 1058, 1233, 726,
+         1257, 750, 1424, 599, 1448, 623, 1177, 1496, 1511, 38, 670, 543, 544, 865, 1358, 1229, 754,
+         1393, 271, 1420, 627, 1328, 1478, 1413, 634, 1529, 513, 1410, 637, 574, 393, 1226, 757,
+         438, 1032, 75, 948, 503, 7, 1220, 763, 1208, 832, 323, 828, 703, 466, 403, 410, 413, 469,
+         1108, 475, 1052, 490, 427, 484, 1059, 493, 1132, 1125, 1122, 1169, 1488, 1241, 734, 662,
+         535, 1432, 607, 1193, 1512, 1447, 608, 686, 559, 358, 801, 1294, 207, 1414, 577, 1165,
+         1484, 1477, 1474, 690, 563, 570, 573, 1329, 1392, 1465, 638, 457, 1096, 327, 768, 1162,
+         139, 1156, 3, 693, 1012, 699, 892, 502, 439, 1272, 767, 530, 533, 554, 557, 1425, 598,
+         1449, 622, 1486, 1421, 626, 1521, 521, 1418, 629, 566, 659, 852, 683, 876, 1232, 727, 1256,
+         751, 15, 1228, 755, 1200, 840, 331, 820, 695, 666, 539, 548, 869, 1305, 1368, 1383, 294,
+         1350, 1221, 762, 1401, 263, 1412, 635, 1336, 669, 988, 995, 866, 478, 415, 416, 1057, 385,
+         1218, 765, 446, 1024, 67, 956, 511, 594, 1489, 1422, 585, 597, 534, 1485, 1482, 618, 1513,
+         562, 565, 621, 558, 1457, 630, 723, 1168, 335, 776, 788, 663, 1164, 11, 747, 1192, 691,
+         884, 812, 687, 1264, 759, 730, 1369, 1286, 199, 603, 1304, 1157, 1476, 612, 1319, 698, 571,
+         805, 230, 1337, 1400, 733, 414, 449, 1088, 924, 479, 1154, 131, 931, 480, 701, 1020, 802,
+         1121, 510, 447, 658, 531, 538, 541, 1297, 1360, 1433, 606, 1166, 143, 1222, 705, 649, 968,
+         71, 896, 661, 980, 667, 860, 470, 407, 1240, 735, 1293, 1356, 1349, 1346, 1290, 203, 1284,
+         259, 682, 555, 676, 867, 1321, 1384, 1255, 736, 498, 435, 442, 445, 501, 1140, 507, 1084,
+         685, 1004, 997, 994, 494, 431, 102, 929, 1201, 1520, 1273, 766, 694, 567, 1464, 639, 722,
+         1361, 1230, 713, 595, 1296, 79, 904, 602, 1497, 1158, 135, 605, 542, 641, 960, 725, 406,
+         1357, 1354, 916, 471, 1292, 267, 731, 1176, 1285, 1348, 796, 671, 1282, 195, 746, 1385,
+         434, 437, 619, 1320, 499, 1076, 740, 1191, 506, 443, 803, 672, 509, 1148, 749, 430, 1265,
+         758, 940, 495, 1456, 631, 933, 166, 1209, 1528, 930, 993, 702, 575, 610, 1505, 1214, 191,
+         613, 550, 697, 1016, 620, 1327, 112, 951, 813, 238, 369, 822, 739, 1184, 1341, 1404, 804,
+         679, 1338, 251, 939, 488, 1331, 308, 810, 1129, 242, 245, 732, 1183, 450, 387, 795, 664,
+         453, 1092, 596, 1303, 460, 1035, 789, 214, 1101, 1098, 925, 158, 1153, 1472, 922, 985, 646,
+         519, 915, 464, 1423, 584, 786, 1105, 334, 777, 546, 549, 556, 877, 1441, 614, 1391, 302,
+         1278, 761, 176, 49, 127, 952, 1015, 886, 675, 868, 1003, 874, 1248, 743, 424, 1065, 1405,
+         1402, 1395, 306, 1340, 315, 244, 309, 668, 859, 532, 853, 1247, 728, 1367, 278, 386, 389,
+         396, 1037, 451, 1028, 1099, 1034, 989, 986, 979, 850, 94, 921, 400, 1041, 1217, 710, 1487,
+         14, 1408, 583, 520, 841, 738, 1377, 1342, 255, 611, 1312, 1213, 1532, 604, 1311, 642, 515,
+         797, 222, 1281, 1344, 741, 422, 505, 1144, 932, 487, 1210, 187, 923, 472, 645, 964, 794,
+         1113, 454, 391, 748, 1199, 368, 823, 811, 680, 1203, 52, 724, 1175, 652, 843, 787, 656,
+         1231, 712, 941, 174, 113, 950, 938, 1001, 178, 181, 917, 150, 973, 970, 914, 977, 78, 905,
+         674, 547, 540, 861, 1313, 1376, 1375, 286, 1406, 1277, 706, 1345, 319, 1468, 579, 1280,
+         677, 996, 987, 858, 486, 423, 408, 1049, 441, 1274, 709, 390, 1080, 123, 900, 455, 684,
+         875, 660, 851, 1263, 744, 1239, 720, 48, 1267, 716, 1167, 887, 372, 779, 648, 1005, 1002,
+         981, 978, 110, 937, 86, 913, 177, 114, 909, 142, 1014, 117, 906, 969, 418, 421, 428, 1069,
+         483, 1060, 1131, 1066, 476, 1051, 404, 1045, 1117, 1114, 1107, 1042, 1249, 742, 1519, 46,
+         1440, 615, 552, 873, 1439, 600, 1495, 22, 350, 793, 528, 849, 1470, 633, 240, 305, 1533,
+         1530, 1523, 50, 514, 517, 524, 845, 1409, 582, 1359, 270, 383, 824, 1143, 1078, 1212, 59,
+         180, 53, 643, 836, 971, 842, 1216, 711, 392, 1033, 482, 419, 412, 1053, 485, 1124, 1115,
+         1050, 492, 1067, 468, 1043, 1133, 1130, 1109, 1106, 1185, 1504, 1503, 30, 678, 551, 536,
+         857, 1455, 616, 1431, 592, 366, 809, 342, 785, 1534, 1469, 578, 1473, 569, 1466, 581, 518,
+         304, 1459, 588, 1295, 241, 370, 781, 206, 63, 1276, 707, 1152, 888, 379, 772, 647, 1079,
+         116, 907, 456, 1142, 373, 778, 1097, 1206, 183, 120, 959, 689, 1008, 377, 830, 1326, 239,
+         1446, 609, 1197, 1516, 1509, 1506, 1333, 1396, 1339, 316, 1330, 243, 250, 253, 489, 1128,
+         359, 800, 1194, 171, 1188, 35, 458, 395, 452, 1027, 461, 1100, 1093, 1090, 1302, 215, 344,
+         799, 1173, 1492, 1179, 28, 1161, 1480, 1415, 576, 654, 527, 326, 769, 465, 1104, 89, 926,
+         1170, 147, 154, 157, 1270, 753, 1390, 1261, 119, 944, 303, 1452, 184, 57, 1510, 1445, 1023,
+         894, 545, 1442, 1397, 1394, 425, 1258, 1332, 307, 1064, 107, 1403, 314, 39, 1252, 252, 317,
+         864, 355, 394, 397, 1366, 1237, 459, 1036, 279, 1428, 388, 1029, 24, 1243, 1091, 1026, 863,
+         348, 1225, 718, 401, 1234, 1416, 591, 1040, 83, 1479, 6, 153, 90, 512, 833, 990, 93, 1334,
+         247, 376, 831, 1205, 1524, 1211, 60, 650, 523, 644, 835, 1289, 1352, 1223, 704, 497, 1136,
+         121, 958, 1202, 179, 186, 189, 653, 972, 965, 962, 462, 399, 70, 897, 1198, 175, 1254, 737,
+         681, 1000, 103, 928, 1174, 151, 88, 927, 657, 976, 345, 798, 1325, 1388, 1381, 1378, 1322,
+         235, 1316, 291, 1301, 1364, 1307, 284, 1298, 211, 218, 221, 1398, 1269, 714, 1353, 311,
+         1460, 587, 1288, 56, 1275, 708, 1159, 895, 380, 771, 640, 433, 1266, 717, 398, 1072, 115,
+         908, 463, 185, 122, 901, 134, 1022, 125, 898, 961, 1262, 745, 1238, 721, 111, 936, 87, 912,
+         1190, 167, 152, 25, 673, 992, 991, 862, 1389, 1386, 1365, 1362, 1324, 299, 1300, 275, 1317,
+         1380, 1371, 282, 1314, 227, 220, 285, 1462, 625, 1518, 1453, 1525, 1522, 553, 1450, 522,
+         525, 1494, 1429, 1417, 590, 529, 1426, 375, 816, 47, 1260, 1204, 51, 872, 363, 651, 844,
+         23, 1236, 1224, 719, 848, 339, 248, 313, 1382, 1253, 1531, 58, 295, 1444, 516, 837, 280,
+         1435, 1351, 262, 217, 346, 1151, 1086, 417, 1250, 188, 61, 1056, 99, 963, 834, 1055, 92,
+         384, 1025, 1118, 349, 1526, 1461, 586, 1481, 561, 1458, 589, 526, 1454, 617, 1430, 593,
+         1517, 1514, 1493, 1490, 55, 1268, 715, 1160, 880, 371, 780, 655, 367, 808, 343, 784, 1196,
+         43, 1172, 19, 312, 1467, 580, 1287, 249, 378, 773, 198, 1318, 231, 216, 281, 1189, 1508,
+         1499, 26, 1087, 124, 899, 448, 1150, 381, 770, 1089, 481, 1120, 1119, 1054, 1186, 163, 156,
+         29];
+
+    const DECODE_3D: [u16; 1536] =
+        [64, 324, 212, 1296, 273, 213, 773, 897, 130, 3, 275, 1362, 214, 279, 839, 966, 202, 267,
+         15, 1486, 158, 31, 1051, 1114, 793, 585, 1485, 29, 1180, 1484, 1416, 344, 360, 120, 188,
+         1196, 45, 189, 953, 809, 234, 299, 47, 1518, 190, 63, 1083, 1146, 566, 887, 1063, 1126,
+         418, 1059, 51, 1522, 625, 821, 1125, 1057, 480, 1124, 372, 1456, 192, 257, 5, 1476, 148,
+         21, 1041, 1104, 352, 112, 180, 1188, 37, 181, 945, 801, 34, 178, 115, 1251, 359, 119, 1014,
+         870, 1046, 402, 1235, 87, 1415, 1219, 1346, 262, 266, 206, 335, 1419, 91, 351, 1118, 1050,
+         42, 186, 123, 1259, 367, 127, 1022, 878, 749, 957, 1017, 873, 680, 1016, 124, 1260, 412,
+         1053, 857, 984, 520, 841, 269, 1356, 256, 196, 325, 1409, 81, 341, 1108, 1040, 32, 176,
+         113, 1249, 357, 117, 1012, 868, 360, 120, 188, 1196, 45, 189, 953, 809, 1113, 472, 1180,
+         157, 1485, 1164, 1288, 201, 202, 267, 15, 1486, 158, 31, 1051, 1114, 362, 122, 190, 1198,
+         47, 191, 955, 811, 679, 1015, 950, 806, 738, 946, 179, 1187, 467, 1111, 790, 914, 578, 774,
+         199, 1283, 0, 144, 81, 1217, 325, 85, 980, 836, 264, 204, 333, 1417, 89, 349, 1116, 1048,
+         104, 364, 252, 1336, 313, 253, 813, 937, 997, 676, 1332, 245, 1393, 1328, 1184, 161, 162,
+         35, 307, 1394, 246, 311, 871, 998, 106, 366, 254, 1338, 315, 255, 815, 939, 475, 1119, 798,
+         922, 586, 782, 207, 1291, 647, 983, 918, 774, 706, 914, 147, 1155, 320, 80, 148, 1156, 5,
+         149, 913, 769, 194, 259, 7, 1478, 150, 23, 1043, 1106, 162, 35, 307, 1394, 246, 311, 871,
+         998, 933, 737, 1393, 309, 1332, 1392, 1248, 100, 104, 364, 252, 1336, 313, 253, 813, 937,
+         170, 43, 315, 1402, 254, 319, 879, 1006, 414, 1055, 859, 986, 522, 843, 271, 1358, 717,
+         925, 985, 841, 648, 984, 92, 1228, 128, 1, 273, 1360, 212, 277, 837, 964, 72, 332, 220,
+         1304, 281, 221, 781, 905, 266, 206, 335, 1419, 91, 351, 1118, 1050, 854, 518, 1415, 343,
+         1235, 1411, 1474, 18, 34, 178, 115, 1251, 359, 119, 1014, 870, 298, 238, 367, 1451, 123,
+         383, 1150, 1082, 633, 829, 1133, 1065, 488, 1132, 380, 1464, 564, 885, 1061, 1124, 416,
+         1057, 49, 1520, 892, 556, 1453, 381, 1273, 1449, 1512, 56, 1076, 432, 1265, 117, 1445,
+         1249, 1376, 292, 1428, 1232, 384, 452, 1029, 385, 721, 597, 1497, 1176, 456, 393, 1101,
+         460, 668, 541, 542, 863, 1039, 1102, 394, 1035, 27, 1498, 1430, 1234, 386, 454, 1031, 387,
+         723, 599, 1319, 1379, 738, 614, 950, 754, 435, 503, 1211, 1515, 618, 762, 830, 622, 559,
+         703, 819, 611, 1511, 55, 1206, 1510, 1442, 370, 1137, 496, 1204, 181, 1509, 1188, 1312,
+         225, 1489, 1168, 448, 385, 1093, 452, 660, 533, 1430, 1234, 386, 454, 1031, 387, 723, 599,
+         603, 799, 1103, 1035, 458, 1102, 350, 1434, 1497, 1176, 456, 393, 1101, 460, 668, 541,
+         1389, 1324, 680, 553, 1017, 696, 508, 445, 1278, 1454, 554, 698, 891, 555, 623, 767, 1084,
+         440, 1273, 125, 1453, 1257, 1384, 300, 860, 524, 1421, 349, 1241, 1417, 1480, 24, 1236,
+         1412, 512, 656, 849, 513, 581, 725, 1381, 1316, 672, 545, 1009, 688, 500, 437, 438, 1079,
+         883, 1010, 546, 867, 295, 1382, 1238, 1414, 514, 658, 851, 515, 583, 727, 1179, 1483, 586,
+         730, 798, 590, 527, 671, 1327, 1387, 746, 622, 958, 762, 443, 511, 1139, 498, 1206, 183,
+         1511, 1190, 1314, 227, 787, 579, 1479, 23, 1174, 1478, 1410, 338, 1169, 1473, 576, 720,
+         788, 580, 517, 661, 1317, 1377, 736, 612, 948, 752, 433, 501, 505, 1149, 828, 952, 616,
+         812, 237, 1321, 1177, 1481, 584, 728, 796, 588, 525, 669, 1246, 1422, 522, 666, 859, 523,
+         591, 735, 1391, 1326, 682, 555, 1019, 698, 510, 447, 911, 715, 1371, 287, 1310, 1370, 1226,
+         78, 973, 652, 1308, 221, 1369, 1304, 1160, 137, 1349, 1284, 640, 513, 977, 656, 468, 405,
+         1238, 1414, 514, 658, 851, 515, 583, 727, 743, 951, 1011, 867, 674, 1010, 118, 1254, 1381,
+         1316, 672, 545, 1009, 688, 500, 437, 1529, 1208, 488, 425, 1133, 492, 700, 573, 1470, 1274,
+         426, 494, 1071, 427, 763, 639, 975, 654, 1310, 223, 1371, 1306, 1162, 139, 903, 707, 1363,
+         279, 1302, 1362, 1218, 70, 1285, 1345, 704, 580, 916, 720, 401, 469, 1177, 1481, 584, 728,
+         796, 588, 525, 669, 685, 1021, 956, 812, 744, 952, 185, 1193, 1317, 1377, 736, 612, 948,
+         752, 433, 501, 1462, 1266, 418, 486, 1063, 419, 755, 631, 1531, 1210, 490, 427, 1135, 494,
+         702, 575, 1523, 1202, 482, 419, 1127, 486, 694, 567, 635, 831, 1135, 1067, 490, 1134, 382,
+         1466, 505, 1149, 828, 952, 616, 812, 237, 1321, 677, 1013, 948, 804, 736, 944, 177, 1185,
+         1169, 1473, 576, 720, 788, 580, 517, 661, 473, 1117, 796, 920, 584, 780, 205, 1289, 74,
+         334, 222, 1306, 283, 223, 783, 907, 967, 646, 1302, 215, 1363, 1298, 1154, 131, 1468, 1272,
+         424, 492, 1069, 425, 761, 637, 574, 895, 1071, 1134, 426, 1067, 59, 1530, 438, 1079, 883,
+         1010, 546, 867, 295, 1382, 741, 949, 1009, 865, 672, 1008, 116, 1252, 1236, 1412, 512, 656,
+         849, 513, 581, 725, 406, 1047, 851, 978, 514, 835, 263, 1350, 138, 11, 283, 1370, 222, 287,
+         847, 974, 909, 713, 1369, 285, 1308, 1368, 1224, 76, 1359, 1294, 650, 523, 987, 666, 478,
+         415, 751, 959, 1019, 875, 682, 1018, 126, 1262, 685, 1021, 956, 812, 744, 952, 185, 1193,
+         473, 1117, 796, 920, 584, 780, 205, 1289, 1285, 1345, 704, 580, 916, 720, 401, 469, 677,
+         1013, 948, 804, 736, 944, 177, 1185, 354, 114, 182, 1190, 39, 183, 947, 803, 1107, 466,
+         1174, 151, 1479, 1158, 1282, 195, 1295, 1355, 714, 590, 926, 730, 411, 479, 687, 1023, 958,
+         814, 746, 954, 187, 1195, 743, 951, 1011, 867, 674, 1010, 118, 1254, 406, 1047, 851, 978,
+         514, 835, 263, 1350, 1349, 1284, 640, 513, 977, 656, 468, 405, 741, 949, 1009, 865, 672,
+         1008, 116, 1252, 40, 184, 121, 1257, 365, 125, 1020, 876, 1052, 408, 1241, 93, 1421, 1225,
+         1352, 268, 1276, 1452, 552, 696, 889, 553, 621, 765, 446, 1087, 891, 1018, 554, 875, 303,
+         1390, 542, 863, 1039, 1102, 394, 1035, 27, 1498, 601, 797, 1101, 1033, 456, 1100, 348,
+         1432, 1428, 1232, 384, 452, 1029, 385, 721, 597, 534, 855, 1031, 1094, 386, 1027, 19, 1490,
+         226, 291, 39, 1510, 182, 55, 1075, 1138, 817, 609, 1509, 53, 1204, 1508, 1440, 368, 1203,
+         1507, 610, 754, 822, 614, 551, 695, 507, 1151, 830, 954, 618, 814, 239, 1323, 603, 799,
+         1103, 1035, 458, 1102, 350, 1434, 534, 855, 1031, 1094, 386, 1027, 19, 1490, 1489, 1168,
+         448, 385, 1093, 452, 660, 533, 601, 797, 1101, 1033, 456, 1100, 348, 1432, 296, 236, 365,
+         1449, 121, 381, 1148, 1080, 884, 548, 1445, 373, 1265, 1441, 1504, 48, 627, 823, 1127,
+         1059, 482, 1126, 374, 1458, 1521, 1200, 480, 417, 1125, 484, 692, 565, 1145, 504, 1212,
+         189, 1517, 1196, 1320, 233, 362, 122, 190, 1198, 47, 191, 955, 811, 795, 587, 1487, 31,
+         1182, 1486, 1418, 346, 1113, 472, 1180, 157, 1485, 1164, 1288, 201, 965, 644, 1300, 213,
+         1361, 1296, 1152, 129, 66, 326, 214, 1298, 275, 215, 775, 899, 572, 893, 1069, 1132, 424,
+         1065, 57, 1528, 1460, 1264, 416, 484, 1061, 417, 753, 629, 1078, 434, 1267, 119, 1447,
+         1251, 1378, 294, 42, 186, 123, 1259, 367, 127, 1022, 878, 862, 526, 1423, 351, 1243, 1419,
+         1482, 26, 1046, 402, 1235, 87, 1415, 1219, 1346, 262, 901, 705, 1361, 277, 1300, 1360,
+         1216, 68, 136, 9, 281, 1368, 220, 285, 845, 972, 719, 927, 987, 843, 650, 986, 94, 1230,
+         1357, 1292, 648, 521, 985, 664, 476, 413, 1005, 684, 1340, 253, 1401, 1336, 1192, 169, 106,
+         366, 254, 1338, 315, 255, 815, 939, 935, 739, 1395, 311, 1334, 1394, 1250, 102, 997, 676,
+         1332, 245, 1393, 1328, 1184, 161, 1105, 464, 1172, 149, 1477, 1156, 1280, 193, 322, 82,
+         150, 1158, 7, 151, 915, 771, 655, 991, 926, 782, 714, 922, 155, 1163, 1287, 1347, 706, 582,
+         918, 722, 403, 471, 935, 739, 1395, 311, 1334, 1394, 1250, 102, 170, 43, 315, 1402, 254,
+         319, 879, 1006, 1005, 684, 1340, 253, 1401, 1336, 1192, 169, 933, 737, 1393, 309, 1332,
+         1392, 1248, 100, 1044, 400, 1233, 85, 1413, 1217, 1344, 260, 8, 152, 89, 1225, 333, 93,
+         988, 844, 444, 1085, 889, 1016, 552, 873, 301, 1388, 1244, 1420, 520, 664, 857, 521, 589,
+         733, 862, 526, 1423, 351, 1243, 1419, 1482, 26, 298, 238, 367, 1451, 123, 383, 1150, 1082,
+         1078, 434, 1267, 119, 1447, 1251, 1378, 294, 854, 518, 1415, 343, 1235, 1411, 1474, 18,
+         785, 577, 1477, 21, 1172, 1476, 1408, 336, 224, 289, 37, 1508, 180, 53, 1073, 1136, 499,
+         1143, 822, 946, 610, 806, 231, 1315, 1171, 1475, 578, 722, 790, 582, 519, 663, 795, 587,
+         1487, 31, 1182, 1486, 1418, 346, 234, 299, 47, 1518, 190, 63, 1083, 1146, 1145, 504, 1212,
+         189, 1517, 1196, 1320, 233, 793, 585, 1485, 29, 1180, 1484, 1416, 344, 852, 516, 1413, 341,
+         1233, 1409, 1472, 16, 288, 228, 357, 1441, 113, 373, 1140, 1072];
+
+    /// Initial state of the 3D state machine as a function of the number of
+    /// padding levels: the padding state maps the zero coordinates to the
+    /// zero index and to the initial state.
+    const INITIAL_3D: [u16; 2] = [0, 1];
+
+    #[inline]
+    pub fn encode_2d<HilbertIndex: Word, Coordinate: Word>(x: Coordinate,
+                                                           y: Coordinate)
+                                                           -> HilbertIndex {
+        let levels = HilbertIndex::bit_size().to_u32() / 2;
+        debug_assert!(levels % 4 == 0);
+        let mask = low_bits::<HilbertIndex>(levels);
+        let (x, y): (HilbertIndex, HilbertIndex) = (x.to(), y.to());
+        let (x, y) = (x & mask, y & mask);
+        let nibble = HilbertIndex::from_u8(0xF);
+        let mut v = HilbertIndex::zero();
+        let mut state = 0u16;
+        let mut shift = levels;
+        while shift > 0 {
+            shift -= 4;
+            let s = HilbertIndex::from_u32(shift);
+            let key = (state << 8) | ((x >> s) & nibble).to_u16() << 4 |
+                      ((y >> s) & nibble).to_u16();
+            let entry = ENCODE_2D[key as usize];
+            v = v | HilbertIndex::from_u16(entry & 0xFF) << HilbertIndex::from_u32(2 * shift);
+            state = entry >> 8;
+        }
+        v
+    }
+
+    #[inline]
+    pub fn decode_2d<Coordinate: Word, HilbertIndex: Word>(v: HilbertIndex)
+                                                           -> (Coordinate, Coordinate) {
+        let levels = HilbertIndex::bit_size().to_u32() / 2;
+        debug_assert!(levels % 4 == 0);
+        let byte = HilbertIndex::from_u8(0xFF);
+        let (mut x, mut y) = (HilbertIndex::zero(), HilbertIndex::zero());
+        let mut state = 0u16;
+        let mut shift = levels;
+        while shift > 0 {
+            shift -= 4;
+            let s = HilbertIndex::from_u32(shift);
+            let key = (state << 8) | ((v >> (s + s)) & byte).to_u16();
+            let entry = DECODE_2D[key as usize];
+            x = x | HilbertIndex::from_u16((entry >> 4) & 0xF) << s;
+            y = y | HilbertIndex::from_u16(entry & 0xF) << s;
+            state = entry >> 8;
+        }
+        (x.to(), y.to())
+    }
+
+    #[inline]
+    pub fn encode_3d<HilbertIndex: Word, Coordinate: Word>(x: Coordinate,
+                                                           y: Coordinate,
+                                                           z: Coordinate)
+                                                           -> HilbertIndex {
+        let levels = HilbertIndex::bit_size().to_u32() / 3;
+        let mask = low_bits::<HilbertIndex>(levels);
+        let (x, y, z): (HilbertIndex, HilbertIndex, HilbertIndex) = (x.to(), y.to(), z.to());
+        let (x, y, z) = (x & mask, y & mask, z & mask);
+        let pair = HilbertIndex::from_u8(0x3);
+        let mut v = HilbertIndex::zero();
+        let mut state = INITIAL_3D[(levels % 2) as usize];
+        let mut shift = levels + levels % 2;
+        while shift > 0 {
+            shift -= 2;
+            let s = HilbertIndex::from_u32(shift);
+            let key = (state << 6) | ((x >> s) & pair).to_u16() << 4 |
+                      ((y >> s) & pair).to_u16() << 2 |
+                      ((z >> s) & pair).to_u16();
+            let entry = ENCODE_3D[key as usize];
+            v = v | HilbertIndex::from_u16(entry & 0x3F) << HilbertIndex::from_u32(3 * shift);
+            state = entry >> 6;
+        }
+        v & low_bits(3 * levels)
+    }
+
+    #[inline]
+    pub fn decode_3d<Coordinate: Word, HilbertIndex: Word>
+        (v: HilbertIndex)
+         -> (Coordinate, Coordinate, Coordinate) {
+        let levels = HilbertIndex::bit_size().to_u32() / 3;
+        let v = v & low_bits(3 * levels);
+        let six_bits = HilbertIndex::from_u8(0x3F);
+        let (mut x, mut y, mut z) =
+            (HilbertIndex::zero(), HilbertIndex::zero(), HilbertIndex::zero());
+        let mut state = INITIAL_3D[(levels % 2) as usize];
+        let mut shift = levels + levels % 2;
+        while shift > 0 {
+            shift -= 2;
+            let s = HilbertIndex::from_u32(shift);
+            let key = (state << 6) | ((v >> (s + s + s)) & six_bits).to_u16();
+            let entry = DECODE_3D[key as usize];
+            x = x | HilbertIndex::from_u16((entry >> 4) & 0x3) << s;
+            y = y | HilbertIndex::from_u16((entry >> 2) & 0x3) << s;
+            z = z | HilbertIndex::from_u16(entry & 0x3) << s;
+            state = entry >> 6;
+        }
+        (x.to(), y.to(), z.to())
+    }
+}
+
+pub mod skilling {
+    //! Encoding/decoding of Hilbert curve indices using Skilling's transform.
+    //!
+    //! The coordinates are transformed in place into the "transposed" Hilbert
+    //! index, whose bits are then interleaved into the index with a Morton
+    //! encoding. The transform operates on whole words, processing one level
+    //! of the curve per iteration. See J. Skilling, "Programming the Hilbert
+    //! curve", AIP Conference Proceedings 707, 381 (2004).
+
+    use word::{Word, ToWord};
+    use word::morton;
+    use super::low_bits;
+
+    /// Transforms the coordinates `x` into the transposed Hilbert index.
+    #[inline]
+    fn axes_to_transpose<T: Word>(x: &mut [T], bits: u32) {
+        let n = x.len();
+        // Inverse undo:
+        let mut q = T::one() << T::from_u32(bits - 1);
+        while q > T::one() {
+            let p = q - T::one();
+            for i in 0..n {
+                if x[i] & q != T::zero() {
+                    x[0] = x[0] ^ p;
+                } else {
+                    let t = (x[0] ^ x[i]) & p;
+                    x[0] = x[0] ^ t;
+                    x[i] = x[i] ^ t;
+                }
+            }
+            q = q >> T::one();
+        }
+        // Gray encode:
+        for i in 1..n {
+            x[i] = x[i] ^ x[i - 1];
+        }
+        let mut t = T::zero();
+        let mut q = T::one() << T::from_u32(bits - 1);
+        while q > T::one() {
+            if x[n - 1] & q != T::zero() {
+                t = t ^ (q - T::one());
+            }
+            q = q >> T::one();
+        }
+        for xi in x.iter_mut() {
+            *xi = *xi ^ t;
+        }
+    }
+
+    /// Transforms the transposed Hilbert index `x` into the coordinates.
+    #[inline]
+    fn transpose_to_axes<T: Word>(x: &mut [T], bits: u32) {
+        let n = x.len();
+        // Gray decode:
+        let t = x[n - 1] >> T::one();
+        for i in (1..n).rev() {
+            x[i] = x[i] ^ x[i - 1];
+        }
+        x[0] = x[0] ^ t;
+        // Undo excess work:
+        let mut q = T::from_u8(2);
+        for _ in 1..bits {
+            let p = q - T::one();
+            for i in (0..n).rev() {
+                if x[i] & q != T::zero() {
+                    x[0] = x[0] ^ p;
+                } else {
+                    let t = (x[0] ^ x[i]) & p;
+                    x[0] = x[0] ^ t;
+                    x[i] = x[i] ^ t;
+                }
+            }
+            q = q << T::one();
+        }
+    }
+
+    #[inline]
+    pub fn encode_2d<HilbertIndex: Word, Coordinate: Word>(x: Coordinate,
+                                                           y: Coordinate)
+                                                           -> HilbertIndex {
+        let bits = HilbertIndex::bit_size().to_u32() / 2;
+        let mask = low_bits::<HilbertIndex>(bits);
+        let mut t: [HilbertIndex; 2] = [x.to(), y.to()];
+        t[0] = t[0] & mask;
+        t[1] = t[1] & mask;
+        axes_to_transpose(&mut t, bits);
+        morton::encode_2d(t[1], t[0])
+    }
+
+    #[inline]
+    pub fn decode_2d<Coordinate: Word, HilbertIndex: Word>(v: HilbertIndex)
+                                                           -> (Coordinate, Coordinate) {
+        let bits = HilbertIndex::bit_size().to_u32() / 2;
+        let (t1, t0) = morton::decode_2d(v);
+        let mut t = [t0, t1];
+        transpose_to_axes(&mut t, bits);
+        (t[0].to(), t[1].to())
+    }
+
+    #[inline]
+    pub fn encode_3d<HilbertIndex: Word, Coordinate: Word>(x: Coordinate,
+                                                           y: Coordinate,
+                                                           z: Coordinate)
+                                                           -> HilbertIndex {
+        let bits = HilbertIndex::bit_size().to_u32() / 3;
+        let mask = low_bits::<HilbertIndex>(bits);
+        let mut t: [HilbertIndex; 3] = [x.to(), y.to(), z.to()];
+        for ti in t.iter_mut() {
+            *ti = *ti & mask;
+        }
+        axes_to_transpose(&mut t, bits);
+        morton::encode_3d(t[2], t[1], t[0])
+    }
+
+    #[inline]
+    pub fn decode_3d<Coordinate: Word, HilbertIndex: Word>
+        (v: HilbertIndex)
+         -> (Coordinate, Coordinate, Coordinate) {
+        let bits = HilbertIndex::bit_size().to_u32() / 3;
+        let (t2, t1, t0) = morton::decode_3d(v & low_bits(3 * bits));
+        let mut t = [t0, t1, t2];
+        transpose_to_axes(&mut t, bits);
+        (t[0].to(), t[1].to(), t[2].to())
+    }
+}
+
+/// Encode coordinates `x` and `y` into a Hilbert curve index.
+///
+/// The lower `T::bit_size() / 2` bits of the coordinates are encoded, the
+/// higher bits are ignored.
+///
+/// # Example
+/// ```
+/// use bitwise::word::hilbert;
+///
+/// assert_eq!(hilbert::encode_2d(0u8, 0u8), 0);
+/// assert_eq!(hilbert::encode_2d(1u8, 0u8), 1);
+/// assert_eq!(hilbert::encode_2d(1u8, 1u8), 2);
+/// assert_eq!(hilbert::encode_2d(0u8, 1u8), 3);
+/// assert_eq!(hilbert::encode_2d(5u8, 10u8), 119);
+/// assert_eq!(hilbert::encode_2d(15u8, 0u8), 255);
+/// ```
+#[inline]
+pub fn encode_2d<T: Word>(x: T, y: T) -> T {
+    lut::encode_2d(x, y)
+}
+
+/// Decodes a Hilbert curve index into two-dimensional coordinates.
+///
+/// # Example
+/// ```
+/// use bitwise::word::hilbert;
+///
+/// assert_eq!(hilbert::decode_2d(2u8), (1, 1));
+/// assert_eq!(hilbert::decode_2d(119u8), (5, 10));
+/// assert_eq!(hilbert::decode_2d(u32::MAX), (u16::MAX as u32, 0));
+/// ```
+#[inline]
+pub fn decode_2d<T: Word>(v: T) -> (T, T) {
+    lut::decode_2d(v)
+}
+
+/// Encode coordinates `x`, `y`, and `z` into a Hilbert curve index.
+///
+/// The lower `T::bit_size() / 3` bits of the coordinates are encoded, the
+/// higher bits are ignored.
+///
+/// # Example
+/// ```
+/// use bitwise::word::hilbert;
+///
+/// assert_eq!(hilbert::encode_3d(0u32, 0u32, 0u32), 0);
+/// assert_eq!(hilbert::encode_3d(0u32, 0u32, 1u32), 1);
+/// assert_eq!(hilbert::encode_3d(3u32, 5u32, 7u32), 391);
+/// assert_eq!(hilbert::encode_3d(1023u32, 0u32, 0u32), (1 << 30) - 1);
+/// ```
+#[inline]
+pub fn encode_3d<T: Word>(x: T, y: T, z: T) -> T {
+    lut::encode_3d(x, y, z)
+}
+
+/// Decodes a Hilbert curve index into three-dimensional coordinates.
+///
+/// The `T::bit_size() % 3` highest bits of `v` are ignored.
+///
+/// # Example
+/// ```
+/// use bitwise::word::hilbert;
+///
+/// assert_eq!(hilbert::decode_3d(391u32), (3, 5, 7));
+/// assert_eq!(hilbert::decode_3d(u32::MAX), (1023, 0, 0));
+/// ```
+#[inline]
+pub fn decode_3d<T: Word>(v: T) -> (T, T, T) {
+    lut::decode_3d(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use word::morton::testing_utils::*;
+    use std::fmt::Debug;
+
+    /// Index `v` restricted to the bits used in 3D.
+    fn index_3d<T: Word>(v: T) -> T {
+        v & low_bits(T::bit_size().to_u32() / 3 * 3)
+    }
+
+    fn distance<T: Word>(a: T, b: T) -> T {
+        if a > b { a - b } else { b - a }
+    }
+
+    struct Invariant;
+
+    impl RunnerFn for Invariant {
+        fn run<T: Word + Debug>(&self, v: T) {
+            {
+                // 2D:
+                let (x, y) = lut::decode_2d::<T, _>(v);
+                assert_eq!(lut::encode_2d::<T, _>(x, y), v);
+                assert_eq!(skilling::decode_2d::<T, _>(v), (x, y));
+                assert_eq!(skilling::encode_2d::<T, _>(x, y), v);
+                assert_eq!(decode_2d(v), (x, y));
+                assert_eq!(encode_2d(x, y), v);
+            }
+            {
+                // 3D:
+                let (x, y, z) = lut::decode_3d::<T, _>(v);
+                let w = index_3d(v);
+                assert_eq!(lut::encode_3d::<T, _>(x, y, z), w);
+                assert_eq!(skilling::decode_3d::<T, _>(v), (x, y, z));
+                assert_eq!(skilling::encode_3d::<T, _>(x, y, z), w);
+                assert_eq!(decode_3d(v), (x, y, z));
+                assert_eq!(encode_3d(x, y, z), w);
+            }
+        }
+    }
+
+    #[test]
+    fn invariant() {
+        Runner::run(Invariant {});
+    }
+
+    struct Adjacency;
+
+    impl RunnerFn for Adjacency {
+        fn run<T: Word + Debug>(&self, v: T) {
+            if v == !T::zero() {
+                return;
+            }
+            let n = v + T::one();
+            {
+                let (x0, y0) = decode_2d(v);
+                let (x1, y1) = decode_2d(n);
+                assert_eq!(distance(x0, x1) + distance(y0, y1), T::one());
+            }
+            if index_3d(n) == n {
+                let (x0, y0, z0) = decode_3d(v);
+                let (x1, y1, z1) = decode_3d(n);
+                assert_eq!(distance(x0, x1) + distance(y0, y1) + distance(z0, z1),
+                           T::one());
+            }
+        }
+    }
+
+    #[test]
+    fn adjacency() {
+        Runner::run(Adjacency {});
+    }
+
+    #[test]
+    fn mixed_widths() {
+        let (x, y) = (0xFEDC_BA98_7654_3210u64, 0x0123_4567_89AB_CDEFu64);
+        let v: u128 = lut::encode_2d(x, y);
+        assert_eq!(v, skilling::encode_2d(x, y));
+        assert_eq!(v, encode_2d(x as u128, y as u128));
+        assert_eq!(lut::decode_2d::<u64, _>(v), (x, y));
+        assert_eq!(skilling::decode_2d::<u64, _>(v), (x, y));
+
+        let (x, y, z) = (0x3FF_0F0F_0F0Fu64, 0x2AA_AAAA_AAAAu64, 0x155_5555_5555u64);
+        let v: u128 = lut::encode_3d(x, y, z);
+        assert_eq!(v, skilling::encode_3d(x, y, z));
+        assert_eq!(v, encode_3d(x as u128, y as u128, z as u128));
+        assert_eq!(lut::decode_3d::<u64, _>(v), (x, y, z));
+        assert_eq!(skilling::decode_3d::<u64, _>(v), (x, y, z));
+    }
+}
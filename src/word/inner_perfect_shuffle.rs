@@ -26,7 +26,6 @@ pub fn inner_perfect_shuffle<T: Word>(x: T) -> T {
 
 /// Method version of [`inner_perfect_shuffle`](fn.inner_perfect_shuffle.html).
 pub trait InnerPerfectShuffle {
-    #[inline]
     fn inner_perfect_shuffle(self) -> Self;
 }
 
@@ -26,7 +26,6 @@ pub fn inner_perfect_unshuffle<T: Word>(x: T) -> T {
 
 /// Method version of [`inner_perfect_unshuffle`](fn.inner_perfect_unshuffle.html).
 pub trait InnerPerfectUnshuffle {
-    #[inline]
     fn inner_perfect_unshuffle(self) -> Self;
 }
 
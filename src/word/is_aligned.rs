@@ -37,8 +37,8 @@ pub fn is_aligned<T: Word, U: UnsignedWord>(x: T, alignment: U) -> bool {
 
 /// Method version of [`is_aligned`](fn.is_aligned.html).
 pub trait IsAligned {
-    #[inline]
-    fn is_aligned<U: UnsignedWord>(self, U) -> bool;
+    #[allow(clippy::wrong_self_convention)]
+    fn is_aligned<U: UnsignedWord>(self, alignment: U) -> bool;
 }
 
 impl<T: Word> IsAligned for T {
@@ -85,5 +85,6 @@ mod tests {
         prop_is_aligned_u16_u16: (u16, u16),
         prop_is_aligned_u32_u32: (u32, u32),
         prop_is_aligned_u64_u64: (u64, u64),
+        prop_is_aligned_usize_usize: (usize, usize),
     }
 }
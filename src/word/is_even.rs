@@ -20,7 +20,7 @@ pub fn is_even<T: Word>(x: T) -> bool {
 
 /// Method version of [`is_even`](fn.is_even.html).
 pub trait IsEven {
-    #[inline]
+    #[allow(clippy::wrong_self_convention)]
     fn is_even(self) -> bool;
 }
 
@@ -61,5 +61,7 @@ mod tests {
         prop_is_even_i32: i32,
         prop_is_even_u64: u64,
         prop_is_even_i64: i64,
+        prop_is_even_usize: usize,
+        prop_is_even_isize: isize,
     }
 }
@@ -20,7 +20,7 @@ pub fn is_odd<T: Word>(x: T) -> bool {
 
 /// Method version of [`is_odd`](fn.is_odd.html).
 pub trait IsOdd {
-    #[inline]
+    #[allow(clippy::wrong_self_convention)]
     fn is_odd(self) -> bool;
 }
 
@@ -61,5 +61,7 @@ mod tests {
         prop_is_odd_i32: i32,
         prop_is_odd_u64: u64,
         prop_is_odd_i64: i64,
+        prop_is_odd_usize: usize,
+        prop_is_odd_isize: isize,
     }
 }
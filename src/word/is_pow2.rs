@@ -23,7 +23,7 @@ pub fn is_pow2<T: Word>(x: T) -> bool {
 
 /// Method version of [`is_pow2`](fn.is_pow2.html).
 pub trait IsPow2 {
-    #[inline]
+    #[allow(clippy::wrong_self_convention)]
     fn is_pow2(self) -> bool;
 }
 
@@ -75,5 +75,7 @@ mod tests {
         prop_is_pow2_i32: i32,
         prop_is_pow2_u64: u64,
         prop_is_pow2_i64: i64,
+        prop_is_pow2_usize: usize,
+        prop_is_pow2_isize: isize,
     }
 }
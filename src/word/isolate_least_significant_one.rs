@@ -26,7 +26,6 @@ pub fn isolate_least_significant_one<T: Word>(x: T) -> T {
 
 /// Method version of [`isolate_least_significant_one`](fn.isolate_least_significant_one.html).
 pub trait IsolateLeastSignificantOne {
-    #[inline]
     fn isolate_least_significant_one(self) -> Self;
 }
 
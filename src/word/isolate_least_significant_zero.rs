@@ -26,7 +26,6 @@ pub fn isolate_least_significant_zero<T: Word>(x: T) -> T {
 
 /// Method version of [`isolate_least_significant_zero`](fn.isolate_least_significant_zero.html).
 pub trait IsolateLeastSignificantZero {
-    #[inline]
     fn isolate_least_significant_zero(self) -> Self;
 }
 
@@ -0,0 +1,278 @@
+//! LEB128 variable-length encoding of integers.
+//!
+//! An integer is split into groups of 7 bits, from the least significant
+//! one, and each group is stored in a byte whose most significant bit is set
+//! if more bytes follow. Unsigned integers are encoded until the remaining
+//! bits are zero (unsigned LEB128, the varints of Protocol Buffers), and
+//! signed integers until the remaining bits are copies of the sign bit
+//! (signed LEB128, as in DWARF and WebAssembly).
+//!
+//! Protocol Buffers `sint` varints are the unsigned LEB128 encoding of the
+//! [`zigzag_encode`](../fn.zigzag_encode.html)d integer.
+//!
+//! Only the shortest encoding of each integer is accepted: decoding rejects
+//! overlong encodings, which have redundant trailing groups, and encodings
+//! of integers that do not fit in the target type.
+//!
+//! # Examples
+//!
+//! ```
+//! use bitwise::word::leb128::{self, Error};
+//!
+//! let mut bytes = [0; 10];
+//! assert_eq!(leb128::encode(624_485u32, &mut bytes), Ok(3));
+//! assert_eq!(bytes[..3], [0xE5, 0x8E, 0x26]);
+//! assert_eq!(leb128::decode::<u32>(&bytes), Ok((624_485, 3)));
+//!
+//! assert_eq!(leb128::encode(-123_456i64, &mut bytes), Ok(3));
+//! assert_eq!(bytes[..3], [0xC0, 0xBB, 0x78]);
+//! assert_eq!(leb128::decode::<i64>(&bytes), Ok((-123_456, 3)));
+//!
+//! assert_eq!(leb128::decode::<u32>(&[0x80, 0x00]), Err(Error::Overlong));
+//! assert_eq!(leb128::decode::<u8>(&[0x80, 0x02]), Err(Error::Overflow));
+//! assert_eq!(leb128::decode::<u32>(&[0x80]), Err(Error::Truncated));
+//! assert_eq!(leb128::encode(300u16, &mut bytes[..1]), Err(Error::Truncated));
+//! ```
+
+use std::fmt;
+
+use word::Word;
+
+/// Error encoding or decoding an integer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ends before the end of the encoding.
+    Truncated,
+    /// The encoding is longer than the shortest encoding of the integer.
+    Overlong,
+    /// The encoded integer does not fit in the word type.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Error::Truncated => "the buffer ends before the end of the LEB128 encoding",
+            Error::Overlong => "the LEB128 encoding is overlong",
+            Error::Overflow => "the LEB128 encoded integer does not fit in the word type",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::libstd::error::Error for Error {}
+
+/// Is `T` a signed integer type?
+#[inline]
+fn is_signed<T: Word>() -> bool {
+    !T::zero() < T::zero()
+}
+
+/// Maximum length in bytes of the encoding of a `T`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::leb128;
+///
+/// assert_eq!(leb128::max_len::<u8>(), 2);
+/// assert_eq!(leb128::max_len::<i64>(), 10);
+/// assert_eq!(leb128::max_len::<u128>(), 19);
+/// ```
+#[inline]
+pub fn max_len<T: Word>() -> usize {
+    (T::bit_size().to_usize() + 6) / 7
+}
+
+/// Length in bytes of the encoding of `x`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::leb128;
+///
+/// assert_eq!(leb128::encoded_len(127u8), 1);
+/// assert_eq!(leb128::encoded_len(128u8), 2);
+/// assert_eq!(leb128::encoded_len(63i8), 1);
+/// assert_eq!(leb128::encoded_len(64i8), 2);
+/// assert_eq!(leb128::encoded_len(-64i8), 1);
+/// assert_eq!(leb128::encoded_len(-65i8), 2);
+/// ```
+#[inline]
+pub fn encoded_len<T: Word>(x: T) -> usize {
+    let bits = if !is_signed::<T>() {
+        T::bit_size() - x.leading_zeros()
+    } else if x < T::zero() {
+        T::bit_size() - (!x).leading_zeros() + T::one()
+    } else {
+        T::bit_size() - x.leading_zeros() + T::one()
+    };
+    ((bits.to_usize() + 6) / 7).max(1)
+}
+
+/// Encodes `x` into the beginning of `bytes`, returning the length of the
+/// encoding.
+///
+/// Unsigned integers use unsigned LEB128 and signed integers signed LEB128.
+/// If `bytes` is too short, returns `Error::Truncated` without modifying it.
+#[inline]
+pub fn encode<T: Word>(x: T, bytes: &mut [u8]) -> Result<usize, Error> {
+    let len = encoded_len(x);
+    if bytes.len() < len {
+        return Err(Error::Truncated);
+    }
+    let seven = T::from_u32(7);
+    let mut x = x;
+    for byte in &mut bytes[..len - 1] {
+        *byte = x.to_u8() | 0x80;
+        x = x >> seven;
+    }
+    bytes[len - 1] = x.to_u8() & 0x7F;
+    Ok(len)
+}
+
+/// Decodes an integer from the beginning of `bytes`, returning it and the
+/// length of its encoding.
+///
+/// Unsigned integers use unsigned LEB128 and signed integers signed LEB128.
+///
+/// # Errors
+///
+/// - `Error::Truncated` if `bytes` ends before the end of the encoding,
+/// - `Error::Overlong` if the encoding is not the shortest one,
+/// - `Error::Overflow` if the integer does not fit in a `T`.
+#[inline]
+pub fn decode<T: Word>(bytes: &[u8]) -> Result<(T, usize), Error> {
+    let signed = is_signed::<T>();
+    let last = bytes.iter().position(|&b| b & 0x80 == 0).ok_or(Error::Truncated)?;
+    let len = last + 1;
+    if last > 0 {
+        let (previous, byte) = (bytes[last - 1] & 0x7F, bytes[last]);
+        let redundant = if signed {
+            (byte == 0 && previous & 0x40 == 0) || (byte == 0x7F && previous & 0x40 != 0)
+        } else {
+            byte == 0
+        };
+        if redundant {
+            return Err(Error::Overlong);
+        }
+    }
+    if len > max_len::<T>() {
+        return Err(Error::Overflow);
+    }
+    // Bits of the last group that fit in a `T`:
+    let bits = T::bit_size().to_u32();
+    let fitting = bits - 7 * last as u32;
+    if fitting < 7 {
+        let group = bytes[last];
+        let fits = if signed {
+            let high = group >> (fitting - 1);
+            high == 0 || high == 0x7F >> (fitting - 1)
+        } else {
+            group >> fitting == 0
+        };
+        if !fits {
+            return Err(Error::Overflow);
+        }
+    }
+    let mut x = 0u128;
+    for (i, &byte) in bytes[..len].iter().enumerate() {
+        x |= ((byte & 0x7F) as u128) << (7 * i);
+    }
+    if signed && bytes[last] & 0x40 != 0 && 7 * len < 128 {
+        x |= !0 << (7 * len);
+    }
+    Ok((T::from_u128(x), len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    fn check<T: Word + fmt::Debug>(x: T) -> bool {
+        let mut bytes = [0xFF; 20];
+        let len = encode(x, &mut bytes).unwrap();
+        len == encoded_len(x) && len <= max_len::<T>() &&
+        decode::<T>(&bytes) == Ok((x, len)) &&
+        decode::<T>(&bytes[..len - 1]) == Err(Error::Truncated) &&
+        encode(x, &mut [0; 20][..len - 1]) == Err(Error::Truncated)
+    }
+
+    /// Signed LEB128 encoding of `x`.
+    fn naive(x: i128, bytes: &mut [u8]) -> usize {
+        let mut x = x;
+        let mut len = 0;
+        loop {
+            let byte = (x & 0x7F) as u8;
+            x >>= 7;
+            let done = (x == 0 && byte & 0x40 == 0) || (x == -1 && byte & 0x40 != 0);
+            bytes[len] = byte | if done { 0 } else { 0x80 };
+            len += 1;
+            if done {
+                return len;
+            }
+        }
+    }
+
+    #[test]
+    fn prop_leb128() {
+        fn inner(x: u64, y: u64, shift: u8) -> bool {
+            let x = x >> (shift % 64);
+            let z = (y as u128) << 64 | x as u128;
+            check(x) && check(x as i64) && check(x as u8) && check(x as i8) &&
+            check(x as u16) && check(x as i16) && check(x as u32) && check(x as i32) &&
+            check(x as usize) && check(x as isize) && check(z) && check(z as i128) &&
+            check(z >> (shift % 128)) && check((z as i128) >> (shift % 128))
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u8) -> bool);
+    }
+
+    #[test]
+    fn prop_decode_arbitrary() {
+        // Decoding accepts exactly the shortest encodings of the integers
+        // that fit:
+        fn inner(x: i64, shift: u8, extra: bool) -> bool {
+            let x = x >> (shift % 64);
+            let mut bytes = [0; 20];
+            let len = naive(x as i128, &mut bytes);
+            if extra {
+                // An overlong encoding of `x`:
+                bytes[len - 1] |= 0x80;
+                bytes[len] = if x < 0 { 0x7F } else { 0 };
+                return decode::<i64>(&bytes) == Err(Error::Overlong) &&
+                       decode::<i8>(&bytes) == Err(Error::Overlong);
+            }
+            let expected = if (-128..128).contains(&x) {
+                Ok((x as i8, len))
+            } else {
+                Err(Error::Overflow)
+            };
+            decode::<i8>(&bytes) == expected && decode::<i64>(&bytes) == Ok((x, len))
+        }
+        QuickCheck::new().quickcheck(inner as fn(i64, u8, bool) -> bool);
+    }
+
+    #[test]
+    fn extremes() {
+        assert!(check(u128::MAX) && check(i128::MIN) && check(i128::MAX));
+        let mut bytes = [0; 20];
+        assert_eq!(encode(u8::MAX, &mut bytes), Ok(2));
+        assert_eq!(bytes[..2], [0xFF, 0x01]);
+        assert_eq!(encode(i8::MIN, &mut bytes), Ok(2));
+        assert_eq!(bytes[..2], [0x80, 0x7F]);
+        assert_eq!(decode::<i8>(&[0x80, 0x7E]), Err(Error::Overflow));
+        assert_eq!(decode::<i8>(&[0xFF, 0x00]), Ok((127, 2)));
+        assert_eq!(decode::<i8>(&[0xFF, 0x01]), Err(Error::Overflow));
+        assert_eq!(decode::<i8>(&[0xFF, 0x7F]), Err(Error::Overlong));
+        assert_eq!(decode::<u8>(&[0xFF, 0x81, 0x00]), Err(Error::Overlong));
+        assert_eq!(decode::<u8>(&[0xFF, 0x81, 0x01]), Err(Error::Overflow));
+        assert_eq!(decode::<u64>(&[0xFF; 9]), Err(Error::Truncated));
+        let mut max = [0xFF; 10];
+        max[9] = 0x01;
+        assert_eq!(decode::<u64>(&max), Ok((u64::MAX, 10)));
+        max[9] = 0x02;
+        assert_eq!(decode::<u64>(&max), Err(Error::Overflow));
+        assert_eq!(decode::<u32>(&[0x00, 0x80]), Ok((0, 1)));
+    }
+}
@@ -22,7 +22,6 @@ pub fn mask_trailing_ones<T: Word>(x: T) -> T {
 
 /// Method version of [`mask_trailing_ones`](fn.mask_trailing_ones.html).
 pub trait MaskTrailingOnes {
-    #[inline]
     fn mask_trailing_ones(self) -> Self;
 }
 
@@ -24,7 +24,6 @@ pub fn mask_trailing_ones_and_least_significant_zero<T: Word>(x: T) -> T {
 
 /// Method version of [`mask_trailing_zeros_and_least_significant_zero`](fn.mask_trailing_zeros_and_least_significant_zero.html).
 pub trait MaskTrailingOnesAndLeastSignificantZero {
-    #[inline]
     fn mask_trailing_ones_and_least_significant_zero(self) -> Self;
 }
 
@@ -22,7 +22,6 @@ pub fn mask_trailing_zeros<T: Word>(x: T) -> T {
 
 /// Method version of [`mask_trailing_zeros`](fn.mask_trailing_zeros.html).
 pub trait MaskTrailingZeros {
-    #[inline]
     fn mask_trailing_zeros(self) -> Self;
 }
 
@@ -24,7 +24,6 @@ pub fn mask_trailing_zeros_and_least_significant_one<T: Word>(x: T) -> T {
 
 /// Method version of [`mask_trailing_zeros_and_least_significant_one`](fn.mask_trailing_zeros_and_least_significant_one.html).
 pub trait MaskTrailingZerosAndLeastSignificantOne {
-    #[inline]
     fn mask_trailing_zeros_and_least_significant_one(self) -> Self;
 }
 
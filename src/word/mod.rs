@@ -1,5 +1,6 @@
-//! Algorithms for single words (u8...u64).
+//! Algorithms for single words (u8...u128, usize).
 
+#[allow(clippy::module_inception)]
 mod word;
 pub use self::word::*;
 
@@ -63,6 +64,36 @@ pub use self::pow::*;
 mod parity;
 pub use self::parity::*;
 
+mod prefix_xor;
+pub use self::prefix_xor::*;
+
+mod prefix_or;
+pub use self::prefix_or::*;
+
+mod prefix_and;
+pub use self::prefix_and::*;
+
+mod suffix_xor;
+pub use self::suffix_xor::*;
+
+mod suffix_or;
+pub use self::suffix_or::*;
+
+mod suffix_and;
+pub use self::suffix_and::*;
+
+mod to_gray;
+pub use self::to_gray::*;
+
+mod from_gray;
+pub use self::from_gray::*;
+
+mod gray_increment;
+pub use self::gray_increment::*;
+
+mod gray_changed_bit;
+pub use self::gray_changed_bit::*;
+
 mod clear_least_significant_one;
 pub use self::clear_least_significant_one::*;
 
@@ -126,6 +157,12 @@ pub use self::reverse_byte_groups::*;
 mod reverse_bytes;
 pub use self::reverse_bytes::*;
 
+mod transpose;
+pub use self::transpose::*;
+
+mod flip_8x8;
+pub use self::flip_8x8::*;
+
 mod clear_bits_geq;
 pub use self::clear_bits_geq::*;
 
@@ -168,6 +205,12 @@ pub use self::outer_perfect_shuffle::*;
 mod outer_perfect_unshuffle;
 pub use self::outer_perfect_unshuffle::*;
 
+mod delta_swap;
+pub use self::delta_swap::*;
+
+mod bit_permutation;
+pub use self::bit_permutation::*;
+
 mod inner_perfect_shuffle;
 pub use self::inner_perfect_shuffle::*;
 
@@ -180,6 +223,24 @@ pub use self::parallel_bits_deposit::*;
 mod parallel_bits_extract;
 pub use self::parallel_bits_extract::*;
 
+mod compress_left;
+pub use self::compress_left::*;
+
+mod expand_left;
+pub use self::expand_left::*;
+
+mod compress_mask;
+pub use self::compress_mask::*;
+
+mod sheep_and_goats;
+pub use self::sheep_and_goats::*;
+
+mod clmul;
+pub use self::clmul::*;
+
+mod select_in_word;
+pub use self::select_in_word::*;
+
 mod extract_bits;
 pub use self::extract_bits::*;
 
@@ -192,10 +253,32 @@ pub use self::is_even::*;
 mod is_odd;
 pub use self::is_odd::*;
 
+mod zigzag_encode;
+pub use self::zigzag_encode::*;
+
+mod zigzag_decode;
+pub use self::zigzag_decode::*;
+
 pub mod gcd;
 
 pub mod morton;
 pub use morton::decode_2d as morton_decode_2d;
 pub use morton::decode_3d as morton_decode_3d;
+pub use morton::decode_nd as morton_decode_nd;
 pub use morton::encode_2d as morton_encode_2d;
 pub use morton::encode_3d as morton_encode_3d;
+pub use morton::encode_nd as morton_encode_nd;
+
+pub mod leb128;
+pub use leb128::decode as leb128_decode;
+pub use leb128::encode as leb128_encode;
+
+pub mod galois;
+pub use galois::inverse as gf_inverse;
+pub use galois::mul as gf_mul;
+
+pub mod hilbert;
+pub use hilbert::decode_2d as hilbert_decode_2d;
+pub use hilbert::decode_3d as hilbert_decode_3d;
+pub use hilbert::encode_2d as hilbert_encode_2d;
+pub use hilbert::encode_3d as hilbert_encode_3d;
@@ -1,8 +1,11 @@
 //! Encoding/decoding of Morton Z-curve indices.
 //!
-//! The encode/decode 2d/3d functions in this module expose the fastest
-//! algorithm for the target and set of target features enabled when a nightly
-//! compiler is used.
+//! The encode/decode 2d/3d/nd functions in this module expose the fastest
+//! algorithm for the target and set of target features enabled. The `bmi2`
+//! implementation is selected at compile-time when the `bmi2` target feature
+//! is enabled. With the `std` cargo feature, it is selected at run-time if the
+//! CPU has fast `pdep`/`pext` instructions (see the
+//! [`cpu`](../../cpu/index.html) module).
 //!
 //! ## Correctness
 //!
@@ -26,9 +29,9 @@
 //! This decision was based on the following facts:
 //!
 //! - The `bmi2` algorithm is the fastest on machines with the BMI 2.0
-//! instruction set, but very slow otherwise.
+//!   instruction set, but very slow otherwise.
 //! - The performance of the `bitmask` and the look-up table (`lut`) algorithms in
-//! the micro-benchmarks is comparable.
+//!   the micro-benchmarks is comparable.
 //! - The `lut` version requires more memory than the `bitmask` version.
 //!
 //! and this hypothesis:
@@ -54,16 +57,207 @@
 //! `lut` sub-modules.
 //!
 //! The `bmi2` implementation is always loss-less.
+//!
+//! ## Neighbor arithmetic
+//!
+//! The `increment`, `decrement`, `add`, `sub`, and `cmp` 2d/3d functions
+//! operate on the coordinates of Morton indices without decoding them, by
+//! masking the interleaved bits of each dimension.
+//!
+//! ## Batch encoding/decoding
+//!
+//! The `_slice` (struct-of-arrays) and `_points` (array-of-structs) 2d/3d
+//! functions encode/decode many indices at once. They select the
+//! implementation once per call, and their portable loops are
+//! auto-vectorized.
+//!
+//! ## Anisotropic keys
+//!
+//! `Interleaving` encodes/decodes keys with a different number of bits per
+//! dimension, interleaved in a configurable order.
+//!
+//! ## Range queries
+//!
+//! Axis-aligned box queries over a sorted sequence of Morton indices are
+//! supported by `in_box`, `bigmin`, `litmax`, and `intervals`. The boxes are
+//! given by the Morton indices of their lower and upper corners.
 
 #![allow(dead_code)]
 
+use std::cmp::Ordering;
+
+use cpu;
 use word;
-use word::Word;
+use word::{Word, UnsignedWord};
+
+/// Mask of the lower `bits` bits of a word.
+#[inline]
+fn low_bits<T: Word>(bits: u32) -> T {
+    if bits >= T::bit_size().to_u32() {
+        !T::zero()
+    } else {
+        (T::one() << T::from_u32(bits)) - T::one()
+    }
+}
+
+/// Masks of the bits of a 128-bit index that belong to each dimension:
+/// the `d`-th mask has the bits `d, d + N, d + 2N, ...` set.
+const fn dimension_masks<const N: usize>() -> [u128; N] {
+    let mut masks = [0u128; N];
+    let mut i = 0;
+    while i < 128 {
+        masks[i % N] |= 1 << i;
+        i += 1;
+    }
+    masks
+}
+
+struct Dimensions<const N: usize>;
+
+impl<const N: usize> Dimensions<N> {
+    const MASKS: [u128; N] = dimension_masks::<N>();
+}
+
+/// Masks of the bits that belong to each dimension of a 2D Morton index.
+#[inline]
+fn masks_2d<T: Word>() -> [T; 2] {
+    let m = Dimensions::<2>::MASKS;
+    [T::from_u128(m[0]), T::from_u128(m[1])]
+}
+
+/// Masks of the bits that belong to each dimension of a 3D Morton index.
+#[inline]
+fn masks_3d<T: Word>() -> [T; 3] {
+    let m = Dimensions::<3>::MASKS;
+    [T::from_u128(m[0]), T::from_u128(m[1]), T::from_u128(m[2])]
+}
+
+/// Masks of the bits that belong to each dimension of a 3D Morton index
+/// produced by a lossy implementation, which only uses the lower `used_bits`
+/// bits of the index.
+#[inline]
+fn lossy_masks_3d<T: Word>(used_bits: u32) -> [T; 3] {
+    let used: T = low_bits(used_bits);
+    let m = masks_3d::<T>();
+    [m[0] & used, m[1] & used, m[2] & used]
+}
+
+// Neighbor arithmetic on the bits of a single dimension `mask` of a Morton
+// index. Setting the bits of the other dimensions to one (or zero) before an
+// addition (subtraction) propagates the carries (borrows) across them.
+
+#[inline]
+fn masked_increment<T: Word>(v: T, mask: T) -> T {
+    ((v | !mask).wrapping_add(T::one()) & mask) | (v & !mask)
+}
+
+#[inline]
+fn masked_decrement<T: Word>(v: T, mask: T) -> T {
+    ((v & mask).wrapping_sub(T::one()) & mask) | (v & !mask)
+}
+
+#[inline]
+fn masked_add<T: Word>(a: T, b: T, mask: T) -> T {
+    (a | !mask).wrapping_add(b & mask) & mask
+}
+
+#[inline]
+fn masked_sub<T: Word>(a: T, b: T, mask: T) -> T {
+    (a & mask).wrapping_sub(b & mask) & mask
+}
+
+#[inline]
+fn masked_cmp<T: Word>(a: T, b: T, mask: T) -> Ordering {
+    let (a, b) = ((a & mask).to_unsigned(), (b & mask).to_unsigned());
+    if a < b {
+        Ordering::Less
+    } else if a > b {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Implements the neighbor arithmetic of a backend, whose dimension masks
+/// are given by the functions `$masks_2d` and `$masks_3d`.
+macro_rules! neighbor_arithmetic_impl {
+    ($masks_2d:ident, $masks_3d:ident) => (
+        #[inline]
+        pub fn increment_2d<MortonIndex: Word>(v: MortonIndex, dimension: usize) -> MortonIndex {
+            super::masked_increment(v, $masks_2d::<MortonIndex>()[dimension])
+        }
+
+        #[inline]
+        pub fn decrement_2d<MortonIndex: Word>(v: MortonIndex, dimension: usize) -> MortonIndex {
+            super::masked_decrement(v, $masks_2d::<MortonIndex>()[dimension])
+        }
+
+        #[inline]
+        pub fn add_2d<MortonIndex: Word>(a: MortonIndex, b: MortonIndex) -> MortonIndex {
+            let [x, y] = $masks_2d::<MortonIndex>();
+            super::masked_add(a, b, x) | super::masked_add(a, b, y)
+        }
+
+        #[inline]
+        pub fn sub_2d<MortonIndex: Word>(a: MortonIndex, b: MortonIndex) -> MortonIndex {
+            let [x, y] = $masks_2d::<MortonIndex>();
+            super::masked_sub(a, b, x) | super::masked_sub(a, b, y)
+        }
+
+        #[inline]
+        pub fn cmp_2d<MortonIndex: Word>(a: MortonIndex,
+                                         b: MortonIndex)
+                                         -> (Ordering, Ordering) {
+            let [x, y] = $masks_2d::<MortonIndex>();
+            (super::masked_cmp(a, b, x), super::masked_cmp(a, b, y))
+        }
+
+        #[inline]
+        pub fn increment_3d<MortonIndex: Word>(v: MortonIndex, dimension: usize) -> MortonIndex {
+            super::masked_increment(v, $masks_3d::<MortonIndex>()[dimension])
+        }
+
+        #[inline]
+        pub fn decrement_3d<MortonIndex: Word>(v: MortonIndex, dimension: usize) -> MortonIndex {
+            super::masked_decrement(v, $masks_3d::<MortonIndex>()[dimension])
+        }
+
+        #[inline]
+        pub fn add_3d<MortonIndex: Word>(a: MortonIndex, b: MortonIndex) -> MortonIndex {
+            let [x, y, z] = $masks_3d::<MortonIndex>();
+            super::masked_add(a, b, x) | super::masked_add(a, b, y) | super::masked_add(a, b, z)
+        }
+
+        #[inline]
+        pub fn sub_3d<MortonIndex: Word>(a: MortonIndex, b: MortonIndex) -> MortonIndex {
+            let [x, y, z] = $masks_3d::<MortonIndex>();
+            super::masked_sub(a, b, x) | super::masked_sub(a, b, y) | super::masked_sub(a, b, z)
+        }
+
+        #[inline]
+        pub fn cmp_3d<MortonIndex: Word>(a: MortonIndex,
+                                         b: MortonIndex)
+                                         -> (Ordering, Ordering, Ordering) {
+            let [x, y, z] = $masks_3d::<MortonIndex>();
+            (super::masked_cmp(a, b, x), super::masked_cmp(a, b, y), super::masked_cmp(a, b, z))
+        }
+    )
+}
 
 pub mod lut {
     //! Encoding/decoding of Morton Z-curve indices using a look-up table.
 
+    use std::cmp::Ordering;
     use word::{Word, ToWord};
+    use super::{masks_2d, lossy_masks_3d};
+
+    /// Masks of the dimensions of a 3D Morton index: the highest bit of 64-bit
+    /// indices, and the two highest bits of 128-bit indices, are not used.
+    #[inline]
+    fn masks_3d<MortonIndex: Word>() -> [MortonIndex; 3] {
+        let bits = MortonIndex::bit_size().to_u32();
+        lossy_masks_3d(if bits <= 32 { bits } else { bits / 3 * 3 })
+    }
 
     const ENCODE_2D: [u16; 256] =
         [0, 1, 4, 5, 16, 17, 20, 21, 64, 65, 68, 69, 80, 81, 84, 85, 256, 257, 260, 261, 272, 273,
@@ -228,8 +422,9 @@ pub mod lut {
         let mut result = MortonIndex::zero();
         const MASK: u16 = 0x000001ff; // TODO: try u8
         let mask = MortonIndex::from_u16(MASK);
-        let loops = MortonIndex::byte_size().to_u8();
-        let loops = if loops >= 8 { 7 } else { loops };
+        // Number of 9-bit chunks whose start (shifted by at most 2) lies
+        // within the index:
+        let loops = (MortonIndex::bit_size().to_u8() + 6) / 9;
         for i in 0..loops {
             let shift = (i * 9) + startshift;
             let index: usize = ((v >> shift.to()) & mask).to_usize();
@@ -248,19 +443,23 @@ pub mod lut {
          decode_3d_h(v, &DECODE_3D, 1),
          decode_3d_h(v, &DECODE_3D, 2))
     }
+
+    neighbor_arithmetic_impl!(masks_2d, masks_3d);
 }
 
 pub mod bmi2 {
     //! Encoding/decoding of Morton Z-curve indices using BMI2 pdep/pext instructions.
+    use std::cmp::Ordering;
     use word::{Word, ToWord, ParallelBitsDeposit, ParallelBitsExtract};
+    use super::{Dimensions, masks_2d, masks_3d};
 
     #[inline]
     pub fn encode_2d<MortonIndex: Word, Coordinate: Word>(x: Coordinate,
                                                           y: Coordinate)
                                                           -> MortonIndex {
-        (y.parallel_bits_deposit(0xAAAAAAAAAAAAAAAAu64) |
-         x.parallel_bits_deposit(0x5555555555555555u64))
-            .to()
+        let (x, y): (MortonIndex, MortonIndex) = (x.to(), y.to());
+        y.parallel_bits_deposit(0xAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAu128) |
+        x.parallel_bits_deposit(0x55555555555555555555555555555555u128)
     }
 
     #[inline]
@@ -268,17 +467,17 @@ pub mod bmi2 {
                                                           y: Coordinate,
                                                           z: Coordinate)
                                                           -> MortonIndex {
-        (z.parallel_bits_deposit(0x4924924924924924u64) |
-         y.parallel_bits_deposit(0x2492492492492492u64) |
-         x.parallel_bits_deposit(0x9249249249249249u64))
-            .to()
+        let (x, y, z): (MortonIndex, MortonIndex, MortonIndex) = (x.to(), y.to(), z.to());
+        z.parallel_bits_deposit(0x24924924924924924924924924924924u128) |
+        y.parallel_bits_deposit(0x92492492492492492492492492492492u128) |
+        x.parallel_bits_deposit(0x49249249249249249249249249249249u128)
     }
 
     #[inline]
     pub fn decode_2d<Coordinate: Word, MortonIndex: Word>(v: MortonIndex)
                                                           -> (Coordinate, Coordinate) {
-        (v.parallel_bits_extract(0x5555555555555555u64).to(),
-         v.parallel_bits_extract(0xAAAAAAAAAAAAAAAAu64).to())
+        (v.parallel_bits_extract(0x55555555555555555555555555555555u128).to(),
+         v.parallel_bits_extract(0xAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAu128).to())
     }
 
 
@@ -286,16 +485,52 @@ pub mod bmi2 {
     pub fn decode_3d<Coordinate: Word, MortonIndex: Word>
         (v: MortonIndex)
          -> (Coordinate, Coordinate, Coordinate) {
-        (v.parallel_bits_extract(0x9249249249249249u64).to(),
-         v.parallel_bits_extract(0x2492492492492492u64).to(),
-         v.parallel_bits_extract(0x4924924924924924u64).to())
+        (v.parallel_bits_extract(0x49249249249249249249249249249249u128).to(),
+         v.parallel_bits_extract(0x92492492492492492492492492492492u128).to(),
+         v.parallel_bits_extract(0x24924924924924924924924924924924u128).to())
+    }
+
+    #[inline]
+    pub fn encode_nd<MortonIndex: Word, Coordinate: Word, const N: usize>
+        (coordinates: [Coordinate; N])
+         -> MortonIndex {
+        let mut v = MortonIndex::zero();
+        for (&x, &mask) in coordinates.iter().zip(Dimensions::<N>::MASKS.iter()) {
+            let x: MortonIndex = x.to();
+            v = v | x.parallel_bits_deposit(mask);
+        }
+        v
+    }
+
+    #[inline]
+    pub fn decode_nd<Coordinate: Word, MortonIndex: Word, const N: usize>
+        (v: MortonIndex)
+         -> [Coordinate; N] {
+        let mut coordinates = [Coordinate::zero(); N];
+        for (x, &mask) in coordinates.iter_mut().zip(Dimensions::<N>::MASKS.iter()) {
+            *x = v.parallel_bits_extract(mask).to();
+        }
+        coordinates
     }
+
+    neighbor_arithmetic_impl!(masks_2d, masks_3d);
 }
 
 
 pub mod bitmask {
     //! Encoding/decoding of Morton Z-curve indices using precomputed bitmasks.
-    use word::Word;
+    use std::cmp::Ordering;
+    use word::{Word, ToWord};
+    use super::{low_bits, masks_2d, lossy_masks_3d};
+
+    /// Masks of the dimensions of a 3D Morton index: the two highest bits of
+    /// 32-bit and 128-bit indices, and the highest bit of 64-bit indices, are
+    /// not used.
+    #[inline]
+    fn masks_3d<MortonIndex: Word>() -> [MortonIndex; 3] {
+        let bits = MortonIndex::bit_size().to_u32();
+        lossy_masks_3d(if bits <= 16 { bits } else { bits / 3 * 3 })
+    }
 
     const MASK_2D_U32: [u32; 6] = [0xFFFFFFFF, 0x0000FFFF, 0x00FF00FF, 0x0F0F0F0F, 0x33333333,
                                    0x55555555];
@@ -314,10 +549,26 @@ pub mod bitmask {
                                    0x10c30c30c30c30c3u64,
                                    0x1249249249249249u64];
 
+    const MASK_2D_U128: [u128; 7] = [0x0000000000000000FFFFFFFFFFFFFFFF,
+                                     0x00000000FFFFFFFF00000000FFFFFFFF,
+                                     0x0000FFFF0000FFFF0000FFFF0000FFFF,
+                                     0x00FF00FF00FF00FF00FF00FF00FF00FF,
+                                     0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F,
+                                     0x33333333333333333333333333333333,
+                                     0x55555555555555555555555555555555];
+
+    const MASK_3D_U128: [u128; 7] = [0x3ffffffffff,
+                                     0x000003ff0000000000000000ffffffff,
+                                     0x000003ff00000000ffff00000000ffff,
+                                     0x030000ff0000ff0000ff0000ff0000ff,
+                                     0x0300f00f00f00f00f00f00f00f00f00f,
+                                     0x030c30c30c30c30c30c30c30c30c30c3,
+                                     0x09249249249249249249249249249249];
+
 
     #[inline]
     fn split_by_second_bits<MortonIndex: Word, Coordinate: Word>(x: Coordinate) -> MortonIndex {
-        match Coordinate::bit_size().to_u8() {
+        match MortonIndex::bit_size().to_u8() {
             32 => {
                 let mut x = x.to_u32();
                 x = (x | x << 16) & MASK_2D_U32[1];
@@ -337,6 +588,17 @@ pub mod bitmask {
                 x = (x | x << 1) & MASK_2D_U64[5];
                 MortonIndex::from_u64(x)
             }
+            128 => {
+                let mut x = x.to_u128();
+                x = (x | x << 64) & MASK_2D_U128[0];
+                x = (x | x << 32) & MASK_2D_U128[1];
+                x = (x | x << 16) & MASK_2D_U128[2];
+                x = (x | x << 8) & MASK_2D_U128[3];
+                x = (x | x << 4) & MASK_2D_U128[4];
+                x = (x | x << 2) & MASK_2D_U128[5];
+                x = (x | x << 1) & MASK_2D_U128[6];
+                MortonIndex::from_u128(x)
+            }
             _ => MortonIndex::from_u32(split_by_second_bits(x.to_u32())),
         }
     }
@@ -364,6 +626,17 @@ pub mod bitmask {
                 x = (x ^ (x >> 16)) & MASK_2D_U64[0];
                 Coordinate::from_u64(x)
             }
+            128 => {
+                let mut x = x.to_u128();
+                x &= MASK_2D_U128[6];
+                x = (x ^ (x >> 1)) & MASK_2D_U128[5];
+                x = (x ^ (x >> 2)) & MASK_2D_U128[4];
+                x = (x ^ (x >> 4)) & MASK_2D_U128[3];
+                x = (x ^ (x >> 8)) & MASK_2D_U128[2];
+                x = (x ^ (x >> 16)) & MASK_2D_U128[1];
+                x = (x ^ (x >> 32)) & MASK_2D_U128[0];
+                Coordinate::from_u128(x)
+            }
             _ => Coordinate::from_u32(get_second_bits(x.to_u32())),
         }
     }
@@ -384,7 +657,7 @@ pub mod bitmask {
 
     #[inline]
     fn split_by_third_bits<MortonIndex: Word, Coordinate: Word>(x: Coordinate) -> MortonIndex {
-        match Coordinate::bit_size().to_u8() {
+        match MortonIndex::bit_size().to_u8() {
             32 => {
                 let mut x = x.to_u32();
                 x &= MASK_3D_U32[0];
@@ -403,6 +676,16 @@ pub mod bitmask {
                 x = (x | x << 2) & MASK_3D_U64[5];
                 MortonIndex::from_u64(x)
             }
+            128 => {
+                let mut x = x.to_u128() & MASK_3D_U128[0];
+                x = (x | x << 64) & MASK_3D_U128[1];
+                x = (x | x << 32) & MASK_3D_U128[2];
+                x = (x | x << 16) & MASK_3D_U128[3];
+                x = (x | x << 8) & MASK_3D_U128[4];
+                x = (x | x << 4) & MASK_3D_U128[5];
+                x = (x | x << 2) & MASK_3D_U128[6];
+                MortonIndex::from_u128(x)
+            }
             _ => MortonIndex::from_u32(split_by_third_bits(x.to_u32())),
         }
     }
@@ -428,6 +711,16 @@ pub mod bitmask {
                 x = (x ^ ((x) >> 32)) & MASK_3D_U64[0];
                 Coordinate::from_u64(x)
             }
+            128 => {
+                let mut x = x.to_u128() & MASK_3D_U128[6];
+                x = (x ^ (x >> 2)) & MASK_3D_U128[5];
+                x = (x ^ (x >> 4)) & MASK_3D_U128[4];
+                x = (x ^ (x >> 8)) & MASK_3D_U128[3];
+                x = (x ^ (x >> 16)) & MASK_3D_U128[2];
+                x = (x ^ (x >> 32)) & MASK_3D_U128[1];
+                x = (x ^ (x >> 64)) & MASK_3D_U128[0];
+                Coordinate::from_u128(x)
+            }
 
             _ => Coordinate::from_u32(get_third_bits(x.to_u32())),
         }
@@ -451,6 +744,108 @@ pub mod bitmask {
          get_third_bits(v >> MortonIndex::one()),
          get_third_bits(v >> MortonIndex::from_u8(2)))
     }
+
+    /// Magic masks to split the bits of a word by `n` bits.
+    ///
+    /// Spreading the bits proceeds in steps `k = 6, ..., 0`. In step `k`, the
+    /// bits `i` of the coordinate with `i & 2^k != 0` are moved to the left by
+    /// `(n - 1) * 2^k`. The `k`-th mask has the positions of all bits after
+    /// step `k` set: bit `i` ends up in position `i + (n - 1) * (i & !(2^k - 1))`.
+    ///
+    /// The masks are computed for 128-bit words and are truncated to the width
+    /// of the Morton index.
+    const fn split_masks(n: usize) -> [u128; 7] {
+        let mut masks = [0u128; 7];
+        let mut k = 0;
+        while k < 7 {
+            let low = (1 << k) - 1;
+            let mut i = 0;
+            while i < 128 {
+                let p = i + (n - 1) * (i & !low);
+                if p < 128 {
+                    masks[k] |= 1 << p;
+                }
+                i += 1;
+            }
+            k += 1;
+        }
+        masks
+    }
+
+    struct Masks<const N: usize>;
+
+    impl<const N: usize> Masks<N> {
+        const SPLIT: [u128; 7] = split_masks(N);
+    }
+
+    /// Number of bits of each coordinate that are stored in the index.
+    #[inline]
+    fn bits_per_coordinate<MortonIndex: Word>(n: usize) -> u32 {
+        (MortonIndex::bit_size().to_u32() + n as u32 - 1) / n as u32
+    }
+
+    #[inline]
+    fn split_by_nth_bits<MortonIndex: Word, Coordinate: Word, const N: usize>
+        (x: Coordinate)
+         -> MortonIndex {
+        let bits = bits_per_coordinate::<MortonIndex>(N);
+        let mut x: MortonIndex = x.to();
+        x = x & low_bits(bits);
+        for k in (0..7).rev() {
+            let step = 1u32 << k;
+            if step < bits {
+                let shift = MortonIndex::from_u32((N as u32 - 1) * step);
+                x = (x | (x << shift)) & Masks::<N>::SPLIT[k].to();
+            }
+        }
+        x
+    }
+
+    #[inline]
+    fn get_nth_bits<Coordinate: Word, MortonIndex: Word, const N: usize>
+        (x: MortonIndex)
+         -> Coordinate {
+        let bits = bits_per_coordinate::<MortonIndex>(N);
+        let mut x = x & Masks::<N>::SPLIT[0].to();
+        for k in 0..7 {
+            let step = 1u32 << k;
+            if step < bits {
+                let shift = MortonIndex::from_u32((N as u32 - 1) * step);
+                let mask = if k < 6 {
+                    Masks::<N>::SPLIT[k + 1].to()
+                } else {
+                    !MortonIndex::zero()
+                };
+                x = (x | (x >> shift)) & mask;
+            }
+        }
+        (x & low_bits(bits)).to()
+    }
+
+    #[inline]
+    pub fn encode_nd<MortonIndex: Word, Coordinate: Word, const N: usize>
+        (coordinates: [Coordinate; N])
+         -> MortonIndex {
+        let mut v = MortonIndex::zero();
+        for (d, &x) in coordinates.iter().enumerate() {
+            let x = split_by_nth_bits::<MortonIndex, Coordinate, N>(x);
+            v = v | (x << MortonIndex::from_u32(d as u32));
+        }
+        v
+    }
+
+    #[inline]
+    pub fn decode_nd<Coordinate: Word, MortonIndex: Word, const N: usize>
+        (v: MortonIndex)
+         -> [Coordinate; N] {
+        let mut coordinates = [Coordinate::zero(); N];
+        for (d, x) in coordinates.iter_mut().enumerate() {
+            *x = get_nth_bits::<Coordinate, MortonIndex, N>(v >> MortonIndex::from_u32(d as u32));
+        }
+        coordinates
+    }
+
+    neighbor_arithmetic_impl!(masks_2d, masks_3d);
 }
 
 /// Encode coordinates `x` and `y` into an interleaved Morton index for a
@@ -481,20 +876,18 @@ pub mod bitmask {
 /// assert_eq!(morton::encode_2d(x, y), r);
 /// assert_eq!(morton::encode_2d(x as u32, y as u32), r as u32);
 /// assert_eq!(morton::encode_2d(x as u64, y as u64), r as u64);
+/// assert_eq!(morton::encode_2d(x as u128, y as u128), r as u128);
+///
+/// // The backends in the sub-modules allow the coordinates and the index to
+/// // have different widths:
+/// let v: u128 = morton::bitmask::encode_2d(u64::MAX, 0u64);
+/// assert_eq!(v, 0x55555555555555555555555555555555u128);
 /// ```
 #[inline]
 pub fn encode_2d<T: Word>(x: T, y: T) -> T {
-    #[cfg(RUSTC_IS_NIGHTLY)]
-    {
-        if cfg!(target_feature = "bmi2") {
-            bmi2::encode_2d(x, y)
-        } else {
-            // bitmask::encode_2d(x, y)
-            lut::encode_2d(x, y)
-        }
-    }
-    #[cfg(not(RUSTC_IS_NIGHTLY))]
-    {
+    if cpu::use_bmi2() {
+        bmi2::encode_2d(x, y)
+    } else {
         // bitmask::encode_2d(x, y)
         lut::encode_2d(x, y)
     }
@@ -512,6 +905,10 @@ pub fn encode_3d_high_bits<T: Word>(v: T, x: T, y: T, _: T) -> T {
         64 => {
             v = word::copy_bit(x, 21u8, v, 63u8);
         }
+        128 => {
+            v = word::copy_bit(x, 42u8, v, 126u8);
+            v = word::copy_bit(y, 42u8, v, 127u8);
+        }
         _ => {}
     };
     v
@@ -553,17 +950,9 @@ pub fn encode_3d_high_bits<T: Word>(v: T, x: T, y: T, _: T) -> T {
 /// ```
 #[inline]
 pub fn encode_3d<T: Word>(x: T, y: T, z: T) -> T {
-    #[cfg(RUSTC_IS_NIGHTLY)]
-    {
-        if cfg!(target_feature = "bmi2") {
-            bmi2::encode_3d(x, y, z)
-        } else {
-            let v = bitmask::encode_3d(x, y, z);
-            encode_3d_high_bits(v, x, y, z)
-        }
-    }
-    #[cfg(not(RUSTC_IS_NIGHTLY))]
-    {
+    if cpu::use_bmi2() {
+        bmi2::encode_3d(x, y, z)
+    } else {
         let v = bitmask::encode_3d(x, y, z);
         encode_3d_high_bits(v, x, y, z)
     }
@@ -595,17 +984,9 @@ pub fn encode_3d<T: Word>(x: T, y: T, z: T) -> T {
 /// ```
 #[inline]
 pub fn decode_2d<T: Word>(v: T) -> (T, T) {
-    #[cfg(RUSTC_IS_NIGHTLY)]
-    {
-        if cfg!(target_feature = "bmi2") {
-            bmi2::decode_2d(v)
-        } else {
-            bitmask::decode_2d(v)
-            //lut::decode_2d(v)
-        }
-    }
-    #[cfg(not(RUSTC_IS_NIGHTLY))]
-    {
+    if cpu::use_bmi2() {
+        bmi2::decode_2d(v)
+    } else {
         bitmask::decode_2d(v)
         //lut::decode_2d(v)
     }
@@ -624,6 +1005,10 @@ pub fn decode_3d_high_bits<T: Word>(v: T, x: T, y: T, z: T) -> (T, T, T) {
         64 => {
             x = word::copy_bit(v, 63u8, x, 21u8);
         }
+        128 => {
+            x = word::copy_bit(v, 126u8, x, 42u8);
+            y = word::copy_bit(v, 127u8, y, 42u8);
+        }
         _ => {}
     };
     (x, y, z)
@@ -660,101 +1045,1003 @@ pub fn decode_3d_high_bits<T: Word>(v: T, x: T, y: T, z: T) -> (T, T, T) {
 /// ```
 #[inline]
 pub fn decode_3d<T: Word>(v: T) -> (T, T, T) {
-    #[cfg(RUSTC_IS_NIGHTLY)]
-    {
-        if cfg!(target_feature = "bmi2") {
-            bmi2::decode_3d(v)
-        } else {
-            let (x, y, z) = bitmask::decode_3d(v);
-            decode_3d_high_bits(v, x, y, z)
-        }
-    }
-    #[cfg(not(RUSTC_IS_NIGHTLY))]
-    {
+    if cpu::use_bmi2() {
+        bmi2::decode_3d(v)
+    } else {
         let (x, y, z) = bitmask::decode_3d(v);
         decode_3d_high_bits(v, x, y, z)
     }
 }
 
+/// Encode the `N` coordinates into an interleaved Morton index for an
+/// `N`-dimensional z-Curve.
+///
+/// Using `_.i` to denote the `i`-th bit in a word and `x0, ..., x{N-1}` to
+/// denote the coordinates, this function encodes their bits into a value `v`:
+///
+/// `v = |...|x{N-1}.1|...|x1.1|x0.1|x{N-1}.0|...|x1.0|x0.0|`
+///
+/// that is, bit `i` of coordinate `d` is stored in bit `i * N + d` of `v`.
+/// The bits of the coordinates that do not fit into `v` are dropped. For
+/// `N == 2` and `N == 3` this is the same encoding as `encode_2d` and
+/// `encode_3d`.
+///
+/// Note: the encoded Morton index is always invertible to the coordinates using
+/// the `decode_nd` function.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let x = 0b0000_0011u8;
+/// let y = 0b0000_0001u8;
+/// let z = 0b0000_0010u8;
+/// let w = 0b0000_0000u8;
+/// let r = 0b0101_0011u8;
+/// assert_eq!(morton::encode_nd([x, y, z, w]), r);
+/// assert_eq!(morton::encode_nd([x, y]), morton::encode_2d(x, y));
+/// assert_eq!(morton::encode_nd([x, y, z]), morton::encode_3d(x, y, z));
+/// ```
+#[inline]
+pub fn encode_nd<T: Word, const N: usize>(coordinates: [T; N]) -> T {
+    if cpu::use_bmi2() {
+        bmi2::encode_nd(coordinates)
+    } else {
+        bitmask::encode_nd(coordinates)
+    }
+}
 
-#[doc(hidden)]
-pub mod testing_utils {
-    //! Testing utilities, used in tests and benchmarks (TODO: make optional)
-    use super::*;
-    use std::fmt::Debug;
-
-    pub trait RunnerFn {
-        fn run<T: Word + Debug>(&self, T);
+/// Decodes an interleaved Morton index for an `N`-dimensional Z-Curve into its
+/// coordinates.
+///
+/// This is the inverse of `encode_nd`: bit `i * N + d` of `v` is stored in
+/// bit `i` of the `d`-th coordinate.
+///
+/// Note: the decoded coordinates are always invertible to the morton code using
+/// the `encode_nd` function.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let r = 0b0101_0011u8;
+/// assert_eq!(morton::decode_nd(r), [0b11u8, 0b01, 0b10, 0b00]);
+/// assert_eq!(morton::decode_nd::<_, 4>(u128::MAX), [u32::MAX as u128; 4]);
+/// ```
+#[inline]
+pub fn decode_nd<T: Word, const N: usize>(v: T) -> [T; N] {
+    if cpu::use_bmi2() {
+        bmi2::decode_nd(v)
+    } else {
+        bitmask::decode_nd(v)
     }
+}
 
-    pub struct Runner;
-    impl Runner {
-        pub fn run_u8<T: RunnerFn>(i: &T) {
-            (0u8..u8::max_value()).map(|v| i.run(v)).count();
-        }
-        pub fn run_u16<T: RunnerFn>(i: &T) {
-            (0u16..u16::max_value()).map(|v| i.run(v)).count();
+/// Encodes the coordinates `x[i]` and `y[i]` into the Morton indices
+/// `out[i]` of a 2D z-Curve.
+///
+/// This is equivalent to calling `encode_2d` on every element but selects the
+/// implementation once for the whole slice. Without BMI2, the loop uses the
+/// `bitmask` implementation, which only performs shifts, ands, and ors, and
+/// is auto-vectorized by the compiler (e.g. with SSE2 or AVX2).
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let x = [1u32, 2, 3];
+/// let y = [4u32, 5, 6];
+/// let mut v = [0u32; 3];
+/// morton::encode_2d_slice(&x, &y, &mut v);
+/// assert_eq!(v, [morton::encode_2d(1, 4), morton::encode_2d(2, 5), morton::encode_2d(3, 6)]);
+/// ```
+#[inline]
+pub fn encode_2d_slice<T: Word>(x: &[T], y: &[T], out: &mut [T]) {
+    assert_eq!(x.len(), out.len());
+    assert_eq!(y.len(), out.len());
+    let coordinates = x.iter().zip(y);
+    if cpu::use_bmi2() {
+        for (v, (&x, &y)) in out.iter_mut().zip(coordinates) {
+            *v = bmi2::encode_2d(x, y);
         }
-        pub fn run_u32<T: RunnerFn>(i: &T) {
-            let t0_min = u16::max_value() as u32;
-            let t0_max = t0_min + 1000000;
-            (t0_min..t0_max).map(|v| i.run(v)).count();
-            let t1_max = u32::max_value();
-            let t1_min = t1_max - 1000000;
-            (t1_min..t1_max).map(|v| i.run(v)).count();
+    } else {
+        for (v, (&x, &y)) in out.iter_mut().zip(coordinates) {
+            *v = bitmask::encode_2d(x, y);
         }
+    }
+}
 
-        pub fn run_u64<T: RunnerFn>(i: &T) {
-            let t0_min = u32::max_value() as u64;
-            let t0_max = t0_min + 1000000;
-            (t0_min..t0_max).map(|v| i.run(v)).count();
-            let t1_max = u64::max_value();
-            let t1_min = t1_max - 1000000;
-            (t1_min..t1_max).map(|v| i.run(v)).count();
-
+/// Decodes the Morton indices `v[i]` of a 2D z-Curve into the coordinates
+/// `x[i]` and `y[i]`.
+///
+/// This is the inverse of `encode_2d_slice`.
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = [morton::encode_2d(1u32, 4), morton::encode_2d(2, 5)];
+/// let (mut x, mut y) = ([0u32; 2], [0u32; 2]);
+/// morton::decode_2d_slice(&v, &mut x, &mut y);
+/// assert_eq!((x, y), ([1, 2], [4, 5]));
+/// ```
+#[inline]
+pub fn decode_2d_slice<T: Word>(v: &[T], x: &mut [T], y: &mut [T]) {
+    assert_eq!(x.len(), v.len());
+    assert_eq!(y.len(), v.len());
+    let coordinates = x.iter_mut().zip(y.iter_mut());
+    if cpu::use_bmi2() {
+        for (&v, (x, y)) in v.iter().zip(coordinates) {
+            let (a, b) = bmi2::decode_2d(v);
+            *x = a;
+            *y = b;
         }
-
-        pub fn run<T: RunnerFn>(i: T) {
-            Runner::run_u8(&i);
-            Runner::run_u16(&i);
-            Runner::run_u32(&i);
-            Runner::run_u64(&i);
+    } else {
+        for (&v, (x, y)) in v.iter().zip(coordinates) {
+            let (a, b) = bitmask::decode_2d(v);
+            *x = a;
+            *y = b;
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use super::testing_utils::*;
-    use std::fmt::Debug;
-
-    struct BMI2Invariant;
-
-    impl RunnerFn for BMI2Invariant {
-        fn run<T: Word + Debug>(&self, v: T) {
-            {
-                // 2D:
-                let (x, y) = bmi2::decode_2d::<T, _>(v);
-                let vs = bmi2::encode_2d::<T, _>(x, y);
-                assert_eq!(vs, v);
-            }
-            {
-                // 3D:
-                let (x, y, z) = bmi2::decode_3d::<T, _>(v);
-                let vs = bmi2::encode_3d::<T, _>(x, y, z);
-                assert_eq!(vs, v);
-            }
+/// Encodes the coordinates `x[i]`, `y[i]`, and `z[i]` into the Morton indices
+/// `out[i]` of a 3D z-Curve.
+///
+/// This is equivalent to calling `encode_3d` on every element but selects the
+/// implementation once for the whole slice (see `encode_2d_slice`).
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let (x, y, z) = ([1u64, 2], [3u64, 4], [5u64, 6]);
+/// let mut v = [0u64; 2];
+/// morton::encode_3d_slice(&x, &y, &z, &mut v);
+/// assert_eq!(v, [morton::encode_3d(1, 3, 5), morton::encode_3d(2, 4, 6)]);
+/// ```
+#[inline]
+pub fn encode_3d_slice<T: Word>(x: &[T], y: &[T], z: &[T], out: &mut [T]) {
+    assert_eq!(x.len(), out.len());
+    assert_eq!(y.len(), out.len());
+    assert_eq!(z.len(), out.len());
+    let coordinates = x.iter().zip(y).zip(z);
+    if cpu::use_bmi2() {
+        for (v, ((&x, &y), &z)) in out.iter_mut().zip(coordinates) {
+            *v = bmi2::encode_3d(x, y, z);
+        }
+    } else {
+        for (v, ((&x, &y), &z)) in out.iter_mut().zip(coordinates) {
+            *v = encode_3d_high_bits(bitmask::encode_3d(x, y, z), x, y, z);
         }
     }
+}
+
+/// Decodes the Morton indices `v[i]` of a 3D z-Curve into the coordinates
+/// `x[i]`, `y[i]`, and `z[i]`.
+///
+/// This is the inverse of `encode_3d_slice`.
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = [morton::encode_3d(1u64, 3, 5), morton::encode_3d(2, 4, 6)];
+/// let (mut x, mut y, mut z) = ([0u64; 2], [0u64; 2], [0u64; 2]);
+/// morton::decode_3d_slice(&v, &mut x, &mut y, &mut z);
+/// assert_eq!((x, y, z), ([1, 2], [3, 4], [5, 6]));
+/// ```
+#[inline]
+pub fn decode_3d_slice<T: Word>(v: &[T], x: &mut [T], y: &mut [T], z: &mut [T]) {
+    assert_eq!(x.len(), v.len());
+    assert_eq!(y.len(), v.len());
+    assert_eq!(z.len(), v.len());
+    let coordinates = x.iter_mut().zip(y.iter_mut()).zip(z.iter_mut());
+    if cpu::use_bmi2() {
+        for (&v, ((x, y), z)) in v.iter().zip(coordinates) {
+            let (a, b, c) = bmi2::decode_3d(v);
+            *x = a;
+            *y = b;
+            *z = c;
+        }
+    } else {
+        for (&v, ((x, y), z)) in v.iter().zip(coordinates) {
+            let (a, b, c) = bitmask::decode_3d(v);
+            let (a, b, c) = decode_3d_high_bits(v, a, b, c);
+            *x = a;
+            *y = b;
+            *z = c;
+        }
+    }
+}
+
+/// Encodes the points `[x, y]` into the Morton indices `out[i]` of a 2D
+/// z-Curve.
+///
+/// This is the array-of-structs version of `encode_2d_slice`.
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let points = [[1u32, 4], [2, 5]];
+/// let mut v = [0u32; 2];
+/// morton::encode_2d_points(&points, &mut v);
+/// assert_eq!(v, [morton::encode_2d(1, 4), morton::encode_2d(2, 5)]);
+/// ```
+#[inline]
+pub fn encode_2d_points<T: Word>(points: &[[T; 2]], out: &mut [T]) {
+    assert_eq!(points.len(), out.len());
+    if cpu::use_bmi2() {
+        for (v, &[x, y]) in out.iter_mut().zip(points) {
+            *v = bmi2::encode_2d(x, y);
+        }
+    } else {
+        for (v, &[x, y]) in out.iter_mut().zip(points) {
+            *v = bitmask::encode_2d(x, y);
+        }
+    }
+}
+
+/// Decodes the Morton indices `v[i]` of a 2D z-Curve into the points
+/// `points[i] = [x, y]`.
+///
+/// This is the inverse of `encode_2d_points`.
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = [morton::encode_2d(1u32, 4), morton::encode_2d(2, 5)];
+/// let mut points = [[0u32; 2]; 2];
+/// morton::decode_2d_points(&v, &mut points);
+/// assert_eq!(points, [[1, 4], [2, 5]]);
+/// ```
+#[inline]
+pub fn decode_2d_points<T: Word>(v: &[T], points: &mut [[T; 2]]) {
+    assert_eq!(points.len(), v.len());
+    if cpu::use_bmi2() {
+        for (&v, p) in v.iter().zip(points) {
+            let (x, y) = bmi2::decode_2d(v);
+            *p = [x, y];
+        }
+    } else {
+        for (&v, p) in v.iter().zip(points) {
+            let (x, y) = bitmask::decode_2d(v);
+            *p = [x, y];
+        }
+    }
+}
+
+/// Encodes the points `[x, y, z]` into the Morton indices `out[i]` of a 3D
+/// z-Curve.
+///
+/// This is the array-of-structs version of `encode_3d_slice`.
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let points = [[1u64, 3, 5], [2, 4, 6]];
+/// let mut v = [0u64; 2];
+/// morton::encode_3d_points(&points, &mut v);
+/// assert_eq!(v, [morton::encode_3d(1, 3, 5), morton::encode_3d(2, 4, 6)]);
+/// ```
+#[inline]
+pub fn encode_3d_points<T: Word>(points: &[[T; 3]], out: &mut [T]) {
+    assert_eq!(points.len(), out.len());
+    if cpu::use_bmi2() {
+        for (v, &[x, y, z]) in out.iter_mut().zip(points) {
+            *v = bmi2::encode_3d(x, y, z);
+        }
+    } else {
+        for (v, &[x, y, z]) in out.iter_mut().zip(points) {
+            *v = encode_3d_high_bits(bitmask::encode_3d(x, y, z), x, y, z);
+        }
+    }
+}
+
+/// Decodes the Morton indices `v[i]` of a 3D z-Curve into the points
+/// `points[i] = [x, y, z]`.
+///
+/// This is the inverse of `encode_3d_points`.
+///
+/// # Panics
+///
+/// If the slices do not have the same length.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = [morton::encode_3d(1u64, 3, 5), morton::encode_3d(2, 4, 6)];
+/// let mut points = [[0u64; 3]; 2];
+/// morton::decode_3d_points(&v, &mut points);
+/// assert_eq!(points, [[1, 3, 5], [2, 4, 6]]);
+/// ```
+#[inline]
+pub fn decode_3d_points<T: Word>(v: &[T], points: &mut [[T; 3]]) {
+    assert_eq!(points.len(), v.len());
+    if cpu::use_bmi2() {
+        for (&v, p) in v.iter().zip(points) {
+            let (x, y, z) = bmi2::decode_3d(v);
+            *p = [x, y, z];
+        }
+    } else {
+        for (&v, p) in v.iter().zip(points) {
+            let (x, y, z) = bitmask::decode_3d(v);
+            let (x, y, z) = decode_3d_high_bits(v, x, y, z);
+            *p = [x, y, z];
+        }
+    }
+}
+
+
+/// Interleaving of the bits of `N` coordinates with different bit budgets
+/// into a Morton index of type `T`.
+///
+/// The interleaving is given by one mask per dimension: the `i`-th bit of the
+/// `d`-th coordinate is stored in the position of the `i`-th set bit of the
+/// `d`-th mask. Encoding and decoding are implemented with
+/// `parallel_bits_deposit` and `parallel_bits_extract`, and use the BMI2
+/// instructions if these are selected (see the [`cpu`](../../cpu/index.html)
+/// module).
+///
+/// The coordinate bits beyond the budget of their dimension are dropped when
+/// encoding.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton::Interleaving;
+///
+/// // 24 bits of x, 24 of y, and 16 of z: the lower 48 bits of the key
+/// // interleave x, y, and z, the upper 16 bits interleave x and y.
+/// let spec = Interleaving::<u64, 3>::new([24, 24, 16]);
+/// let v = spec.encode([0xFF_FFFF, 0, 0]);
+/// assert_eq!(v, 0x5555_2492_4924_9249);
+/// assert_eq!(spec.decode(v), [0xFF_FFFF, 0, 0]);
+///
+/// // With equal budgets it is the same as the isotropic encoding:
+/// let spec = Interleaving::<u32, 2>::new([16, 16]);
+/// assert_eq!(spec.encode([3, 5]), bitwise::word::morton::encode_2d(3, 5));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Interleaving<T: Word, const N: usize> {
+    masks: [T; N],
+}
+
+impl<T: Word, const N: usize> Interleaving<T, N> {
+    /// Interleaving with `bits[d]` bits for dimension `d`.
+    ///
+    /// Starting from the least significant bit, the key stores one bit of
+    /// each dimension in turn, in the order `0, 1, ..., N - 1`, skipping the
+    /// dimensions whose budget has been exhausted.
+    ///
+    /// # Panics
+    ///
+    /// If the sum of the budgets is larger than the number of bits of `T`.
+    pub fn new(bits: [u32; N]) -> Self {
+        let total: u32 = bits.iter().sum();
+        assert!(total <= T::bit_size().to_u32(),
+                "the bit budget ({}) does not fit into the key", total);
+        let mut masks = [T::zero(); N];
+        let mut remaining = bits;
+        let mut position = T::zero();
+        while position != T::from_u32(total) {
+            for (mask, remaining) in masks.iter_mut().zip(remaining.iter_mut()) {
+                if *remaining > 0 {
+                    *mask = *mask | (T::one() << position);
+                    *remaining -= 1;
+                    position = position + T::one();
+                }
+            }
+        }
+        Self { masks }
+    }
+
+    /// Interleaving in which the `i`-th bit of the key stores the next bit of
+    /// the dimension `sequence[i]`.
+    ///
+    /// # Panics
+    ///
+    /// If the sequence is longer than the number of bits of `T` or contains a
+    /// dimension `>= N`.
+    ///
+    /// # Example
+    /// ```
+    /// use bitwise::word::morton::Interleaving;
+    ///
+    /// // Two bits of x, then alternating bits of y and x:
+    /// let spec = Interleaving::<u8, 2>::from_sequence(&[0, 0, 1, 0, 1, 0]);
+    /// assert_eq!(spec.masks(), [0b10_1011, 0b01_0100]);
+    /// assert_eq!(spec.encode([0b1111, 0b11]), 0b11_1111);
+    /// ```
+    pub fn from_sequence(sequence: &[usize]) -> Self {
+        assert!(sequence.len() <= T::bit_size().to_usize(),
+                "the sequence does not fit into the key");
+        let mut masks = [T::zero(); N];
+        for (position, &dimension) in sequence.iter().enumerate() {
+            masks[dimension] = masks[dimension] | (T::one() << T::from_u32(position as u32));
+        }
+        Self { masks }
+    }
+
+    /// Interleaving with the bits of dimension `d` stored in `masks[d]`.
+    ///
+    /// # Panics
+    ///
+    /// If the masks overlap.
+    pub fn from_masks(masks: [T; N]) -> Self {
+        let mut all = T::zero();
+        for &mask in &masks {
+            assert!(all & mask == T::zero(), "the masks overlap");
+            all = all | mask;
+        }
+        Self { masks }
+    }
+
+    /// Masks of the bits of the key that belong to each dimension.
+    #[inline]
+    pub fn masks(&self) -> [T; N] {
+        self.masks
+    }
+
+    /// Number of bits of each dimension.
+    #[inline]
+    pub fn bits(&self) -> [u32; N] {
+        let mut bits = [0; N];
+        for (bits, &mask) in bits.iter_mut().zip(&self.masks) {
+            *bits = mask.count_ones().to_u32();
+        }
+        bits
+    }
+
+    /// Encodes the `coordinates` into a Morton index.
+    #[inline]
+    pub fn encode(&self, coordinates: [T; N]) -> T {
+        let mut v = T::zero();
+        for (&x, &mask) in coordinates.iter().zip(&self.masks) {
+            v = v | word::parallel_bits_deposit(x, mask);
+        }
+        v
+    }
+
+    /// Decodes the Morton index `v` into its coordinates.
+    ///
+    /// The bits of `v` that do not belong to any dimension are ignored.
+    #[inline]
+    pub fn decode(&self, v: T) -> [T; N] {
+        let mut coordinates = [T::zero(); N];
+        for (x, &mask) in coordinates.iter_mut().zip(&self.masks) {
+            *x = word::parallel_bits_extract(v, mask);
+        }
+        coordinates
+    }
+}
+
+/// Increments the coordinate `dimension` of the 2D Morton index `v` by one.
+///
+/// This is equivalent to decoding `v`, incrementing the coordinate, and
+/// encoding the result, but operates directly on the interleaved bits. The
+/// coordinate wraps around on overflow.
+///
+/// # Panics
+///
+/// If `dimension >= 2`.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = morton::encode_2d(3u8, 5u8);
+/// assert_eq!(morton::increment_2d(v, 0), morton::encode_2d(4u8, 5u8));
+/// assert_eq!(morton::increment_2d(v, 1), morton::encode_2d(3u8, 6u8));
+/// assert_eq!(morton::increment_2d(morton::encode_2d(15u8, 0u8), 0), 0);
+/// ```
+#[inline]
+pub fn increment_2d<T: Word>(v: T, dimension: usize) -> T {
+    masked_increment(v, masks_2d::<T>()[dimension])
+}
+
+/// Decrements the coordinate `dimension` of the 2D Morton index `v` by one.
+///
+/// The coordinate wraps around on underflow.
+///
+/// # Panics
+///
+/// If `dimension >= 2`.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = morton::encode_2d(3u8, 4u8);
+/// assert_eq!(morton::decrement_2d(v, 0), morton::encode_2d(2u8, 4u8));
+/// assert_eq!(morton::decrement_2d(v, 1), morton::encode_2d(3u8, 3u8));
+/// assert_eq!(morton::decrement_2d(0u8, 1), morton::encode_2d(0u8, 15u8));
+/// ```
+#[inline]
+pub fn decrement_2d<T: Word>(v: T, dimension: usize) -> T {
+    masked_decrement(v, masks_2d::<T>()[dimension])
+}
+
+/// Adds the 2D Morton indices `a` and `b` dimension-wise.
+///
+/// Each coordinate wraps around on overflow.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let a = morton::encode_2d(3u8, 5u8);
+/// let b = morton::encode_2d(6u8, 12u8);
+/// assert_eq!(morton::add_2d(a, b), morton::encode_2d(9u8, 1u8));
+/// ```
+#[inline]
+pub fn add_2d<T: Word>(a: T, b: T) -> T {
+    let [x, y] = masks_2d::<T>();
+    masked_add(a, b, x) | masked_add(a, b, y)
+}
+
+/// Subtracts the 2D Morton index `b` from `a` dimension-wise.
+///
+/// Each coordinate wraps around on underflow.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let a = morton::encode_2d(3u8, 5u8);
+/// let b = morton::encode_2d(1u8, 6u8);
+/// assert_eq!(morton::sub_2d(a, b), morton::encode_2d(2u8, 15u8));
+/// ```
+#[inline]
+pub fn sub_2d<T: Word>(a: T, b: T) -> T {
+    let [x, y] = masks_2d::<T>();
+    masked_sub(a, b, x) | masked_sub(a, b, y)
+}
+
+/// Compares the coordinates of the 2D Morton indices `a` and `b`.
+///
+/// # Example
+/// ```
+/// use std::cmp::Ordering;
+/// use bitwise::word::morton;
+///
+/// let a = morton::encode_2d(3u8, 5u8);
+/// let b = morton::encode_2d(4u8, 5u8);
+/// assert_eq!(morton::cmp_2d(a, b), (Ordering::Less, Ordering::Equal));
+/// ```
+#[inline]
+pub fn cmp_2d<T: Word>(a: T, b: T) -> (Ordering, Ordering) {
+    let [x, y] = masks_2d::<T>();
+    (masked_cmp(a, b, x), masked_cmp(a, b, y))
+}
+
+/// Increments the coordinate `dimension` of the 3D Morton index `v` by one.
+///
+/// This is equivalent to decoding `v`, incrementing the coordinate, and
+/// encoding the result, but operates directly on the interleaved bits. The
+/// coordinate wraps around on overflow.
+///
+/// # Panics
+///
+/// If `dimension >= 3`.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = morton::encode_3d(1u32, 2u32, 3u32);
+/// assert_eq!(morton::increment_3d(v, 0), morton::encode_3d(2u32, 2u32, 3u32));
+/// assert_eq!(morton::increment_3d(v, 2), morton::encode_3d(1u32, 2u32, 4u32));
+/// ```
+#[inline]
+pub fn increment_3d<T: Word>(v: T, dimension: usize) -> T {
+    masked_increment(v, masks_3d::<T>()[dimension])
+}
+
+/// Decrements the coordinate `dimension` of the 3D Morton index `v` by one.
+///
+/// The coordinate wraps around on underflow.
+///
+/// # Panics
+///
+/// If `dimension >= 3`.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let v = morton::encode_3d(1u32, 2u32, 3u32);
+/// assert_eq!(morton::decrement_3d(v, 1), morton::encode_3d(1u32, 1u32, 3u32));
+/// assert_eq!(morton::decrement_3d(0u32, 0), morton::encode_3d(2047u32, 0, 0));
+/// ```
+#[inline]
+pub fn decrement_3d<T: Word>(v: T, dimension: usize) -> T {
+    masked_decrement(v, masks_3d::<T>()[dimension])
+}
+
+/// Adds the 3D Morton indices `a` and `b` dimension-wise.
+///
+/// Each coordinate wraps around on overflow.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let a = morton::encode_3d(1u32, 2u32, 3u32);
+/// let b = morton::encode_3d(10u32, 20u32, 30u32);
+/// assert_eq!(morton::add_3d(a, b), morton::encode_3d(11u32, 22u32, 33u32));
+/// ```
+#[inline]
+pub fn add_3d<T: Word>(a: T, b: T) -> T {
+    let [x, y, z] = masks_3d::<T>();
+    masked_add(a, b, x) | masked_add(a, b, y) | masked_add(a, b, z)
+}
+
+/// Subtracts the 3D Morton index `b` from `a` dimension-wise.
+///
+/// Each coordinate wraps around on underflow.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let a = morton::encode_3d(10u32, 20u32, 30u32);
+/// let b = morton::encode_3d(1u32, 2u32, 3u32);
+/// assert_eq!(morton::sub_3d(a, b), morton::encode_3d(9u32, 18u32, 27u32));
+/// ```
+#[inline]
+pub fn sub_3d<T: Word>(a: T, b: T) -> T {
+    let [x, y, z] = masks_3d::<T>();
+    masked_sub(a, b, x) | masked_sub(a, b, y) | masked_sub(a, b, z)
+}
+
+/// Compares the coordinates of the 3D Morton indices `a` and `b`.
+///
+/// # Example
+/// ```
+/// use std::cmp::Ordering;
+/// use bitwise::word::morton;
+///
+/// let a = morton::encode_3d(1u32, 2u32, 3u32);
+/// let b = morton::encode_3d(1u32, 1u32, 4u32);
+/// assert_eq!(morton::cmp_3d(a, b), (Ordering::Equal, Ordering::Greater, Ordering::Less));
+/// ```
+#[inline]
+pub fn cmp_3d<T: Word>(a: T, b: T) -> (Ordering, Ordering, Ordering) {
+    let [x, y, z] = masks_3d::<T>();
+    (masked_cmp(a, b, x), masked_cmp(a, b, y), masked_cmp(a, b, z))
+}
+
+/// Is the Morton index `v` of an `N`-dimensional z-Curve inside the box with
+/// lower corner `min` and upper corner `max` (both inclusive)?
+///
+/// The corners are Morton indices, e.g., obtained with `encode_2d`,
+/// `encode_3d`, or `encode_nd`.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// let min = morton::encode_2d(1u8, 1u8);
+/// let max = morton::encode_2d(2u8, 3u8);
+/// assert!(morton::in_box::<_, 2>(morton::encode_2d(2u8, 2u8), min, max));
+/// assert!(!morton::in_box::<_, 2>(morton::encode_2d(3u8, 2u8), min, max));
+/// ```
+#[inline]
+pub fn in_box<T: UnsignedWord, const N: usize>(v: T, min: T, max: T) -> bool {
+    Dimensions::<N>::MASKS.iter().all(|&mask| {
+        let mask = T::from_u128(mask);
+        let x = v & mask;
+        x >= min & mask && x <= max & mask
+    })
+}
+
+/// Bit `bit_position` and the mask of the bits of the same dimension below it.
+#[inline]
+fn bit_and_below<T: UnsignedWord, const N: usize>(bit_position: u32) -> (T, T) {
+    let bit = T::one() << T::from_u32(bit_position);
+    let dimension = T::from_u128(Dimensions::<N>::MASKS[bit_position as usize % N]);
+    (bit, dimension & (bit - T::one()))
+}
+
+/// Returns the smallest Morton index `>= v` of an `N`-dimensional z-Curve
+/// inside the box with lower corner `min` and upper corner `max` (both
+/// inclusive), if any.
+///
+/// When scanning a sorted sequence of Morton indices for the ones inside the
+/// box, `bigmin` gives the next index to continue the scan from after an
+/// index outside the box has been found (BIGMIN of Tropf and Herzog,
+/// "Multidimensional Range Search in Dynamically Balanced Trees", 1981).
+///
+/// # Panics
+///
+/// If `min` is not smaller than or equal to `max` in every dimension.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// // Box from (1, 1) to (2, 3):
+/// let min = morton::encode_2d(1u8, 1u8);
+/// let max = morton::encode_2d(2u8, 3u8);
+///
+/// // (3, 1) is outside the box, the next index inside the box is (1, 2):
+/// let v = morton::encode_2d(3u8, 1u8);
+/// assert_eq!(morton::bigmin::<_, 2>(v, min, max), Some(morton::encode_2d(1u8, 2u8)));
+/// assert_eq!(morton::bigmin::<_, 2>(min, min, max), Some(min));
+/// assert_eq!(morton::bigmin::<_, 2>(max + 1, min, max), None);
+/// ```
+#[inline]
+pub fn bigmin<T: UnsignedWord, const N: usize>(v: T, min: T, max: T) -> Option<T> {
+    debug_assert!(in_box::<T, N>(min, min, max) && in_box::<T, N>(max, min, max));
+    if in_box::<T, N>(v, min, max) {
+        return Some(v);
+    }
+    let (mut min, mut max) = (min, max);
+    let mut result = None;
+    for bit_position in (0..T::bit_size().to_u32()).rev() {
+        let (bit, below) = bit_and_below::<T, N>(bit_position);
+        match (v & bit != T::zero(), min & bit != T::zero(), max & bit != T::zero()) {
+            (false, false, true) => {
+                result = Some((min | bit) & !below);
+                max = (max & !bit) | below;
+            }
+            (false, true, true) => return Some(min),
+            (true, false, false) => return result,
+            (true, false, true) => min = (min | bit) & !below,
+            (false, true, false) | (true, true, false) => unreachable!(),
+            (false, false, false) | (true, true, true) => {}
+        }
+    }
+    result
+}
+
+/// Returns the largest Morton index `<= v` of an `N`-dimensional z-Curve
+/// inside the box with lower corner `min` and upper corner `max` (both
+/// inclusive), if any.
+///
+/// This is the counterpart of [`bigmin`](fn.bigmin.html) for scanning a
+/// sorted sequence of Morton indices backwards (LITMAX).
+///
+/// # Panics
+///
+/// If `min` is not smaller than or equal to `max` in every dimension.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// // Box from (1, 1) to (2, 3):
+/// let min = morton::encode_2d(1u8, 1u8);
+/// let max = morton::encode_2d(2u8, 3u8);
+///
+/// // (3, 1) is outside the box, the previous index inside the box is (2, 1):
+/// let v = morton::encode_2d(3u8, 1u8);
+/// assert_eq!(morton::litmax::<_, 2>(v, min, max), Some(morton::encode_2d(2u8, 1u8)));
+/// assert_eq!(morton::litmax::<_, 2>(max, min, max), Some(max));
+/// assert_eq!(morton::litmax::<_, 2>(min - 1, min, max), None);
+/// ```
+#[inline]
+pub fn litmax<T: UnsignedWord, const N: usize>(v: T, min: T, max: T) -> Option<T> {
+    debug_assert!(in_box::<T, N>(min, min, max) && in_box::<T, N>(max, min, max));
+    if in_box::<T, N>(v, min, max) {
+        return Some(v);
+    }
+    let (mut min, mut max) = (min, max);
+    let mut result = None;
+    for bit_position in (0..T::bit_size().to_u32()).rev() {
+        let (bit, below) = bit_and_below::<T, N>(bit_position);
+        match (v & bit != T::zero(), min & bit != T::zero(), max & bit != T::zero()) {
+            (false, false, true) => max = (max & !bit) | below,
+            (false, true, true) => return result,
+            (true, false, false) => return Some(max),
+            (true, false, true) => {
+                result = Some((max & !bit) | below);
+                min = (min | bit) & !below;
+            }
+            (false, true, false) | (true, true, false) => unreachable!(),
+            (false, false, false) | (true, true, true) => {}
+        }
+    }
+    result
+}
+
+/// Iterator over the contiguous intervals of Morton indices covered by a box.
+///
+/// Created by [`intervals`](fn.intervals.html).
+#[derive(Clone, Debug)]
+pub struct Intervals<T, const N: usize> {
+    next: Option<T>,
+    min: T,
+    max: T,
+}
+
+impl<T: UnsignedWord, const N: usize> Iterator for Intervals<T, N> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<(T, T)> {
+        let first = self.next?;
+        let mut v = first;
+        loop {
+            // Largest block of indices aligned at `v` inside the box. An
+            // aligned block of `2^k` indices is itself a box, so it is inside
+            // the query box if its last index is.
+            let mut k = v.trailing_zeros().to_u32();
+            let mut last = v | low_bits(k);
+            while !in_box::<T, N>(last, self.min, self.max) {
+                k -= 1;
+                last = v | low_bits(k);
+            }
+            if last == self.max {
+                self.next = None;
+                return Some((first, last));
+            }
+            v = last + T::one();
+            if !in_box::<T, N>(v, self.min, self.max) {
+                self.next = bigmin::<T, N>(v, self.min, self.max);
+                return Some((first, last));
+            }
+        }
+    }
+}
+
+/// Splits the box with lower corner `min` and upper corner `max` (both
+/// inclusive) of an `N`-dimensional z-Curve into the contiguous intervals of
+/// Morton indices that it covers.
+///
+/// The intervals `(first, last)` are inclusive, disjoint, non-adjacent, and
+/// are returned in increasing order.
+///
+/// # Panics
+///
+/// If `min` is not smaller than or equal to `max` in every dimension.
+///
+/// # Example
+/// ```
+/// use bitwise::word::morton;
+///
+/// // Box from (1, 1) to (2, 3):
+/// let min = morton::encode_2d(1u8, 1u8);
+/// let max = morton::encode_2d(2u8, 3u8);
+/// let intervals: Vec<_> = morton::intervals::<_, 2>(min, max).collect();
+/// assert_eq!(intervals, [(3, 3), (6, 6), (9, 9), (11, 12), (14, 14)]);
+///
+/// // Aligned boxes are a single interval:
+/// let min = morton::encode_3d(4u32, 0, 8);
+/// let max = morton::encode_3d(7u32, 3, 11);
+/// assert_eq!(morton::intervals::<_, 3>(min, max).count(), 1);
+/// ```
+#[inline]
+pub fn intervals<T: UnsignedWord, const N: usize>(min: T, max: T) -> Intervals<T, N> {
+    debug_assert!(in_box::<T, N>(min, min, max) && in_box::<T, N>(max, min, max));
+    Intervals {
+        next: Some(min),
+        min,
+        max,
+    }
+}
+
+#[doc(hidden)]
+pub mod testing_utils {
+    //! Testing utilities, used in tests and benchmarks (TODO: make optional)
+    use super::*;
+    use std::fmt::Debug;
+
+    pub trait RunnerFn {
+        fn run<T: Word + Debug>(&self, v: T);
+    }
+
+    pub struct Runner;
+    impl Runner {
+        pub fn run_u8<T: RunnerFn>(i: &T) {
+            (0u8..u8::MAX).map(|v| i.run(v)).count();
+        }
+        pub fn run_u16<T: RunnerFn>(i: &T) {
+            (0u16..u16::MAX).map(|v| i.run(v)).count();
+        }
+        pub fn run_u32<T: RunnerFn>(i: &T) {
+            let t0_min = u16::MAX as u32;
+            let t0_max = t0_min + 1000000;
+            (t0_min..t0_max).map(|v| i.run(v)).count();
+            let t1_max = u32::MAX;
+            let t1_min = t1_max - 1000000;
+            (t1_min..t1_max).map(|v| i.run(v)).count();
+        }
+
+        pub fn run_u64<T: RunnerFn>(i: &T) {
+            let t0_min = u32::MAX as u64;
+            let t0_max = t0_min + 1000000;
+            (t0_min..t0_max).map(|v| i.run(v)).count();
+            let t1_max = u64::MAX;
+            let t1_min = t1_max - 1000000;
+            (t1_min..t1_max).map(|v| i.run(v)).count();
+
+        }
+
+        pub fn run_u128<T: RunnerFn>(i: &T) {
+            let t0_min = u64::MAX as u128;
+            let t0_max = t0_min + 100000;
+            (t0_min..t0_max).map(|v| i.run(v)).count();
+            let t1_max = u128::MAX;
+            let t1_min = t1_max - 100000;
+            (t1_min..t1_max).map(|v| i.run(v)).count();
+        }
+
+        pub fn run<T: RunnerFn>(i: T) {
+            Runner::run_u8(&i);
+            Runner::run_u16(&i);
+            Runner::run_u32(&i);
+            Runner::run_u64(&i);
+            Runner::run_u128(&i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::testing_utils::*;
+    use std::fmt::Debug;
+    use quickcheck::QuickCheck;
+    use test_util::random;
+
+    struct BMI2Invariant;
+
+    impl RunnerFn for BMI2Invariant {
+        fn run<T: Word + Debug>(&self, v: T) {
+            {
+                // 2D:
+                let (x, y) = bmi2::decode_2d::<T, _>(v);
+                let vs = bmi2::encode_2d::<T, _>(x, y);
+                assert_eq!(vs, v);
+            }
+            {
+                // 3D:
+                let (x, y, z) = bmi2::decode_3d::<T, _>(v);
+                let vs = bmi2::encode_3d::<T, _>(x, y, z);
+                assert_eq!(vs, v);
+            }
+        }
+    }
+
+    #[test]
+    fn bmi2_invariant() {
+        Runner::run(BMI2Invariant {});
+    }
+
+    struct BitmaskInvariant;
 
-    #[test]
-    fn bmi2_invariant() {
-        Runner::run(BMI2Invariant {});
-    }
-
-    struct BitmaskInvariant;
-
     impl RunnerFn for BitmaskInvariant {
         fn run<T: Word + Debug>(&self, v: T) {
             {
@@ -869,6 +2156,7 @@ mod tests {
                 let v = match T::bit_size().to_u8() {
                     32 => v.clear_bits_geq(30u8),
                     64 => v.clear_bits_geq(63u8),
+                    128 => v.clear_bits_geq(126u8),
                     _ => v,
                 };
 
@@ -899,4 +2187,373 @@ mod tests {
     fn cmp_impls() {
         Runner::run(CmpImpls {});
     }
+
+    fn check_nd<T: Word + Debug, const N: usize>(v: T) {
+        let c_bmi2 = bmi2::decode_nd::<T, T, N>(v);
+        let c_mb = bitmask::decode_nd::<T, T, N>(v);
+        assert_eq!(c_bmi2, c_mb);
+        assert_eq!(decode_nd::<T, N>(v), c_bmi2);
+
+        assert_eq!(bmi2::encode_nd::<T, T, N>(c_bmi2), v);
+        assert_eq!(bitmask::encode_nd::<T, T, N>(c_bmi2), v);
+        assert_eq!(encode_nd(c_bmi2), v);
+    }
+
+    struct NdImpls;
+
+    impl RunnerFn for NdImpls {
+        fn run<T: Word + Debug>(&self, v: T) {
+            check_nd::<T, 1>(v);
+            check_nd::<T, 2>(v);
+            check_nd::<T, 3>(v);
+            check_nd::<T, 4>(v);
+            check_nd::<T, 5>(v);
+            check_nd::<T, 6>(v);
+            check_nd::<T, 7>(v);
+            check_nd::<T, 8>(v);
+
+            assert_eq!(bmi2::decode_nd::<T, T, 2>(v),
+                       { let (x, y) = bmi2::decode_2d(v); [x, y] });
+            assert_eq!(bmi2::decode_nd::<T, T, 3>(v),
+                       { let (x, y, z) = bmi2::decode_3d(v); [x, y, z] });
+        }
+    }
+
+    #[test]
+    fn nd_impls() {
+        Runner::run_u8(&NdImpls {});
+        Runner::run_u16(&NdImpls {});
+    }
+
+    #[test]
+    fn prop_nd_impls() {
+        fn inner(a: u64, b: u64) -> bool {
+            let v = ((a as u128) << 64) | b as u128;
+            NdImpls {}.run(a as u32);
+            NdImpls {}.run(a);
+            NdImpls {}.run(v);
+            true
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+
+    #[test]
+    fn nd_mixed_widths() {
+        // Four 32-bit coordinates fill a 128-bit index:
+        let c = [0xFEDC_BA98u32, 0x7654_3210, 0x0123_4567, 0x89AB_CDEF];
+        let v: u128 = bmi2::encode_nd(c);
+        assert_eq!(v, bitmask::encode_nd(c));
+        assert_eq!(bmi2::decode_nd::<u32, _, 4>(v), c);
+        assert_eq!(bitmask::decode_nd::<u32, _, 4>(v), c);
+
+        // Bits of the coordinates that do not fit into the index are dropped:
+        let v: u8 = bitmask::encode_nd([0xFFu8; 3]);
+        assert_eq!(v, 0xFF);
+        assert_eq!(bitmask::decode_nd::<u8, _, 3>(v), [0b111, 0b111, 0b11]);
+    }
+
+    /// Checks the neighbor arithmetic of the backend `$m` against decoding,
+    /// modifying, and encoding the coordinates.
+    macro_rules! check_neighbors {
+        ($v:expr, $m:tt) => ({
+            let one = T::one();
+            let w = $v.rotate_left(5);
+            {
+                // 2D:
+                let (mx, my): (T, T) = $m::decode_2d(!T::zero());
+                let (x, y): (T, T) = $m::decode_2d($v);
+                let v: T = $m::encode_2d(x, y);
+                let (wx, wy): (T, T) = $m::decode_2d(w);
+                let e = |x: T, y: T| -> T { $m::encode_2d(x & mx, y & my) };
+                assert_eq!($m::increment_2d(v, 0), e(x.wrapping_add(one), y));
+                assert_eq!($m::increment_2d(v, 1), e(x, y.wrapping_add(one)));
+                assert_eq!($m::decrement_2d(v, 0), e(x.wrapping_sub(one), y));
+                assert_eq!($m::decrement_2d(v, 1), e(x, y.wrapping_sub(one)));
+                assert_eq!($m::add_2d(v, w), e(x.wrapping_add(wx), y.wrapping_add(wy)));
+                assert_eq!($m::sub_2d(v, w), e(x.wrapping_sub(wx), y.wrapping_sub(wy)));
+                assert_eq!($m::cmp_2d(v, w),
+                           (x.partial_cmp(&wx).unwrap(), y.partial_cmp(&wy).unwrap()));
+            }
+            {
+                // 3D:
+                let (mx, my, mz): (T, T, T) = $m::decode_3d(!T::zero());
+                let (x, y, z): (T, T, T) = $m::decode_3d($v);
+                let v: T = $m::encode_3d(x, y, z);
+                let (wx, wy, wz): (T, T, T) = $m::decode_3d(w);
+                let e = |x: T, y: T, z: T| -> T { $m::encode_3d(x & mx, y & my, z & mz) };
+                assert_eq!($m::increment_3d(v, 0), e(x.wrapping_add(one), y, z));
+                assert_eq!($m::increment_3d(v, 1), e(x, y.wrapping_add(one), z));
+                assert_eq!($m::increment_3d(v, 2), e(x, y, z.wrapping_add(one)));
+                assert_eq!($m::decrement_3d(v, 0), e(x.wrapping_sub(one), y, z));
+                assert_eq!($m::decrement_3d(v, 1), e(x, y.wrapping_sub(one), z));
+                assert_eq!($m::decrement_3d(v, 2), e(x, y, z.wrapping_sub(one)));
+                assert_eq!($m::add_3d(v, w),
+                           e(x.wrapping_add(wx), y.wrapping_add(wy), z.wrapping_add(wz)));
+                assert_eq!($m::sub_3d(v, w),
+                           e(x.wrapping_sub(wx), y.wrapping_sub(wy), z.wrapping_sub(wz)));
+                assert_eq!($m::cmp_3d(v, w),
+                           (x.partial_cmp(&wx).unwrap(),
+                            y.partial_cmp(&wy).unwrap(),
+                            z.partial_cmp(&wz).unwrap()));
+            }
+        })
+    }
+
+    struct Neighbors;
+
+    impl RunnerFn for Neighbors {
+        fn run<T: Word + Debug>(&self, v: T) {
+            check_neighbors!(v, super);
+            check_neighbors!(v, bmi2);
+            check_neighbors!(v, bitmask);
+            check_neighbors!(v, lut);
+        }
+    }
+
+    #[test]
+    fn neighbors() {
+        Runner::run_u8(&Neighbors {});
+        Runner::run_u16(&Neighbors {});
+    }
+
+    #[test]
+    fn prop_neighbors() {
+        fn inner(a: u64, b: u64) -> bool {
+            // Also check the complements to cover the higher bits:
+            let v = ((a as u128) << 64) | b as u128;
+            for &v in &[v, !v] {
+                Neighbors {}.run(v as u32);
+                Neighbors {}.run(v as u64);
+                Neighbors {}.run(v);
+            }
+            true
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+
+    /// Checks `bigmin`, `litmax`, and `intervals` against a linear scan over
+    /// all Morton indices of `T`.
+    fn check_box<T: UnsignedWord + Debug, const N: usize>(a: [T; N], b: [T; N]) {
+        // Drop the bits of the corners that do not fit into the index:
+        let mut lo = decode_nd::<T, N>(encode_nd(a));
+        let mut hi = decode_nd::<T, N>(encode_nd(b));
+        for d in 0..N {
+            if lo[d] > hi[d] {
+                std::mem::swap(&mut lo[d], &mut hi[d]);
+            }
+        }
+        let (min, max) = (encode_nd(lo), encode_nd(hi));
+        let inside = |v: T| {
+            let c = decode_nd::<T, N>(v);
+            (0..N).all(|d| lo[d] <= c[d] && c[d] <= hi[d])
+        };
+
+        let mut intervals = intervals::<T, N>(min, max);
+        let mut first = None;
+        let mut previous = None;
+        let mut v = T::zero();
+        loop {
+            assert_eq!(in_box::<T, N>(v, min, max), inside(v));
+            if inside(v) {
+                first = first.or(Some(v));
+                previous = Some(v);
+            } else if let Some(f) = first.take() {
+                assert_eq!(intervals.next(), Some((f, v - T::one())));
+            }
+            assert_eq!(litmax::<T, N>(v, min, max), previous);
+            if v == !T::zero() {
+                break;
+            }
+            v = v + T::one();
+        }
+        if let Some(f) = first {
+            assert_eq!(intervals.next(), Some((f, v)));
+        }
+        assert_eq!(intervals.next(), None);
+        let mut next = None;
+        loop {
+            if inside(v) {
+                next = Some(v);
+            }
+            assert_eq!(bigmin::<T, N>(v, min, max), next);
+            if v == T::zero() {
+                break;
+            }
+            v = v - T::one();
+        }
+    }
+
+    #[test]
+    fn prop_box_u8() {
+        fn inner(a: (u8, u8, u8, u8), b: (u8, u8, u8, u8)) -> bool {
+            check_box::<u8, 1>([a.0], [b.0]);
+            check_box::<u8, 2>([a.0, a.1], [b.0, b.1]);
+            check_box::<u8, 3>([a.0, a.1, a.2], [b.0, b.1, b.2]);
+            check_box::<u8, 4>([a.0, a.1, a.2, a.3], [b.0, b.1, b.2, b.3]);
+            true
+        }
+        QuickCheck::new().quickcheck(inner as fn((u8, u8, u8, u8), (u8, u8, u8, u8)) -> bool);
+    }
+
+    #[test]
+    fn prop_box_u16() {
+        fn inner(a: (u16, u16, u16), b: (u16, u16, u16)) -> bool {
+            check_box::<u16, 2>([a.0, a.1], [b.0, b.1]);
+            check_box::<u16, 3>([a.0, a.1, a.2], [b.0, b.1, b.2]);
+            true
+        }
+        QuickCheck::new()
+            .tests(10)
+            .quickcheck(inner as fn((u16, u16, u16), (u16, u16, u16)) -> bool);
+    }
+
+    #[test]
+    fn box_u64() {
+        // Large boxes are split into few intervals:
+        let min = encode_2d(0x1000u64, 0x2000u64);
+        let max = encode_2d(0x1FFFu64, 0x2FFFu64);
+        let mut i = intervals::<_, 2>(min, max);
+        assert_eq!((i.next(), i.next()), (Some((min, max)), None));
+
+        let min = encode_3d(1u64, 1, 1);
+        let max = encode_3d(1u64 << 20, 1 << 20, 1 << 20);
+        let mut count = 0;
+        let mut previous: Option<(u64, u64)> = None;
+        for (first, last) in intervals::<_, 3>(min, max).take(1000) {
+            assert!(first <= last && in_box::<_, 3>(first, min, max));
+            assert!(in_box::<_, 3>(last, min, max));
+            assert!(!in_box::<_, 3>(last + 1, min, max));
+            if let Some((_, previous_last)) = previous {
+                assert!(!in_box::<_, 3>(first - 1, min, max));
+                assert_eq!(bigmin::<_, 3>(previous_last + 1, min, max), Some(first));
+                assert_eq!(litmax::<_, 3>(first - 1, min, max), Some(previous_last));
+            }
+            previous = Some((first, last));
+            count += 1;
+        }
+        assert_eq!(count, 1000);
+    }
+
+    fn check_batch<T: Word>(seed: u64) -> bool {
+        let mut v = [T::zero(); 16];
+        for (v, r) in v.iter_mut().zip(random(seed)) {
+            *v = T::from_u128(r);
+        }
+
+        let (mut x, mut y, mut z) = ([T::zero(); 16], [T::zero(); 16], [T::zero(); 16]);
+        let mut points_2d = [[T::zero(); 2]; 16];
+        let mut points_3d = [[T::zero(); 3]; 16];
+        let mut w = [T::zero(); 16];
+
+        decode_2d_slice(&v, &mut x, &mut y);
+        decode_2d_points(&v, &mut points_2d);
+        for i in 0..16 {
+            if (x[i], y[i]) != decode_2d(v[i]) || points_2d[i] != [x[i], y[i]] {
+                return false;
+            }
+        }
+        encode_2d_slice(&x, &y, &mut w);
+        if w != v {
+            return false;
+        }
+        encode_2d_points(&points_2d, &mut w);
+        if w != v {
+            return false;
+        }
+
+        decode_3d_slice(&v, &mut x, &mut y, &mut z);
+        decode_3d_points(&v, &mut points_3d);
+        for i in 0..16 {
+            if (x[i], y[i], z[i]) != decode_3d(v[i]) || points_3d[i] != [x[i], y[i], z[i]] {
+                return false;
+            }
+        }
+        encode_3d_slice(&x, &y, &z, &mut w);
+        if w != v {
+            return false;
+        }
+        encode_3d_points(&points_3d, &mut w);
+        w == v
+    }
+
+    #[test]
+    fn prop_batch() {
+        fn inner(seed: u64) -> bool {
+            check_batch::<u8>(seed) && check_batch::<u16>(seed) && check_batch::<u32>(seed) &&
+            check_batch::<u64>(seed) && check_batch::<u128>(seed) &&
+            check_batch::<i32>(seed)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn batch_length_mismatch() {
+        encode_2d_slice(&[1u32, 2], &[3u32], &mut [0u32; 2]);
+    }
+
+    #[test]
+    fn interleaving_isotropic() {
+        let spec = Interleaving::<u32, 3>::new([11, 11, 10]);
+        assert_eq!(spec.masks(), masks_3d::<u32>());
+        let spec = Interleaving::<u64, 2>::new([32, 32]);
+        assert_eq!(spec.masks(), masks_2d::<u64>());
+        let mut sequence = [0; 128];
+        for (i, d) in sequence.iter_mut().enumerate() {
+            *d = i % 5;
+        }
+        let spec = Interleaving::<u128, 5>::from_sequence(&sequence);
+        assert_eq!(spec.masks(), Dimensions::<5>::MASKS);
+        assert_eq!(spec, Interleaving::new([26, 26, 26, 25, 25]));
+    }
+
+    #[test]
+    fn prop_interleaving() {
+        fn inner(x: u64, y: u64, z: u64, bx: u8, by: u8, bz: u8) -> bool {
+            let bits = [bx as u32 % 25, by as u32 % 25, bz as u32 % 15];
+            let spec = Interleaving::<u64, 3>::new(bits);
+            let masks = spec.masks();
+            let c = [x, y, z];
+            let v = spec.encode(c);
+            let d = spec.decode(v);
+            spec.bits() == bits &&
+            masks[0] & masks[1] == 0 && masks[1] & masks[2] == 0 && masks[0] & masks[2] == 0 &&
+            (0..3).all(|i| d[i] == c[i] & low_bits::<u64>(bits[i])) &&
+            spec.encode(d) == v &&
+            Interleaving::from_masks(masks) == spec &&
+            spec.encode(spec.decode(!v)) == !v & (masks[0] | masks[1] | masks[2])
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u64, u8, u8, u8) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn interleaving_budget() {
+        Interleaving::<u16, 2>::new([9, 8]);
+    }
+
+    #[test]
+    fn wide_index() {
+        // 2D: 64-bit coordinates into a 128-bit index.
+        let (x, y) = (0xFEDC_BA98_7654_3210u64, 0x0123_4567_89AB_CDEFu64);
+        let v: u128 = bmi2::encode_2d(x, y);
+        assert_eq!(v, encode_2d(x as u128, y as u128));
+        assert_eq!(v, bitmask::encode_2d(x, y));
+        assert_eq!(v, lut::encode_2d(x, y));
+        assert_eq!(bmi2::decode_2d::<u64, _>(v), (x, y));
+        assert_eq!(bitmask::decode_2d::<u64, _>(v), (x, y));
+        assert_eq!(lut::decode_2d::<u64, _>(v), (x, y));
+        assert_eq!(decode_2d(v), (x as u128, y as u128));
+
+        // 3D: 42-bit coordinates into a 128-bit index.
+        let (x, y, z) = (0x3FF_0F0F_0F0Fu64, 0x2AA_AAAA_AAAAu64, 0x155_5555_5555u64);
+        let v: u128 = bmi2::encode_3d(x, y, z);
+        assert_eq!(v, encode_3d(x as u128, y as u128, z as u128));
+        assert_eq!(v, bitmask::encode_3d(x, y, z));
+        assert_eq!(v, lut::encode_3d(x, y, z));
+        assert_eq!(bmi2::decode_3d::<u64, _>(v), (x, y, z));
+        assert_eq!(bitmask::decode_3d::<u64, _>(v), (x, y, z));
+        assert_eq!(lut::decode_3d::<u64, _>(v), (x, y, z));
+        assert_eq!(decode_3d(v), (x as u128, y as u128, z as u128));
+    }
 }
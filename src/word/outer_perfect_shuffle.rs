@@ -17,33 +17,39 @@ use word::Word;
 ///
 /// assert_eq!(n.outer_perfect_shuffle(), s);
 /// assert_eq!(outer_perfect_shuffle(n), s);
+///
+/// let w = (u64::MAX as u128) << 64;
+/// assert_eq!(w.outer_perfect_shuffle(), 0xAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAu128);
 /// ```
 #[inline]
 pub fn outer_perfect_shuffle<T: Word>(x: T) -> T {
     let mut x = x;
     let s = T::byte_size();
+    if s > T::from_u8(8) {
+        let t = (x ^ (x >> T::from_u8(32))) & T::from_u128(0x0000000000000000FFFFFFFF00000000u128);
+        x = x ^ t ^ (t << T::from_u8(32));
+    }
     if s > T::from_u8(4) {
-        let t = (x ^ (x >> T::from_u8(16))) & T::from_u64(0x00000000FFFF0000u64);
+        let t = (x ^ (x >> T::from_u8(16))) & T::from_u128(0x00000000FFFF000000000000FFFF0000u128);
         x = x ^ t ^ (t << T::from_u8(16));
     }
     if s > T::from_u8(2) {
-        let t = (x ^ (x >> T::from_u8(8))) & T::from_u64(0x0000FF000000FF00u64);
+        let t = (x ^ (x >> T::from_u8(8))) & T::from_u128(0x0000FF000000FF000000FF000000FF00u128);
         x = x ^ t ^ (t << T::from_u8(8));
     }
     if s > T::one() {
-        let t = (x ^ (x >> T::from_u8(4))) & T::from_u64(0x00F000F000F000F0u64);
+        let t = (x ^ (x >> T::from_u8(4))) & T::from_u128(0x00F000F000F000F000F000F000F000F0u128);
         x = x ^ t ^ (t << T::from_u8(4));
     }
-    let t = (x ^ (x >> T::from_u8(2))) & T::from_u64(0x0C0C0C0C0C0C0C0Cu64);
+    let t = (x ^ (x >> T::from_u8(2))) & T::from_u128(0x0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0Cu128);
     x = x ^ t ^ (t << T::from_u8(2));
-    let t = (x ^ (x >> T::one())) & T::from_u64(0x2222222222222222u64);
+    let t = (x ^ (x >> T::one())) & T::from_u128(0x22222222222222222222222222222222u128);
     x = x ^ t ^ (t << T::one());
     x
 }
 
 /// Method version of [`outer_perfect_shuffle`](fn.outer_perfect_shuffle.html).
 pub trait OuterPerfectShuffle {
-    #[inline]
     fn outer_perfect_shuffle(self) -> Self;
 }
 
@@ -32,8 +32,7 @@ pub fn parallel_bits_extract<T: Word, U: Word>(x: T, mask: U) -> T {
 
 /// Method version of [`parallel_bits_extract`](fn.parallel_bits_extract.html).
 pub trait ParallelBitsExtract {
-    #[inline]
-    fn parallel_bits_extract<U: Word>(self, U) -> Self;
+    fn parallel_bits_extract<U: Word>(self, mask: U) -> Self;
 }
 
 impl<T: Word> ParallelBitsExtract for T {
@@ -42,3 +41,44 @@ impl<T: Word> ParallelBitsExtract for T {
         parallel_bits_extract(self, mask)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use word::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn prop_full_mask() {
+        // A mask with every bit set extracts `x` unchanged, also for the
+        // 128-bit words, which extract from each 64-bit half:
+        fn inner(x: u64, y: u64) -> bool {
+            let z = (x as u128) << 64 | y as u128;
+            x.parallel_bits_extract(!0u64) == x &&
+            (x as u8).parallel_bits_extract(!0u8) == x as u8 &&
+            z.parallel_bits_extract(!0u128) == z &&
+            z.parallel_bits_extract(!0u64 as u128) == y as u128 &&
+            z.parallel_bits_extract(!0u128 << 64) == x as u128
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+
+    #[test]
+    fn prop_signed() {
+        assert_eq!(i8::MIN.parallel_bits_extract(i8::MIN), 1);
+        assert_eq!((-1i64).parallel_bits_extract(-0x10), 0x0FFF_FFFF_FFFF_FFFF);
+        // Signed words extract the same bits as the unsigned words of the
+        // same width, also when the sign bit of `x` or of the mask is set:
+        fn inner(x: u64, mask: u64) -> bool {
+            (x as i8).parallel_bits_extract(mask as i8) ==
+                (x as u8).parallel_bits_extract(mask as u8) as i8 &&
+            (x as i16).parallel_bits_extract(mask as i16) ==
+                (x as u16).parallel_bits_extract(mask as u16) as i16 &&
+            (x as i32).parallel_bits_extract(mask as i32) ==
+                (x as u32).parallel_bits_extract(mask as u32) as i32 &&
+            (x as i64).parallel_bits_extract(mask as i64) == x.parallel_bits_extract(mask) as i64 &&
+            (x as isize).parallel_bits_extract(mask as isize) ==
+                (x as usize).parallel_bits_extract(mask as usize) as isize
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+}
@@ -29,7 +29,6 @@ pub fn parity<T: Word>(x: T) -> T {
 
 /// Method version of [`parity`](fn.parity.html).
 pub trait Parity {
-    #[inline]
     fn parity(self) -> Self;
 }
 
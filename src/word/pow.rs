@@ -20,7 +20,7 @@ pub fn pow<T: Word, U: UnsignedWord>(x: T, exp: U) -> T {
 // one in std:
 /*
 pub trait Pow {
-  #[inline] fn pow<U: UnsignedWord>(self, n: U) -> Self;
+  fn pow<U: UnsignedWord>(self, n: U) -> Self;
 }
 
 impl<T: Word> Pow for T {
@@ -0,0 +1,37 @@
+use word::{Word, prefix_or};
+
+/// Replaces each bit of `x` by the AND of itself and all the less
+/// significant bits of `x`.
+///
+/// Keeps the trailing ones of `x`; this is
+/// [`mask_trailing_ones`](fn.mask_trailing_ones.html).
+///
+/// # Keywords:
+///
+/// Prefix AND, scan.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(prefix_and(0b1111_1111u8), 0b1111_1111);
+/// assert_eq!(prefix_and(0b0010_0111u8), 0b0000_0111);
+/// assert_eq!(0b1000_0000u8.prefix_and(), 0b0000_0000);
+/// ```
+#[inline]
+pub fn prefix_and<T: Word>(x: T) -> T {
+    !prefix_or(!x)
+}
+
+/// Method version of [`prefix_and`](fn.prefix_and.html).
+pub trait PrefixAnd {
+    fn prefix_and(self) -> Self;
+}
+
+impl<T: Word> PrefixAnd for T {
+    #[inline]
+    fn prefix_and(self) -> Self {
+        prefix_and(self)
+    }
+}
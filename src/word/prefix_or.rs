@@ -0,0 +1,36 @@
+use word::Word;
+
+/// Replaces each bit of `x` by the OR of itself and all the less significant
+/// bits of `x`.
+///
+/// Sets all the bits above the least significant set bit of `x`.
+///
+/// # Keywords:
+///
+/// Prefix OR, scan, smear left.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(prefix_or(0b0000_0000u8), 0b0000_0000);
+/// assert_eq!(prefix_or(0b0010_0100u8), 0b1111_1100);
+/// assert_eq!(0b1000_0000u8.prefix_or(), 0b1000_0000);
+/// ```
+#[inline]
+pub fn prefix_or<T: Word>(x: T) -> T {
+    x | x.wrapping_neg()
+}
+
+/// Method version of [`prefix_or`](fn.prefix_or.html).
+pub trait PrefixOr {
+    fn prefix_or(self) -> Self;
+}
+
+impl<T: Word> PrefixOr for T {
+    #[inline]
+    fn prefix_or(self) -> Self {
+        prefix_or(self)
+    }
+}
@@ -0,0 +1,103 @@
+use word::Word;
+use cpu;
+
+/// Replaces each bit of `x` by the XOR of itself and all the less significant
+/// bits of `x`.
+///
+/// Bit `i` of the result is the parity of the bits `0..=i` of `x`. This is
+/// computed with a carry-less multiplication by `!0` if the `pclmulqdq`
+/// target feature is enabled (see the [`cpu`](../cpu/index.html) module),
+/// and with `log2(bit_size)` shift-XOR steps otherwise.
+///
+/// # Keywords:
+///
+/// Prefix XOR, prefix parity, scan, quote masks.
+///
+/// # Intrinsics:
+/// - PCLMULQDQ: `pclmulqdq(x, !0)`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(prefix_xor(0b0000_0000u8), 0b0000_0000);
+/// assert_eq!(prefix_xor(0b0000_0001u8), 0b1111_1111);
+/// assert_eq!(prefix_xor(0b0010_0100u8), 0b0001_1100);
+/// // The regions between pairs of quotes:
+/// assert_eq!(0b1001_0010_0100u16.prefix_xor(), 0b0111_0001_1100);
+/// ```
+#[inline]
+pub fn prefix_xor<T: Word>(x: T) -> T {
+    let bits = T::bit_size().to_u32();
+    if bits <= 64 {
+        if let Some(p) = cpu::clmul(x.to_unsigned().to_u64(), !0) {
+            return T::from_u64(p as u64);
+        }
+    } else if let (Some(low), Some(high)) = (cpu::clmul(x.to_u64(), !0),
+                                              cpu::clmul((x.to_u128() >> 64) as u64, !0)) {
+        let carry = if (low as u64) >> 63 == 0 { 0 } else { !0 };
+        return T::from_u128((((high as u64 ^ carry) as u128) << 64) | (low as u64) as u128);
+    }
+    let mut x = x;
+    let mut shift = 1;
+    while shift < bits {
+        x = x ^ (x << T::from_u32(shift));
+        shift *= 2;
+    }
+    x
+}
+
+/// Method version of [`prefix_xor`](fn.prefix_xor.html).
+pub trait PrefixXor {
+    fn prefix_xor(self) -> Self;
+}
+
+impl<T: Word> PrefixXor for T {
+    #[inline]
+    fn prefix_xor(self) -> Self {
+        prefix_xor(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use word::*;
+    use quickcheck::QuickCheck;
+
+    /// Applies `op` to the bits `0..=i` (`suffix == false`) or `i..bits` of
+    /// `x` for each bit `i`.
+    fn naive<F: Fn(bool, bool) -> bool>(x: u128, bits: u32, suffix: bool, op: F) -> u128 {
+        (0..bits).filter(|&i| {
+            let range = if suffix { i..bits } else { 0..i + 1 };
+            range.map(|j| x & (1 << j) != 0).fold(None, |acc, b| {
+                Some(acc.map_or(b, |a| op(a, b)))
+            }).unwrap()
+        }).fold(0, |acc, i| acc | (1 << i))
+    }
+
+    fn check<T: Word>(x: T) -> bool {
+        let bits = T::bit_size().to_u32();
+        let y = x.to_unsigned().to_u128();
+        let eq = |r: T, suffix: bool, op: fn(bool, bool) -> bool| {
+            r.to_unsigned().to_u128() == naive(y, bits, suffix, op)
+        };
+        eq(x.prefix_xor(), false, |a, b| a ^ b) && eq(x.suffix_xor(), true, |a, b| a ^ b) &&
+        eq(x.prefix_or(), false, |a, b| a | b) && eq(x.suffix_or(), true, |a, b| a | b) &&
+        eq(x.prefix_and(), false, |a, b| a & b) && eq(x.suffix_and(), true, |a, b| a & b)
+    }
+
+    #[test]
+    fn prop_prefix_suffix() {
+        fn inner(x: u64, y: u64, shift: u8) -> bool {
+            let z = (x as u128) << 64 | y as u128;
+            [x, !x, x >> (shift % 64), !(x >> (shift % 64)), x << (shift % 64)].iter()
+                .all(|&x| {
+                    check(x) && check(x as i64) && check(x as u8) && check(x as i8) &&
+                    check(x as u16) && check(x as i16) && check(x as u32) && check(x as i32)
+                }) &&
+            check(z) && check(z as i128) && check(z >> (shift % 128)) && check(!z >> shift)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u8) -> bool);
+    }
+}
@@ -4,7 +4,7 @@ use word::is_pow2::*;
 /// Reverses groups of bits within each subword of `x`.
 ///
 /// * `group_bit_size` - The size (in bits) of the groups of bits to be
-/// reversed.
+///   reversed.
 /// * `no_subwords` - The number of subwords in `x`.
 ///
 /// The word `x` is divided into `no_subwords`. The bit groups of size
@@ -52,6 +52,10 @@ use word::is_pow2::*;
 ///
 /// // Reverse bits within each 8 2-bit subwords of `n`:
 /// assert_eq!(n.reverse_bit_groups(1u32, 8u32), 0b1010_1110_0101_1010u16);
+///
+/// // Reverse the two 64-bit halves of a 128-bit word:
+/// let w = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128;
+/// assert_eq!(w.reverse_bit_groups(64u32, 1u32), 0xFEDC_BA98_7654_3210_0123_4567_89AB_CDEFu128);
 /// ```
 #[inline]
 pub fn reverse_bit_groups<T: Word, U: UnsignedWord>(x: T, group_bit_size: U, no_subwords: U) -> T {
@@ -59,12 +63,10 @@ pub fn reverse_bit_groups<T: Word, U: UnsignedWord>(x: T, group_bit_size: U, no_
     // is released under the MIT's License here:
     // https://github.com/fmatthew5876/stdcxx-bitops
 
-    type TU<U> where U: Word = U::Unsigned; // TODO: fix warning ?
-
     debug_assert!(group_bit_size.is_pow2());
     debug_assert!(no_subwords.is_pow2());
 
-    let mut y: TU<T> = x.to();
+    let mut y: T::Unsigned = x.to();
     let width = T::byte_size();
     let subword_bit_size = T::bit_size() / no_subwords.to();
     debug_assert!(subword_bit_size.to_u32() % group_bit_size.to_u32() == 0);
@@ -75,14 +77,14 @@ pub fn reverse_bit_groups<T: Word, U: UnsignedWord>(x: T, group_bit_size: U, no_
         };
 
         up0(1.to(),
-            0x5555555555555555u64.to(),
-            0xAAAAAAAAAAAAAAAAu64.to());
+            0x55555555555555555555555555555555u128.to(),
+            0xAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAu128.to());
         up0(2.to(),
-            0x3333333333333333u64.to(),
-            0xCCCCCCCCCCCCCCCCu64.to());
+            0x33333333333333333333333333333333u128.to(),
+            0xCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCu128.to());
         up0(4.to(),
-            0x0F0F0F0F0F0F0F0Fu64.to(),
-            0xF0F0F0F0F0F0F0F0u64.to());
+            0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0Fu128.to(),
+            0xF0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0u128.to());
     }
 
     {
@@ -92,23 +94,26 @@ pub fn reverse_bit_groups<T: Word, U: UnsignedWord>(x: T, group_bit_size: U, no_
 
         up1(1.to(),
             8.to(),
-            0x00FF00FF00FF00FFu64.to(),
-            0xFF00FF00FF00FF00u64.to());
+            0x00FF00FF00FF00FF00FF00FF00FF00FFu128.to(),
+            0xFF00FF00FF00FF00FF00FF00FF00FF00u128.to());
         up1(2.to(),
             16.to(),
-            0x0000FFFF0000FFFFu64.to(),
-            0xFFFF0000FFFF0000u64.to());
+            0x0000FFFF0000FFFF0000FFFF0000FFFFu128.to(),
+            0xFFFF0000FFFF0000FFFF0000FFFF0000u128.to());
         up1(4.to(),
             32.to(),
-            0x00000000FFFFFFFFu64.to(),
-            0xFFFFFFFF00000000u64.to());
+            0x00000000FFFFFFFF00000000FFFFFFFFu128.to(),
+            0xFFFFFFFF00000000FFFFFFFF00000000u128.to());
+        up1(8.to(),
+            64.to(),
+            0x0000000000000000FFFFFFFFFFFFFFFFu128.to(),
+            0xFFFFFFFFFFFFFFFF0000000000000000u128.to());
     }
     y.to()
 }
 
 /// Method version of [`reverse_bit_groups`](fn.reverse_bit_groups.html).
 pub trait ReverseBitGroups: Word {
-    #[inline]
     fn reverse_bit_groups<T: UnsignedWord>(self, x: T, y: T) -> Self;
 }
 
@@ -18,7 +18,6 @@ pub fn reverse_bit_nibbles<T: Word>(x: T) -> T {
 
 /// Method version of [`reverse_bit_nibbles`](fn.reverse_bit_nibbles.html).
 pub trait ReverseBitNibbles: Word {
-    #[inline]
     fn reverse_bit_nibbles(self) -> Self;
 }
 
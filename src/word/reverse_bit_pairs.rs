@@ -18,7 +18,6 @@ pub fn reverse_bit_pairs<T: Word>(x: T) -> T {
 
 /// Method version of [`reverse_bit_pairs`](fn.reverse_bit_pairs.html).
 pub trait ReverseBitPairs: Word {
-    #[inline]
     fn reverse_bit_pairs(self) -> Self;
 }
 
@@ -12,6 +12,7 @@ use word::Word;
 ///
 /// assert_eq!(0b1011_0010u8.reverse_bits(), 0b0100_1101u8);
 /// assert_eq!(reverse_bits(0b1011_0010_1010_1001u16), 0b1001_0101_0100_1101u16);
+/// assert_eq!(reverse_bits(0b0110u128), 0b0110u128 << 124);
 /// ```
 #[inline]
 pub fn reverse_bits<T: Word>(x: T) -> T {
@@ -20,7 +21,6 @@ pub fn reverse_bits<T: Word>(x: T) -> T {
 
 /// Method version of [`reverse_bits`](fn.reverse_bits.html).
 pub trait ReverseBits: Word {
-    #[inline]
     fn reverse_bits(self) -> Self;
 }
 
@@ -4,7 +4,7 @@ use word::reverse_bit_groups::*;
 /// Reverses groups of bytes within each subword of `x`
 ///
 /// * `group_byte_size` - The size (in bytes) of the groups of bytes to be
-/// reversed.
+///   reversed.
 /// * `no_subwords` - The number of subwords in `x`.
 ///
 /// The word `x` is divided into `no_subwords`. The byte groups of size
@@ -36,7 +36,6 @@ pub fn reverse_byte_groups<T: Word, U: UnsignedWord>(x: T,
 
 /// Method version of [`reverse_byte_groups`](fn.reverse_byte_groups.html).
 pub trait ReverseByteGroups: Word {
-    #[inline]
     fn reverse_byte_groups<U: UnsignedWord>(self, group_byte_size: U, no_subwords: U) -> Self;
 }
 
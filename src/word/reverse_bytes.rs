@@ -24,7 +24,6 @@ pub fn reverse_bytes<T: Word>(x: T) -> T {
 
 /// Method version of [`reverse_bytes`](fn.reverse_bytes.html).
 pub trait ReverseBytes: Word {
-    #[inline]
     fn reverse_bytes(self) -> Self;
 }
 
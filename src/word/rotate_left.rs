@@ -26,7 +26,6 @@ pub fn rotate_left<T: Word, U: UnsignedWord>(x: T, n: U) -> T {
 
 /// Method version of [`rotate_left`](fn.rotate_left.html).
 pub trait RotateLeft {
-    #[inline]
     fn rotate_left<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -29,7 +29,6 @@ pub fn rotate_right<T: Word, U: UnsignedWord>(x: T, n: U) -> T {
 
 /// Method version of [`rotate_right`](fn.rotate_right.html).
 pub trait RotateRight {
-    #[inline]
     fn rotate_right<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -0,0 +1,127 @@
+use word::{Word, UnsignedWord};
+use cpu;
+
+/// Position of the `k`-th set bit of `x` (in its least significant bit
+/// at position zero).
+///
+/// Returns the number of bits of `x` if `x` has `k` or fewer set bits.
+///
+/// # Keywords:
+///
+/// Select, rank, k-th one.
+///
+/// # Intrinsics:
+/// - BMI 2.0: pdep, tzcnt (`tzcnt(pdep(1 << k, x))`).
+///
+/// Without BMI2 the broadword algorithm of Vigna, "Broadword Implementation
+/// of Rank/Select Queries", 2008 is used.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let n = 0b1011_0100u8;
+///
+/// assert_eq!(n.select_in_word(0u8), 2);
+/// assert_eq!(select_in_word(n, 2u8), 5);
+/// assert_eq!(select_in_word(n, 3u8), 7);
+/// assert_eq!(select_in_word(n, 4u8), 8);
+/// ```
+#[inline]
+pub fn select_in_word<T: Word, U: UnsignedWord>(x: T, k: U) -> T {
+    let bits = T::bit_size().to_u32();
+    let k = if k >= U::from_u32(bits) { bits } else { k.to_u32() };
+    if k >= bits {
+        return T::bit_size();
+    }
+    if bits == 128 {
+        let x = x.to_u128();
+        let low = x as u64;
+        let low_ones = low.count_ones();
+        return T::from_u32(if k < low_ones {
+            select_u64(low, k)
+        } else {
+            64 + select_u64((x >> 64) as u64, k - low_ones)
+        });
+    }
+    if cpu::use_bmi2() {
+        (T::one() << T::from_u32(k)).pdep(x).trailing_zeros()
+    } else {
+        let x = x.to_unsigned().to_u64();
+        T::from_u32(select_u64(x, k).min(bits))
+    }
+}
+
+/// Broadword select of the `k`-th set bit of `x`, with `k < 64`. Returns `64`
+/// if `x` has `k` or fewer set bits.
+#[inline]
+fn select_u64(x: u64, k: u32) -> u32 {
+    const L8: u64 = 0x0101_0101_0101_0101;
+    const H8: u64 = 0x8080_8080_8080_8080;
+    // Number of ones of each byte:
+    let mut s = x - ((x >> 1) & 0x5555_5555_5555_5555);
+    s = (s & 0x3333_3333_3333_3333) + ((s >> 2) & 0x3333_3333_3333_3333);
+    s = (s + (s >> 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    // Byte `i` holds the number of ones of the bytes `0..=i`:
+    let sums = s.wrapping_mul(L8);
+    // The k-th one is in the byte after the bytes whose sums are <= k:
+    let le = (((k as u64 * L8) | H8) - sums) & H8;
+    let place = le.count_ones() * 8;
+    if place == 64 {
+        return 64;
+    }
+    let before = ((sums << 8) >> place) as u32 & 0xFF;
+    let mut byte = (x >> place) as u32 & 0xFF;
+    for _ in 0..k - before {
+        byte &= byte - 1;
+    }
+    place + byte.trailing_zeros()
+}
+
+/// Method version of [`select_in_word`](fn.select_in_word.html).
+pub trait SelectInWord {
+    fn select_in_word<U: UnsignedWord>(self, k: U) -> Self;
+}
+
+impl<T: Word> SelectInWord for T {
+    #[inline]
+    fn select_in_word<U: UnsignedWord>(self, k: U) -> Self {
+        select_in_word(self, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    fn select_naive(x: u128, bits: u32, k: u32) -> u32 {
+        (0..bits).filter(|&i| x & (1 << i) != 0).nth(k as usize).unwrap_or(bits)
+    }
+
+    #[test]
+    fn prop_select_in_word() {
+        fn inner(x: u64, y: u64, k: u8) -> bool {
+            let k = k as u32;
+            let z = (x as u128) << 64 | y as u128;
+            [x, !x, x & y].iter().all(|&x| {
+                select_in_word(x, k) as u32 == select_naive(x as u128, 64, k) &&
+                select_in_word(x as u8, k) as u32 == select_naive(x as u8 as u128, 8, k) &&
+                select_in_word(x as i16, k) as u32 == select_naive(x as u16 as u128, 16, k) &&
+                select_in_word(x as u32, k) == select_naive(x as u32 as u128, 32, k)
+            }) && select_in_word(z, k) as u32 == select_naive(z, 128, k)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u8) -> bool);
+    }
+
+    #[test]
+    fn all_ones() {
+        for k in 0..64u32 {
+            assert_eq!(select_in_word(!0u64, k), k as u64);
+            assert_eq!(select_u64(!0, k), k);
+        }
+        assert_eq!(select_in_word(!0u128, 127u8), 127);
+        assert_eq!(select_in_word(!0u128, 128u8), 128);
+    }
+}
@@ -24,7 +24,6 @@ pub fn set_bit<T: Word, U: UnsignedWord>(x: T, bit: U) -> T {
 
 /// Method version of [`set_bit`](fn.set_bit.html).
 pub trait SetBit {
-    #[inline]
     fn set_bit<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -24,7 +24,6 @@ pub fn set_bits_geq<T: Word, U: UnsignedWord>(x: T, bit: U) -> T {
 
 /// Method version of [`set_bits_geq`](fn.set_bits_geq.html).
 pub trait SetBitsGeq {
-    #[inline]
     fn set_bits_geq<U: UnsignedWord>(self, n: U) -> Self;
 }
 
@@ -23,7 +23,6 @@ pub fn set_least_significant_zero<T: Word>(x: T) -> T {
 
 /// Method version of [`set_least_significant_zero`](fn.set_least_significant_zero.html).
 pub trait SetLeastSignificantZero {
-    #[inline]
     fn set_least_significant_zero(self) -> Self;
 }
 
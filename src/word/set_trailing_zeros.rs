@@ -25,7 +25,6 @@ pub fn set_trailing_zeros<T: Word>(x: T) -> T {
 
 /// Method version of [`set_trailing_zeros`](fn.set_trailing_zeros.html).
 pub trait SetTrailingZeros {
-    #[inline]
     fn set_trailing_zeros(self) -> Self;
 }
 
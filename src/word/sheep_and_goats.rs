@@ -0,0 +1,88 @@
+use word::{Word, ToWord, compress_left};
+
+/// Moves the bits of `x` selected by `mask` to the most significant end of
+/// the result and the other bits to the least significant end, preserving
+/// the order of the bits within each group.
+///
+/// The result is `compress_left(x, mask) | parallel_bits_extract(x, !mask)`.
+/// Every bit permutation is a sequence of `log2(bit_size)` sheep-and-goats
+/// operations.
+///
+/// See also:
+/// [Hacker's Delight: the sheep and goats operation](http://icodeguru.com/Embedded/Hacker's-Delight/044.htm).
+///
+/// # Keywords:
+///
+/// Sheep and goats, SAG, GRP, stable partition of bits.
+///
+/// # Intrinsics:
+/// - BMI 2.0: pext.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let n = 0b1011_0110u8;
+/// //        abcd efgh
+/// let m = 0b0101_1010u8;
+/// let s = 0b0101_1110u8;
+/// //        bdeg acfh
+/// assert_eq!(n.sheep_and_goats(m), s);
+/// assert_eq!(sheep_and_goats(n, m), s);
+/// assert_eq!(sheep_and_goats(n, 0u8), n);
+/// assert_eq!(sheep_and_goats(n, !0u8), n);
+/// ```
+#[inline]
+pub fn sheep_and_goats<T: Word, U: Word>(x: T, mask: U) -> T {
+    let mask: T = mask.to();
+    compress_left(x, mask) | x.pext(!mask)
+}
+
+/// Method version of [`sheep_and_goats`](fn.sheep_and_goats.html).
+pub trait SheepAndGoats {
+    fn sheep_and_goats<U: Word>(self, mask: U) -> Self;
+}
+
+impl<T: Word> SheepAndGoats for T {
+    #[inline]
+    fn sheep_and_goats<U: Word>(self, mask: U) -> Self {
+        sheep_and_goats(self, mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use word::*;
+
+    #[test]
+    fn prop_sheep_and_goats() {
+        fn naive<T: Word>(x: T, mask: T) -> T {
+            // Selected bits from the top down, then unselected bits from the
+            // bottom up:
+            let (mut r, mut hi, mut lo) = (T::zero(), T::bit_size().to_u32(), 0u32);
+            for i in (0..T::bit_size().to_u32()).rev() {
+                if mask.test_bit(i) {
+                    hi -= 1;
+                    r = copy_bit(x, i, r, hi);
+                }
+            }
+            for i in 0..T::bit_size().to_u32() {
+                if !mask.test_bit(i) {
+                    r = copy_bit(x, i, r, lo);
+                    lo += 1;
+                }
+            }
+            r
+        }
+        fn inner(x: u64, mask: u64) -> bool {
+            sheep_and_goats(x, mask) == naive(x, mask) &&
+            sheep_and_goats(x as u8, mask as u8) == naive(x as u8, mask as u8) &&
+            sheep_and_goats(x as i16, mask as i16) == naive(x as i16, mask as i16) &&
+            sheep_and_goats(x as u128 * 3, mask as u128) == naive(x as u128 * 3, mask as u128)
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64) -> bool);
+    }
+}
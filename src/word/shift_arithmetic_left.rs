@@ -29,7 +29,6 @@ pub fn shift_arithmetic_left<T: Word, U: UnsignedWord>(x: T, n: U) -> T {
 
 /// Method version of [`shift_arithmetic_left`](fn.shift_arithmetic_left.html).
 pub trait SAL {
-    #[inline]
     fn shift_arithmetic_left<U: UnsignedWord>(self, n: U) -> Self;
 }
 
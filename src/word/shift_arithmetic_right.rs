@@ -29,7 +29,6 @@ pub fn shift_arithmetic_right<T: Word, U: UnsignedWord>(x: T, n: U) -> T {
 
 /// Method version of [`shift_arithmetic_right`](fn.shift_arithmetic_right.html).
 pub trait SAR {
-    #[inline]
     fn shift_arithmetic_right<U: UnsignedWord>(self, n: U) -> Self;
 }
 
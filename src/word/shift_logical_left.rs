@@ -26,7 +26,6 @@ pub fn shift_logical_left<T: Word, U: Word>(x: T, n: U) -> T {
 
 /// Method version of [`shift_logical_left`](fn.shift_logical_left.html).
 pub trait SLL {
-    #[inline]
     fn shift_logical_left<U: Word>(self, n: U) -> Self;
 }
 
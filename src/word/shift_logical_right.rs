@@ -28,7 +28,6 @@ pub fn shift_logical_right<T: Word, U: Word>(x: T, n: U) -> T {
 
 /// Method version of [`shift_logical_right`](fn.shift_logical_right.html).
 pub trait SLR {
-    #[inline]
     fn shift_logical_right<U: Word>(self, n: U) -> Self;
 }
 
@@ -0,0 +1,36 @@
+use word::{Word, suffix_or};
+
+/// Replaces each bit of `x` by the AND of itself and all the more
+/// significant bits of `x`.
+///
+/// Keeps the leading ones of `x`.
+///
+/// # Keywords:
+///
+/// Suffix AND, scan.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(suffix_and(0b1111_1111u8), 0b1111_1111);
+/// assert_eq!(suffix_and(0b1110_0111u8), 0b1110_0000);
+/// assert_eq!(0b0000_0001u8.suffix_and(), 0b0000_0000);
+/// ```
+#[inline]
+pub fn suffix_and<T: Word>(x: T) -> T {
+    !suffix_or(!x)
+}
+
+/// Method version of [`suffix_and`](fn.suffix_and.html).
+pub trait SuffixAnd {
+    fn suffix_and(self) -> Self;
+}
+
+impl<T: Word> SuffixAnd for T {
+    #[inline]
+    fn suffix_and(self) -> Self {
+        suffix_and(self)
+    }
+}
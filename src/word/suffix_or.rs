@@ -0,0 +1,44 @@
+use word::{Word, shift_logical_right};
+
+/// Replaces each bit of `x` by the OR of itself and all the more significant
+/// bits of `x`.
+///
+/// Sets all the bits below the most significant set bit of `x`.
+///
+/// # Keywords:
+///
+/// Suffix OR, scan, smear right.
+///
+/// # Intrinsics:
+/// - LZCNT: `!0 >> lzcnt(x)`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(suffix_or(0b0000_0000u8), 0b0000_0000);
+/// assert_eq!(suffix_or(0b0010_0100u8), 0b0011_1111);
+/// assert_eq!(0b0000_0001u8.suffix_or(), 0b0000_0001);
+/// assert_eq!((-1i8).suffix_or(), -1);
+/// ```
+#[inline]
+pub fn suffix_or<T: Word>(x: T) -> T {
+    if x == T::zero() {
+        x
+    } else {
+        shift_logical_right(!T::zero(), x.leading_zeros())
+    }
+}
+
+/// Method version of [`suffix_or`](fn.suffix_or.html).
+pub trait SuffixOr {
+    fn suffix_or(self) -> Self;
+}
+
+impl<T: Word> SuffixOr for T {
+    #[inline]
+    fn suffix_or(self) -> Self {
+        suffix_or(self)
+    }
+}
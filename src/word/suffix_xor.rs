@@ -0,0 +1,61 @@
+use word::{Word, shift_logical_right};
+use cpu;
+
+/// Replaces each bit of `x` by the XOR of itself and all the more
+/// significant bits of `x`.
+///
+/// Bit `i` of the result is the parity of the bits `i..` of `x`; this is
+/// [`from_gray`](fn.from_gray.html). It is computed with a carry-less
+/// multiplication by `!0` if the `pclmulqdq` target feature is enabled (see
+/// the [`cpu`](../cpu/index.html) module), and with `log2(bit_size)`
+/// shift-XOR steps otherwise.
+///
+/// # Keywords:
+///
+/// Suffix XOR, suffix parity, scan, Gray decoding.
+///
+/// # Intrinsics:
+/// - PCLMULQDQ: `pclmulqdq(x, !0) >> 63`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(suffix_xor(0b0000_0000u8), 0b0000_0000);
+/// assert_eq!(suffix_xor(0b1000_0000u8), 0b1111_1111);
+/// assert_eq!(0b0010_0100u8.suffix_xor(), 0b0011_1000);
+/// ```
+#[inline]
+pub fn suffix_xor<T: Word>(x: T) -> T {
+    let bits = T::bit_size().to_u32();
+    if bits <= 64 {
+        if let Some(p) = cpu::clmul(x.to_unsigned().to_u64(), !0) {
+            return T::from_u64((p >> 63) as u64);
+        }
+    } else if let (Some(low), Some(high)) = (cpu::clmul(x.to_u64(), !0),
+                                              cpu::clmul((x.to_u128() >> 64) as u64, !0)) {
+        let (low, high) = ((low >> 63) as u64, (high >> 63) as u64);
+        let carry = if high & 1 == 0 { 0 } else { !0 };
+        return T::from_u128(((high as u128) << 64) | (low ^ carry) as u128);
+    }
+    let mut x = x;
+    let mut shift = 1;
+    while shift < bits {
+        x = x ^ shift_logical_right(x, T::from_u32(shift));
+        shift *= 2;
+    }
+    x
+}
+
+/// Method version of [`suffix_xor`](fn.suffix_xor.html).
+pub trait SuffixXor {
+    fn suffix_xor(self) -> Self;
+}
+
+impl<T: Word> SuffixXor for T {
+    #[inline]
+    fn suffix_xor(self) -> Self {
+        suffix_xor(self)
+    }
+}
@@ -20,7 +20,6 @@ pub fn swap_bytes<T: Word>(x: T) -> T {
 
 /// Method version of [`swap_bytes`](fn.swap_bytes.html).
 pub trait SwapBytes {
-    #[inline]
     fn swap_bytes(self) -> Self;
 }
 
@@ -24,7 +24,6 @@ pub fn test_bit<T: Word, U: UnsignedWord>(x: T, bit: U) -> bool {
 
 /// Method version of [`test_bit`](fn.test_bit.html).
 pub trait TestBit {
-    #[inline]
     fn test_bit<U: UnsignedWord>(self, n: U) -> bool;
 }
 
@@ -0,0 +1,40 @@
+use word::{Word, shift_logical_right};
+
+/// Reflected binary Gray code of `x`.
+///
+/// The Gray codes of consecutive integers differ in exactly one bit,
+/// including the codes of the largest integer and of zero.
+///
+/// # Keywords:
+///
+/// Gray code, reflected binary code.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(to_gray(0u8), 0b000);
+/// assert_eq!(to_gray(1u8), 0b001);
+/// assert_eq!(to_gray(2u8), 0b011);
+/// assert_eq!(3u8.to_gray(), 0b010);
+/// assert_eq!(4u8.to_gray(), 0b110);
+/// assert_eq!(u8::MAX.to_gray(), 0b1000_0000);
+/// assert_eq!((-1i16).to_gray(), i16::MIN);
+/// ```
+#[inline]
+pub fn to_gray<T: Word>(x: T) -> T {
+    x ^ shift_logical_right(x, T::one())
+}
+
+/// Method version of [`to_gray`](fn.to_gray.html).
+pub trait ToGray {
+    fn to_gray(self) -> Self;
+}
+
+impl<T: Word> ToGray for T {
+    #[inline]
+    fn to_gray(self) -> Self {
+        to_gray(self)
+    }
+}
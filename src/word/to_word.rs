@@ -6,61 +6,31 @@ impl UnsignedWord for u8 {}
 impl UnsignedWord for u16 {}
 impl UnsignedWord for u32 {}
 impl UnsignedWord for u64 {}
+impl UnsignedWord for u128 {}
+impl UnsignedWord for usize {}
 
 /// From-like trait for words.
+///
+/// The conversion has the semantics of an `as` cast: it sign-extends signed
+/// words, zero-extends unsigned words, and truncates to the width of `Self`.
 pub trait FromWord<T> {
     /// Converts a `T` to `Self`.
-    #[inline]
-    fn from(T) -> Self;
-}
-
-macro_rules! impl_from_word_from_to {
-    ($From:ty, $To:ty) => (
-        impl FromWord<$From> for $To {
-            #[inline] fn from(x: $From) -> Self {
-                x as Self
-            }
-        }
-    )
-}
-
-macro_rules! impl_from_word_multiple {
-    ($From:ty, $To:ty) => (
-        impl_from_word_from_to!($From, $To);
-    );
-    ($From:ty, $To:ty $(, $Rest:ty)+) => (
-        impl_from_word_multiple!($From, $To);
-        impl_from_word_multiple!($From, $($Rest),*);
-    )
+    fn from(x: T) -> Self;
 }
 
-macro_rules! impl_from_word {
-    ($From:ty) => (
-        impl_from_word_multiple!($From, i8, i16, i32, i64,
-                                 u8, u16, u32, u64);
-    )
-}
-
-impl_from_word!(i8);
-impl_from_word!(i16);
-impl_from_word!(i32);
-impl_from_word!(i64);
-impl_from_word!(u8);
-impl_from_word!(u16);
-impl_from_word!(u32);
-impl_from_word!(u64);
-
+// Widening to the largest signed word and truncating back is equivalent to a
+// direct `as` cast between any pair of words, so a single blanket impl covers
+// every conversion without requiring specialization.
 impl<T: Word, U: Word> FromWord<T> for U {
     #[inline]
-    default fn from(x: T) -> Self {
-        ToWord::to(x)
+    fn from(x: T) -> Self {
+        U::from_i128(x.to_i128())
     }
 }
 
 /// Into-like trait for words.
 pub trait ToWord<T> {
     /// Converts self to `T`.
-    #[inline]
     fn to(self) -> T;
 }
 
@@ -73,3 +43,58 @@ impl<T, U> ToWord<U> for T
         U::from(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use word::*;
+    use quickcheck::{TestResult, QuickCheck};
+
+    macro_rules! prop_to_word_tests {
+        ($($name:ident: $From:ty => $($To:ty),*;)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    fn inner(x: $From) -> TestResult {
+                        TestResult::from_bool(true $(&& ToWord::<$To>::to(x) == x as $To)*)
+                    }
+                    QuickCheck::new().quickcheck(inner as fn($From) -> TestResult);
+                }
+            )*
+        }
+    }
+
+    prop_to_word_tests! {
+        prop_to_word_u8: u8 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_u16: u16 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_u32: u32 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_u64: u64 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_i8: i8 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_i16: i16 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_i32: i32 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_usize: usize => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_isize: isize => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_i64: i64 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+    }
+
+    // quickcheck does not generate 128-bit integers: build them from two
+    // 64-bit halves.
+    macro_rules! prop_wide_to_word_tests {
+        ($($name:ident: $From:ty => $($To:ty),*;)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    fn inner(high: u64, low: u64) -> TestResult {
+                        let x = ((high as u128) << 64 | low as u128) as $From;
+                        TestResult::from_bool(true $(&& ToWord::<$To>::to(x) == x as $To)*)
+                    }
+                    QuickCheck::new().quickcheck(inner as fn(u64, u64) -> TestResult);
+                }
+            )*
+        }
+    }
+
+    prop_wide_to_word_tests! {
+        prop_to_word_u128: u128 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        prop_to_word_i128: i128 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+    }
+}
@@ -0,0 +1,210 @@
+use word::{UnsignedWord, delta_swap, shift_logical_right};
+
+/// Transposes the 8x8 bit matrix `x`.
+///
+/// The byte `r` of `x` is the row `r` of the matrix and its bit `c` is the
+/// column `c`, that is, the element `(r, c)` is the bit `8 * r + c`. The
+/// element `(r, c)` of the result is the element `(c, r)` of `x`.
+///
+/// In chess programming terms this is the flip about the a1-h8 diagonal of a
+/// bitboard.
+///
+/// See also:
+/// [Chess Programming Wiki: flip about the diagonal](https://www.chessprogramming.org/Flipping_Mirroring_and_Rotating#Diagonal).
+///
+/// # Keywords:
+///
+/// Transpose, bit matrix, bitboard, flip diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// // The first row becomes the first column:
+/// assert_eq!(transpose_8x8(0xFF), 0x0101_0101_0101_0101);
+/// assert_eq!(transpose_8x8(0x0101_0101_0101_0101), 0xFF);
+/// // The diagonal is left unchanged:
+/// assert_eq!(transpose_8x8(0x8040_2010_0804_0201), 0x8040_2010_0804_0201);
+/// // The element (0, 1) becomes the element (1, 0):
+/// assert_eq!(transpose_8x8(1 << 1), 1 << 8);
+/// ```
+#[inline]
+pub fn transpose_8x8(x: u64) -> u64 {
+    // Swap the 4x4 blocks, the 2x2 blocks within each of them, and the
+    // elements within each 2x2 block:
+    let x = delta_swap(x, 0x0000_0000_F0F0_F0F0, 28u32);
+    let x = delta_swap(x, 0x0000_CCCC_0000_CCCC, 14u32);
+    delta_swap(x, 0x00AA_00AA_00AA_00AA, 7u32)
+}
+
+/// Transposes in place the square bit matrix `rows` of `T::bit_size()` rows
+/// with the recursive swap method.
+///
+/// At each step the upper right and lower left `j x j` blocks of every
+/// `2j x 2j` block are swapped, for `j = bit_size / 2, ..., 2, 1`.
+fn transpose_block<T: UnsignedWord>(rows: &mut [T]) {
+    let n = T::bit_size().to_usize();
+    debug_assert!(rows.len() == n);
+    let mut j = n / 2;
+    let mut mask = shift_logical_right(!T::zero(), T::from_u32(j as u32));
+    while j != 0 {
+        let shift = T::from_u32(j as u32);
+        for k in 0..n {
+            if k & j == 0 {
+                let t = (shift_logical_right(rows[k], shift) ^ rows[k + j]) & mask;
+                rows[k + j] = rows[k + j] ^ t;
+                rows[k] = rows[k] ^ (t << shift);
+            }
+        }
+        j /= 2;
+        mask = mask ^ (mask << T::from_u32(j as u32));
+    }
+}
+
+/// Transposes the 16x16 bit matrix `m` in place.
+///
+/// The word `m[r]` is the row `r` of the matrix and its bit `c` is the column
+/// `c`. The element `(r, c)` of the result is the element `(c, r)` of `m`.
+///
+/// See also:
+/// [Hacker's Delight: transposing a bit matrix](http://icodeguru.com/Embedded/Hacker's-Delight/048.htm).
+///
+/// # Keywords:
+///
+/// Transpose, bit matrix.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let mut m = [0u16; 16];
+/// m[0] = 0xFFFF;
+/// m[3] = 1 << 7;
+/// transpose_16x16(&mut m);
+/// assert!(m.iter().all(|&r| r & 1 == 1));
+/// assert_eq!(m[7], 1 | 1 << 3);
+/// ```
+#[inline]
+pub fn transpose_16x16(m: &mut [u16; 16]) {
+    transpose_block(m)
+}
+
+/// Transposes the 32x32 bit matrix `m` in place.
+///
+/// The word `m[r]` is the row `r` of the matrix and its bit `c` is the column
+/// `c`. The element `(r, c)` of the result is the element `(c, r)` of `m`.
+///
+/// See also:
+/// [Hacker's Delight: transposing a bit matrix](http://icodeguru.com/Embedded/Hacker's-Delight/048.htm).
+///
+/// # Keywords:
+///
+/// Transpose, bit matrix.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let mut m = [0u32; 32];
+/// m[31] = 1;
+/// m[5] = 1 << 20;
+/// transpose_32x32(&mut m);
+/// assert_eq!(m[0], 1 << 31);
+/// assert_eq!(m[20], 1 << 5);
+/// ```
+#[inline]
+pub fn transpose_32x32(m: &mut [u32; 32]) {
+    transpose_block(m)
+}
+
+/// Transposes the 64x64 bit matrix `m` in place.
+///
+/// The word `m[r]` is the row `r` of the matrix and its bit `c` is the column
+/// `c`. The element `(r, c)` of the result is the element `(c, r)` of `m`.
+///
+/// See also:
+/// [Hacker's Delight: transposing a bit matrix](http://icodeguru.com/Embedded/Hacker's-Delight/048.htm).
+///
+/// # Keywords:
+///
+/// Transpose, bit matrix.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// let mut m = [0u64; 64];
+/// for (r, row) in m.iter_mut().enumerate() {
+///     *row = 1 << (63 - r);
+/// }
+/// let anti_diagonal = m;
+/// transpose_64x64(&mut m);
+/// assert_eq!(m, anti_diagonal);
+/// m[0] = !0;
+/// transpose_64x64(&mut m);
+/// assert!(m.iter().all(|&r| r & 1 == 1));
+/// ```
+#[inline]
+pub fn transpose_64x64(m: &mut [u64; 64]) {
+    transpose_block(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use test_util::random;
+    use word::*;
+
+    fn naive<T: Word>(rows: &[T]) -> [T; 64] {
+        let mut r = [T::zero(); 64];
+        for (i, &row) in rows.iter().enumerate() {
+            for (j, t) in r.iter_mut().enumerate().take(rows.len()) {
+                if row.test_bit(j as u32) {
+                    *t = t.set_bit(i as u32);
+                }
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn prop_transpose_8x8() {
+        fn inner(x: u64) -> bool {
+            let rows: [u8; 8] = x.to_le_bytes();
+            let t = naive(&rows);
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&t[..8]);
+            transpose_8x8(x) == u64::from_le_bytes(bytes) && transpose_8x8(transpose_8x8(x)) == x
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64) -> bool);
+    }
+
+    #[test]
+    fn prop_transpose_blocks() {
+        fn inner(seed: u64) -> bool {
+            let mut next = random(seed);
+            let mut m16 = [0u16; 16];
+            let mut m32 = [0u32; 32];
+            let mut m64 = [0u64; 64];
+            for (r, x) in m16.iter_mut().zip(&mut next) { *r = x as u16; }
+            for (r, x) in m32.iter_mut().zip(&mut next) { *r = x as u32; }
+            for (r, x) in m64.iter_mut().zip(&mut next) { *r = x as u64; }
+            let (t16, t32, t64) = (naive(&m16), naive(&m32), naive(&m64));
+            let (o16, o32, o64) = (m16, m32, m64);
+            transpose_16x16(&mut m16);
+            transpose_32x32(&mut m32);
+            transpose_64x64(&mut m64);
+            let ok = m16[..] == t16[..16] && m32[..] == t32[..32] && m64[..] == t64[..];
+            transpose_16x16(&mut m16);
+            transpose_32x32(&mut m32);
+            transpose_64x64(&mut m64);
+            ok && m16 == o16 && m32 == o32 && m64[..] == o64[..]
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64) -> bool);
+    }
+}
@@ -23,74 +23,216 @@ pub trait Word
     + Shr<Self, Output=Self>
     + Shl<Self, Output=Self>
     + PartialEq + PartialOrd
-    + Bzhi
-    + Blsr
-    + Blcfill
-    + Lzcnt
-    + Popcnt
-    + Tzcnt
-    + Bextr
-    + Popcnt
-    + Blsi
-    + Blcic
-    + Blcmsk
-    + T1mskc
-    + Blsmsk
-    + Tzmsk
-    + Pdep
-    + Pext
-    + Rbit
-    + Blcs
-    + Blsfill
 {
     type Unsigned: Word;
     type Signed: Word;
-    #[inline] fn one() -> Self;
-    #[inline] fn zero() -> Self;
-    #[inline] fn byte_size() -> Self;
-    #[inline] fn bit_size() -> Self;
-    #[inline] fn count_ones(self) -> Self;
-    #[inline] fn count_zeros(self) -> Self;
-    #[inline] fn leading_zeros(self) -> Self;
-    #[inline] fn trailing_zeros(self) -> Self;
-    #[inline] fn wrapping_neg(self) -> Self;
-    #[inline] fn wrapping_add(self, Self) -> Self;
-    #[inline] fn wrapping_sub(self, Self) -> Self;
-    #[inline] fn wrapping_shl(self, Self) -> Self;
-    #[inline] fn wrapping_shr(self, Self) -> Self;
-    #[inline] fn to_u8(self) -> u8;
-    #[inline] fn to_u16(self) -> u16;
-    #[inline] fn to_u32(self) -> u32;
-    #[inline] fn to_u64(self) -> u64;
-    #[inline] fn to_i8(self) -> i8;
-    #[inline] fn to_i16(self) -> i16;
-    #[inline] fn to_i32(self) -> i32;
-    #[inline] fn to_i64(self) -> i64;
-    #[inline] fn from_u8(u8) -> Self;
-    #[inline] fn from_u16(u16) -> Self;
-    #[inline] fn from_u32(u32) -> Self;
-    #[inline] fn from_u64(u64) -> Self;
-    #[inline] fn from_i8(i8) -> Self;
-    #[inline] fn from_i16(i16) -> Self;
-    #[inline] fn from_i32(i32) -> Self;
-    #[inline] fn from_i64(i64) -> Self;
-    #[inline] fn rotate_left(self, u32) -> Self;
-    #[inline] fn rotate_right(self, u32) -> Self;
-    #[inline] fn swap_bytes(self) -> Self;
-    #[inline] fn from_be(self) -> Self;
-    #[inline] fn from_le(self) -> Self;
-    #[inline] fn to_be(self) -> Self;
-    #[inline] fn to_le(self) -> Self;
-    #[inline] fn pow(self, exp: u32) -> Self;
-    #[inline] fn to_unsigned(self) -> Self::Unsigned;
-    #[inline] fn to_signed(self) -> Self::Signed;
-    #[inline] fn from_unsigned(Self::Unsigned) -> Self;
-    #[inline] fn from_signed(Self::Signed) -> Self;
-    #[inline] fn to_usize(self) -> usize;
+    fn one() -> Self;
+    fn zero() -> Self;
+    fn byte_size() -> Self;
+    fn bit_size() -> Self;
+    fn count_ones(self) -> Self;
+    fn count_zeros(self) -> Self;
+    fn leading_zeros(self) -> Self;
+    fn trailing_zeros(self) -> Self;
+    fn wrapping_neg(self) -> Self;
+    fn wrapping_add(self, o: Self) -> Self;
+    fn wrapping_sub(self, o: Self) -> Self;
+    fn wrapping_shl(self, o: Self) -> Self;
+    fn wrapping_shr(self, o: Self) -> Self;
+    fn to_u8(self) -> u8;
+    fn to_u16(self) -> u16;
+    fn to_u32(self) -> u32;
+    fn to_u64(self) -> u64;
+    fn to_i8(self) -> i8;
+    fn to_i16(self) -> i16;
+    fn to_i32(self) -> i32;
+    fn to_i64(self) -> i64;
+    fn to_u128(self) -> u128;
+    fn to_i128(self) -> i128;
+    fn from_u8(x: u8) -> Self;
+    fn from_u16(x: u16) -> Self;
+    fn from_u32(x: u32) -> Self;
+    fn from_u64(x: u64) -> Self;
+    fn from_i8(x: i8) -> Self;
+    fn from_i16(x: i16) -> Self;
+    fn from_i32(x: i32) -> Self;
+    fn from_i64(x: i64) -> Self;
+    fn from_u128(x: u128) -> Self;
+    fn from_i128(x: i128) -> Self;
+    fn rotate_left(self, n: u32) -> Self;
+    fn rotate_right(self, n: u32) -> Self;
+    fn swap_bytes(self) -> Self;
+    #[allow(clippy::wrong_self_convention)]
+    fn from_be(self) -> Self;
+    #[allow(clippy::wrong_self_convention)]
+    fn from_le(self) -> Self;
+    fn to_be(self) -> Self;
+    fn to_le(self) -> Self;
+    fn pow(self, exp: u32) -> Self;
+    fn to_unsigned(self) -> Self::Unsigned;
+    fn to_signed(self) -> Self::Signed;
+    fn from_unsigned(x: Self::Unsigned) -> Self;
+    fn from_signed(x: Self::Signed) -> Self;
+    fn to_usize(self) -> usize;
+
+    // Bit manipulation intrinsics, see the `bitintr` crate for their
+    // semantics. These are methods of `Word` instead of supertraits because
+    // `bitintr` does not implement them for all word types.
+    fn bzhi(self, bit_position: u32) -> Self;
+    fn bextr(self, start: Self, length: Self) -> Self;
+    fn blcfill(self) -> Self;
+    fn blcic(self) -> Self;
+    fn blcmsk(self) -> Self;
+    fn blcs(self) -> Self;
+    fn blsfill(self) -> Self;
+    fn blsi(self) -> Self;
+    fn blsmsk(self) -> Self;
+    fn blsr(self) -> Self;
+    fn t1mskc(self) -> Self;
+    fn tzmsk(self) -> Self;
+    fn lzcnt(self) -> Self;
+    fn tzcnt(self) -> Self;
+    fn popcnt(self) -> Self;
+    fn pdep(self, mask: Self) -> Self;
+    fn pext(self, mask: Self) -> Self;
+    fn rbit(self) -> Self;
+}
+
+/// Implements the intrinsics of `Word` using the `bitintr` crate.
+macro_rules! bitintr_impl {
+    ($T:ty, $UT:ty) => (
+        #[inline] fn bzhi(self, bit_position: u32) -> Self {
+            <$T as Bzhi>::bzhi(self, bit_position)
+        }
+        #[inline] fn bextr(self, start: Self, length: Self) -> Self {
+            <$T as Bextr>::bextr(self, start, length)
+        }
+        #[inline] fn blcfill(self) -> Self { <$T as Blcfill>::blcfill(self) }
+        #[inline] fn blcic(self) -> Self { <$T as Blcic>::blcic(self) }
+        #[inline] fn blcmsk(self) -> Self { <$T as Blcmsk>::blcmsk(self) }
+        #[inline] fn blcs(self) -> Self { <$T as Blcs>::blcs(self) }
+        #[inline] fn blsfill(self) -> Self { <$T as Blsfill>::blsfill(self) }
+        #[inline] fn blsi(self) -> Self { <$T as Blsi>::blsi(self) }
+        #[inline] fn blsmsk(self) -> Self { <$T as Blsmsk>::blsmsk(self) }
+        #[inline] fn blsr(self) -> Self { <$T as Blsr>::blsr(self) }
+        #[inline] fn t1mskc(self) -> Self { <$T as T1mskc>::t1mskc(self) }
+        #[inline] fn tzmsk(self) -> Self { <$T as Tzmsk>::tzmsk(self) }
+        #[inline] fn lzcnt(self) -> Self { <$T as Lzcnt>::lzcnt(self) }
+        #[inline] fn tzcnt(self) -> Self { <$T as Tzcnt>::tzcnt(self) }
+        #[inline] fn popcnt(self) -> Self { <$T as Popcnt>::popcnt(self) }
+        // The BMI2 instructions might be selected at run-time (see the `cpu`
+        // module). The software fallbacks of `bitintr` overflow when every
+        // bit of the mask is set, in which case both operations are the
+        // identity, and on signed words, which are done as unsigned words.
+        #[inline] fn pdep(self, mask: Self) -> Self {
+            if let Some(r) = ::cpu::pdep(self as $UT as u64, mask as $UT as u64) {
+                r as $T
+            } else if mask == !0 {
+                self
+            } else {
+                <$UT as Pdep>::pdep(self as $UT, mask as $UT) as $T
+            }
+        }
+        #[inline] fn pext(self, mask: Self) -> Self {
+            if let Some(r) = ::cpu::pext(self as $UT as u64, mask as $UT as u64) {
+                r as $T
+            } else if mask == !0 {
+                self
+            } else {
+                <$UT as Pext>::pext(self as $UT, mask as $UT) as $T
+            }
+        }
+        #[inline] fn rbit(self) -> Self { <$T as Rbit>::rbit(self) }
+    )
+}
+
+/// Implements the intrinsics of `Word` for 128-bit words.
+///
+/// The scatter/gather and bit reversal intrinsics operate on the two 64-bit
+/// halves of the word using `bitintr`.
+macro_rules! wide_impl {
+    ($T:ty, $UT:ty) => (
+        #[inline] fn bzhi(self, bit_position: u32) -> Self {
+            debug_assert!(bit_position < 128);
+            self & (1 as $T).wrapping_shl(bit_position).wrapping_sub(1)
+        }
+        #[inline] fn bextr(self, start: Self, length: Self) -> Self {
+            (self >> start) & (1 as $T).wrapping_shl(length as u32).wrapping_sub(1)
+        }
+        #[inline] fn blcfill(self) -> Self { self & self.wrapping_add(1) }
+        #[inline] fn blcic(self) -> Self { !self & self.wrapping_add(1) }
+        #[inline] fn blcmsk(self) -> Self { self ^ self.wrapping_add(1) }
+        #[inline] fn blcs(self) -> Self { self | self.wrapping_add(1) }
+        #[inline] fn blsfill(self) -> Self { self | self.wrapping_sub(1) }
+        #[inline] fn blsi(self) -> Self { self & self.wrapping_neg() }
+        #[inline] fn blsmsk(self) -> Self { self ^ self.wrapping_sub(1) }
+        #[inline] fn blsr(self) -> Self {
+            debug_assert!(self != 0);
+            self & self.wrapping_sub(1)
+        }
+        #[inline] fn t1mskc(self) -> Self { !self | self.wrapping_add(1) }
+        #[inline] fn tzmsk(self) -> Self { !self & self.wrapping_sub(1) }
+        #[inline] fn lzcnt(self) -> Self { self.leading_zeros() as $T }
+        #[inline] fn tzcnt(self) -> Self { self.trailing_zeros() as $T }
+        #[inline] fn popcnt(self) -> Self { self.count_ones() as $T }
+        #[inline] fn pdep(self, mask: Self) -> Self {
+            let (x, m) = (self as u128, mask as u128);
+            let (m_lo, m_hi) = (m as u64, (m >> 64) as u64);
+            let lo = Word::pdep(x as u64, m_lo);
+            let hi = Word::pdep((x >> m_lo.count_ones()) as u64, m_hi);
+            (((hi as u128) << 64) | lo as u128) as $T
+        }
+        #[inline] fn pext(self, mask: Self) -> Self {
+            let (x, m) = (self as u128, mask as u128);
+            let (m_lo, m_hi) = (m as u64, (m >> 64) as u64);
+            let lo = Word::pext(x as u64, m_lo);
+            let hi = Word::pext((x >> 64) as u64, m_hi);
+            (((hi as u128) << m_lo.count_ones()) | lo as u128) as $T
+        }
+        #[inline] fn rbit(self) -> Self {
+            let x = self as u128;
+            let lo = Rbit::rbit(x as u64);
+            let hi = Rbit::rbit((x >> 64) as u64);
+            (((lo as u128) << 64) | hi as u128) as $T
+        }
+    )
+}
+
+/// Implements the intrinsics of `Word` by casting to the fixed-width word
+/// `$Via` of the same size (used for the pointer-sized words).
+macro_rules! cast_impl {
+    ($T:ty, $UT:ty, $Via:ty) => (
+        #[inline] fn bzhi(self, bit_position: u32) -> Self {
+            <$Via as Bzhi>::bzhi(self as $Via, bit_position) as $T
+        }
+        #[inline] fn bextr(self, start: Self, length: Self) -> Self {
+            <$Via as Bextr>::bextr(self as $Via, start as $Via, length as $Via) as $T
+        }
+        #[inline] fn blcfill(self) -> Self { <$Via as Blcfill>::blcfill(self as $Via) as $T }
+        #[inline] fn blcic(self) -> Self { <$Via as Blcic>::blcic(self as $Via) as $T }
+        #[inline] fn blcmsk(self) -> Self { <$Via as Blcmsk>::blcmsk(self as $Via) as $T }
+        #[inline] fn blcs(self) -> Self { <$Via as Blcs>::blcs(self as $Via) as $T }
+        #[inline] fn blsfill(self) -> Self { <$Via as Blsfill>::blsfill(self as $Via) as $T }
+        #[inline] fn blsi(self) -> Self { <$Via as Blsi>::blsi(self as $Via) as $T }
+        #[inline] fn blsmsk(self) -> Self { <$Via as Blsmsk>::blsmsk(self as $Via) as $T }
+        #[inline] fn blsr(self) -> Self { <$Via as Blsr>::blsr(self as $Via) as $T }
+        #[inline] fn t1mskc(self) -> Self { <$Via as T1mskc>::t1mskc(self as $Via) as $T }
+        #[inline] fn tzmsk(self) -> Self { <$Via as Tzmsk>::tzmsk(self as $Via) as $T }
+        #[inline] fn lzcnt(self) -> Self { <$Via as Lzcnt>::lzcnt(self as $Via) as $T }
+        #[inline] fn tzcnt(self) -> Self { <$Via as Tzcnt>::tzcnt(self as $Via) as $T }
+        #[inline] fn popcnt(self) -> Self { <$Via as Popcnt>::popcnt(self as $Via) as $T }
+        #[inline] fn pdep(self, mask: Self) -> Self {
+            Word::pdep(self as $Via, mask as $Via) as $T
+        }
+        #[inline] fn pext(self, mask: Self) -> Self {
+            Word::pext(self as $Via, mask as $Via) as $T
+        }
+        #[inline] fn rbit(self) -> Self { <$Via as Rbit>::rbit(self as $Via) as $T }
+    )
 }
 
 macro_rules! int_impl {
-    ($T:ty, $UT:ty, $ST:ty) => (
+    ($T:ty, $UT:ty, $ST:ty, $intrinsics:ident $(, $Via:ty)*) => (
         impl Word for $T {
             type Unsigned = $UT;
             type Signed = $ST;
@@ -141,6 +283,8 @@ macro_rules! int_impl {
             #[inline] fn to_i16(self) -> i16 { self as i16 }
             #[inline] fn to_i32(self) -> i32 { self as i32 }
             #[inline] fn to_i64(self) -> i64 { self as i64 }
+            #[inline] fn to_u128(self) -> u128 { self as u128 }
+            #[inline] fn to_i128(self) -> i128 { self as i128 }
             #[inline] fn from_u8(x: u8) -> Self { x as Self }
             #[inline] fn from_u16(x: u16) -> Self { x as Self }
             #[inline] fn from_u32(x: u32) -> Self { x as Self }
@@ -149,6 +293,8 @@ macro_rules! int_impl {
             #[inline] fn from_i16(x: i16) -> Self { x as Self }
             #[inline] fn from_i32(x: i32) -> Self { x as Self }
             #[inline] fn from_i64(x: i64) -> Self { x as Self }
+            #[inline] fn from_u128(x: u128) -> Self { x as Self }
+            #[inline] fn from_i128(x: i128) -> Self { x as Self }
 
             #[inline] fn rotate_left(self, n: u32) -> Self { (self as $T).rotate_left(n) }
             #[inline] fn rotate_right(self, n: u32) -> Self { (self as $T).rotate_right(n) }
@@ -190,17 +336,90 @@ macro_rules! int_impl {
             #[inline] fn to_usize(self) -> usize {
                 self as usize
             }
+
+            $intrinsics!($T, $UT $(, $Via)*);
         }
     )
 }
 
 
-int_impl!(u8, u8, i8);
-int_impl!(u16, u16, i16);
-int_impl!(u32, u32, i32);
-int_impl!(u64, u64, i64);
+int_impl!(u8, u8, i8, bitintr_impl);
+int_impl!(u16, u16, i16, bitintr_impl);
+int_impl!(u32, u32, i32, bitintr_impl);
+int_impl!(u64, u64, i64, bitintr_impl);
+int_impl!(u128, u128, i128, wide_impl);
+
+int_impl!(i8, u8, i8, bitintr_impl);
+int_impl!(i16, u16, i16, bitintr_impl);
+int_impl!(i32, u32, i32, bitintr_impl);
+int_impl!(i64, u64, i64, bitintr_impl);
+int_impl!(i128, u128, i128, wide_impl);
+
+#[cfg(target_pointer_width = "16")]
+int_impl!(usize, usize, isize, cast_impl, u16);
+#[cfg(target_pointer_width = "16")]
+int_impl!(isize, usize, isize, cast_impl, i16);
+#[cfg(target_pointer_width = "32")]
+int_impl!(usize, usize, isize, cast_impl, u32);
+#[cfg(target_pointer_width = "32")]
+int_impl!(isize, usize, isize, cast_impl, i32);
+#[cfg(target_pointer_width = "64")]
+int_impl!(usize, usize, isize, cast_impl, u64);
+#[cfg(target_pointer_width = "64")]
+int_impl!(isize, usize, isize, cast_impl, i64);
+
+#[cfg(test)]
+mod tests {
+    use word::*;
+    use quickcheck::{TestResult, QuickCheck};
 
-int_impl!(i8, u8, i8);
-int_impl!(i16, u16, i16);
-int_impl!(i32, u32, i32);
-int_impl!(i64, u64, i64);
+    fn wide(hi: u64, lo: u64) -> u128 {
+        ((hi as u128) << 64) | lo as u128
+    }
+
+    // Reference implementations of the 128-bit intrinsics, one bit at a time.
+    fn pdep_ref(x: u128, mask: u128) -> u128 {
+        let mut r = 0;
+        let mut k = 0;
+        for i in 0..128 {
+            if mask & (1 << i) != 0 {
+                r |= ((x >> k) & 1) << i;
+                k += 1;
+            }
+        }
+        r
+    }
+
+    fn pext_ref(x: u128, mask: u128) -> u128 {
+        let mut r = 0;
+        let mut k = 0;
+        for i in 0..128 {
+            if mask & (1 << i) != 0 {
+                r |= ((x >> i) & 1) << k;
+                k += 1;
+            }
+        }
+        r
+    }
+
+    fn rbit_ref(x: u128) -> u128 {
+        (0..128).fold(0, |r, i| r | (((x >> i) & 1) << (127 - i)))
+    }
+
+    #[test]
+    fn prop_wide_intrinsics() {
+        fn inner(x_hi: u64, x_lo: u64, m_hi: u64, m_lo: u64) -> TestResult {
+            let (x, m) = (wide(x_hi, x_lo), wide(m_hi, m_lo));
+            TestResult::from_bool(
+                x.pdep(m) == pdep_ref(x, m) && x.pext(m) == pext_ref(x, m) &&
+                x.rbit() == rbit_ref(x) &&
+                (x as i128).pdep(m as i128) == pdep_ref(x, m) as i128 &&
+                (x as i128).pext(m as i128) == pext_ref(x, m) as i128 &&
+                x.popcnt() == (x_hi.count_ones() + x_lo.count_ones()) as u128 &&
+                x.bzhi(m_lo as u32 % 128) == x & ((1 << (m_lo % 128)) - 1) &&
+                x.bextr(m_lo as u128 % 64, m_hi as u128 % 64) ==
+                    (x >> (m_lo % 64)) & ((1 << (m_hi % 64)) - 1))
+        }
+        QuickCheck::new().quickcheck(inner as fn(u64, u64, u64, u64) -> TestResult);
+    }
+}
@@ -0,0 +1,84 @@
+use word::Word;
+
+/// Maps the unsigned integer `x` back to the two's complement integer it was
+/// [`zigzag_encode`](fn.zigzag_encode.html)d from: `0, 1, 2, 3, 4, ...` map
+/// to `0, -1, 1, -2, 2, ...`.
+///
+/// Signed `x` is interpreted as an unsigned integer.
+///
+/// # Keywords:
+///
+/// Zigzag decoding, varint, protobuf `sint`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(zigzag_decode(0u8), 0i8);
+/// assert_eq!(zigzag_decode(1u8), -1i8);
+/// assert_eq!(2u32.zigzag_decode(), 1i32);
+/// assert_eq!(3u64.zigzag_decode(), -2i64);
+/// assert_eq!(u16::MAX.zigzag_decode(), i16::MIN);
+/// assert_eq!((-1i16).zigzag_decode(), i16::MIN);
+/// ```
+#[inline]
+pub fn zigzag_decode<T: Word>(x: T) -> T::Signed {
+    let half = T::from_unsigned(x.to_unsigned() >> T::Unsigned::one());
+    (half ^ T::zero().wrapping_sub(x & T::one())).to_signed()
+}
+
+/// Method version of [`zigzag_decode`](fn.zigzag_decode.html).
+pub trait ZigzagDecode: Word {
+    fn zigzag_decode(self) -> Self::Signed;
+}
+
+impl<T: Word> ZigzagDecode for T {
+    #[inline]
+    fn zigzag_decode(self) -> T::Signed {
+        zigzag_decode(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use word::*;
+    use quickcheck::QuickCheck;
+
+    macro_rules! prop_zigzag_tests {
+        ($($name:ident: $ST:ty, $UT:ty,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    fn inner(x: $ST) -> bool {
+                        let e = x.zigzag_encode();
+                        let naive = if x < 0 {
+                            (!(x as $UT)) * 2 + 1
+                        } else {
+                            (x as $UT) * 2
+                        };
+                        e == naive && e.zigzag_decode() == x &&
+                        (x as $UT).zigzag_encode() == e && (e as $ST).zigzag_decode() == x
+                    }
+                    QuickCheck::new().quickcheck(inner as fn($ST) -> bool);
+                }
+            )*
+        }
+    }
+
+    prop_zigzag_tests! {
+        prop_zigzag_i8: i8, u8,
+        prop_zigzag_i16: i16, u16,
+        prop_zigzag_i32: i32, u32,
+        prop_zigzag_i64: i64, u64,
+        prop_zigzag_isize: isize, usize,
+    }
+
+    #[test]
+    fn zigzag_i128() {
+        for &x in &[0i128, 1, -1, 63, -64, i128::MAX, i128::MIN, 1 << 100, -(1 << 100)] {
+            assert_eq!(x.zigzag_encode().zigzag_decode(), x);
+        }
+        assert_eq!(i128::MIN.zigzag_encode(), u128::MAX);
+    }
+}
@@ -0,0 +1,42 @@
+use word::Word;
+
+/// Maps the two's complement integer `x` to an unsigned integer, so that
+/// integers of small magnitude map to small integers: `0, -1, 1, -2, 2, ...`
+/// map to `0, 1, 2, 3, 4, ...`.
+///
+/// Unsigned `x` is interpreted as a two's complement integer.
+///
+/// # Keywords:
+///
+/// Zigzag encoding, varint, protobuf `sint`.
+///
+/// # Examples
+///
+/// ```
+/// use bitwise::word::*;
+///
+/// assert_eq!(zigzag_encode(0i8), 0u8);
+/// assert_eq!(zigzag_encode(-1i8), 1u8);
+/// assert_eq!(1i32.zigzag_encode(), 2u32);
+/// assert_eq!((-2i64).zigzag_encode(), 3u64);
+/// assert_eq!(i16::MAX.zigzag_encode(), u16::MAX - 1);
+/// assert_eq!(i16::MIN.zigzag_encode(), u16::MAX);
+/// assert_eq!(u32::MAX.zigzag_encode(), 1);
+/// ```
+#[inline]
+pub fn zigzag_encode<T: Word>(x: T) -> T::Unsigned {
+    let sign = T::from_unsigned(x.to_unsigned() >> (T::Unsigned::bit_size() - T::Unsigned::one()));
+    ((x << T::one()) ^ T::zero().wrapping_sub(sign)).to_unsigned()
+}
+
+/// Method version of [`zigzag_encode`](fn.zigzag_encode.html).
+pub trait ZigzagEncode: Word {
+    fn zigzag_encode(self) -> Self::Unsigned;
+}
+
+impl<T: Word> ZigzagEncode for T {
+    #[inline]
+    fn zigzag_encode(self) -> T::Unsigned {
+        zigzag_encode(self)
+    }
+}